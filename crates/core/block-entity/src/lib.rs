@@ -3,7 +3,8 @@
 use std::{any::TypeId, fmt::Debug};
 
 use ::component::{
-    changes::ComponentChanges, map::ComponentMap, ErasedComponentType, RawErasedComponentType,
+    changes::ComponentChanges, map::ComponentMap, ComponentType, ErasedComponentType,
+    ProvideComponentTypes, RawErasedComponentType,
 };
 use ahash::AHashSet;
 use erased_serde::{serialize_trait_object, Serialize as ErasedSerialize};
@@ -122,6 +123,36 @@ where
         &mut self.components
     }
 
+    /// Gets the component of given type.
+    #[inline]
+    pub fn get<C>(&self, ty: &ComponentType<'a, C>) -> Option<&C>
+    where
+        C: 'static,
+    {
+        // SAFETY: `C` does not contain any non-static lifetime parameter.
+        unsafe { self.components.get(ty) }
+    }
+
+    /// Gets a copy of the component of given type, or the default value
+    /// if the component is absent.
+    #[inline]
+    pub fn get_or_default<C>(&self, ty: &ComponentType<'a, C>) -> C
+    where
+        C: Clone + Default + 'static,
+    {
+        self.get(ty).cloned().unwrap_or_default()
+    }
+
+    /// Removes the component of given type, and returns it.
+    #[inline]
+    pub fn remove<C>(&mut self, ty: &ComponentType<'a, C>) -> Option<C>
+    where
+        C: Clone + 'static,
+    {
+        // SAFETY: `C` does not contain any non-static lifetime parameter.
+        unsafe { self.components.remove(ty) }.map(|old| old.into_owned().0)
+    }
+
     /// Marks this block entity as not removed.
     #[inline]
     pub fn cancel_removal(&mut self) {
@@ -141,6 +172,56 @@ where
     }
 }
 
+impl<'a, T: ?Sized, Cx> RawBlockEntity<'a, T, Cx>
+where
+    Cx: ProvideBlockStateExtTy + ProvideComponentTypes<'a>,
+{
+    /// Sets the component of given type, and returns the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given component type is not registered.
+    #[inline]
+    pub fn set<C>(&mut self, ty: &ComponentType<'a, C>, value: C) -> Option<C>
+    where
+        C: Clone + Send + Sync + 'static,
+    {
+        let ty = self.registration(ty);
+        // SAFETY: `C` does not contain any non-static lifetime parameter.
+        unsafe { self.components.insert(ty, value) }.map(|old| old.into_owned().0)
+    }
+
+    /// Updates the component of given type with the given function, which
+    /// receives the current component or `default` if absent, and returns
+    /// the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given component type is not registered.
+    pub fn update<C, F>(&mut self, ty: &ComponentType<'a, C>, default: C, f: F) -> Option<C>
+    where
+        C: Clone + Send + Sync + 'static,
+        F: FnOnce(&mut C),
+    {
+        let mut value = self.get(ty).cloned().unwrap_or(default);
+        f(&mut value);
+        self.set(ty, value)
+    }
+
+    /// Returns the registration of given component type, which is looked up
+    /// through the global context if absent from this block entity.
+    fn registration<C>(&self, ty: &ComponentType<'a, C>) -> ErasedComponentType<'a, Cx>
+    where
+        C: 'static,
+    {
+        // SAFETY: `C` does not contain any non-static lifetime parameter.
+        unsafe { self.components.get_key_value(ty) }
+            .map(|(registration, _)| registration)
+            .or_else(|| Cx::component_type(&RawErasedComponentType::from(ty)))
+            .expect("component type is not registered")
+    }
+}
+
 impl<'a, T: ?Sized, Cx> RawBlockEntity<'a, T, Cx>
 where
     Cx: ProvideBlockStateExtTy + ProvideBuiltInComponentTypes<'a>,
@@ -364,3 +445,6 @@ where
         &'s self,
     ) -> Option<impl FnOnce(BlockPos) -> Box<BlockEntity<'w, Cx>> + 's>;
 }
+
+#[cfg(test)]
+mod tests;
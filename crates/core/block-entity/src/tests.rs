@@ -0,0 +1,178 @@
+use std::sync::OnceLock;
+
+use component::{
+    packet_codec_edcode, packet_codec_variable, ComponentType, ComponentTypeIndex,
+    ErasedComponentType, PacketCodec, ProvideComponentTypes, RawErasedComponentType,
+};
+use rimecraft_block::{BlockState, ProvideBlockStateExtTy, RawBlock, Settings};
+use rimecraft_global_cx::{GlobalContext, ProvideIdTy};
+use rimecraft_registry::{Reg, Registry, RegistryKey, RegistryMut};
+use rimecraft_voxel_math::BlockPos;
+
+use crate::{
+    BlockEntity, Data, DynRawBlockEntityType, ProvideBuiltInComponentTypes, RawBlockEntity,
+    RawBlockEntityType,
+};
+
+#[derive(Debug)]
+enum Context {}
+
+unsafe impl GlobalContext for Context {}
+
+impl ProvideIdTy for Context {
+    type Id = String;
+}
+
+impl ProvideBlockStateExtTy for Context {
+    type BlockStateExt = ();
+}
+
+const STRING_PACKET_CODEC: PacketCodec<'static, String> = packet_codec_edcode();
+const U32_PACKET_CODEC: PacketCodec<'static, u32> = packet_codec_variable();
+
+static BLOCK_ENTITY_DATA: ComponentType<'static, String> = ComponentType::builder::<Context>()
+    .packet_codec(&STRING_PACKET_CODEC)
+    .build();
+static LEVEL: ComponentType<'static, u32> = ComponentType::builder::<Context>()
+    .packet_codec(&U32_PACKET_CODEC)
+    .build();
+
+type ComponentTypes = Registry<String, RawErasedComponentType<'static, Context>>;
+
+fn component_types() -> &'static ComponentTypes {
+    static REGISTRY: OnceLock<ComponentTypes> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut registry: RegistryMut<String, RawErasedComponentType<'static, Context>> =
+            RegistryMut::new(RegistryKey::new(
+                "root".to_owned(),
+                "data_component_type".to_owned(),
+            ));
+        let key = |id: &str| RegistryKey::new("data_component_type".to_owned(), id.to_owned());
+        registry
+            .register(key("block_entity_data"), (&BLOCK_ENTITY_DATA).into())
+            .unwrap();
+        registry.register(key("level"), (&LEVEL).into()).unwrap();
+        registry.into()
+    })
+}
+
+impl ProvideBuiltInComponentTypes<'static> for Context {
+    fn block_entity_data() -> ErasedComponentType<'static, Self> {
+        component_types()
+            .get(&"block_entity_data".to_owned())
+            .unwrap()
+    }
+}
+
+impl ProvideComponentTypes<'static> for Context {
+    fn component_type(
+        ty: &RawErasedComponentType<'static, Self>,
+    ) -> Option<ErasedComponentType<'static, Self>> {
+        static INDEX: OnceLock<ComponentTypeIndex<'static, Context>> = OnceLock::new();
+        INDEX
+            .get_or_init(|| ComponentTypeIndex::new(component_types()))
+            .get(ty)
+    }
+}
+
+#[derive(Debug)]
+struct BeaconType;
+
+impl RawBlockEntityType<Context> for BeaconType {
+    fn supports(&self, state: &BlockState<'_, Context>) -> bool {
+        let _ = state;
+        true
+    }
+
+    fn instantiate<'w>(
+        &self,
+        pos: BlockPos,
+        state: BlockState<'w, Context>,
+    ) -> Option<Box<BlockEntity<'w, Context>>> {
+        let _ = (pos, state);
+        None
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Beacon {
+    primary: u32,
+}
+
+impl Data<'_, Context> for Beacon {}
+
+fn beacon(pos: BlockPos) -> RawBlockEntity<'static, Beacon, Context> {
+    let mut blocks: RegistryMut<String, RawBlock<'static, Context>> =
+        RegistryMut::new(RegistryKey::new("root".to_owned(), "block".to_owned()));
+    blocks
+        .register(
+            RegistryKey::new("block".to_owned(), "beacon".to_owned()),
+            Settings::default().into(),
+        )
+        .unwrap();
+    let blocks: &'static Registry<String, RawBlock<'static, Context>> =
+        Box::leak(Box::new(blocks.into()));
+
+    let mut types: RegistryMut<String, DynRawBlockEntityType<'static, Context>> = RegistryMut::new(
+        RegistryKey::new("root".to_owned(), "block_entity_type".to_owned()),
+    );
+    types
+        .register(
+            RegistryKey::new("block_entity_type".to_owned(), "beacon".to_owned()),
+            Box::new(BeaconType),
+        )
+        .unwrap();
+    let types: &'static Registry<String, DynRawBlockEntityType<'static, Context>> =
+        Box::leak(Box::new(types.into()));
+
+    let block = blocks.get(&"beacon".to_owned()).unwrap();
+    let state = BlockState {
+        block,
+        state: Reg::into_inner(block).states().default_state(),
+    };
+    RawBlockEntity::new(
+        types.get(&"beacon".to_owned()).unwrap(),
+        pos,
+        state,
+        Beacon { primary: 1 },
+    )
+}
+
+#[test]
+fn accessors() {
+    let pos = BlockPos::new(1, 64, -3);
+    let mut beacon = beacon(pos);
+    assert_eq!(beacon.pos(), pos);
+    assert_eq!(beacon.data(), &Beacon { primary: 1 });
+    beacon.data_mut().primary = 4;
+    assert_eq!(beacon.data().primary, 4);
+
+    assert!(!beacon.is_removed());
+    beacon.mark_removed();
+    assert!(beacon.is_removed());
+    beacon.cancel_removal();
+    assert!(!beacon.is_removed());
+}
+
+#[test]
+fn components() {
+    let mut beacon = beacon(BlockPos::new(0, 0, 0));
+    assert!(beacon.components().is_empty());
+    assert_eq!(beacon.get(&LEVEL), None);
+    assert_eq!(beacon.get_or_default(&LEVEL), 0);
+
+    assert_eq!(beacon.set(&LEVEL, 2), None);
+    assert_eq!(beacon.get(&LEVEL), Some(&2));
+    assert_eq!(beacon.set(&LEVEL, 3), Some(2));
+    assert_eq!(beacon.update(&LEVEL, 0, |level| *level += 1), Some(3));
+    assert_eq!(beacon.get_or_default(&LEVEL), 4);
+
+    let components = beacon.create_components();
+    assert_eq!(components.len(), 1);
+    assert_eq!(unsafe { components.get(&LEVEL) }, Some(&4));
+
+    assert_eq!(beacon.remove(&LEVEL), Some(4));
+    assert_eq!(beacon.get(&LEVEL), None);
+    assert_eq!(beacon.update(&LEVEL, 1, |_| {}), None);
+    assert_eq!(beacon.get(&LEVEL), Some(&1));
+}
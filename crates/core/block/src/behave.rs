@@ -1,6 +1,15 @@
 //! Common behaviors of block state extensions.
 
-use rimecraft_state::State;
+use std::sync::Arc;
+
+use rimecraft_state::{State, States};
+use rimecraft_voxel_math::{
+    direction::{BlockMirror, BlockRotation},
+    BlockPos,
+};
+use rimecraft_voxel_shape::VoxelShapeSlice;
+
+use crate::Settings;
 
 /// Block state extensions that could returns a luminance value from
 /// the given state of the block.
@@ -8,3 +17,241 @@ pub trait ProvideLuminance: Sized {
     /// The luminance.
     fn luminance(&self, state: &State<'_, Self>) -> u32;
 }
+
+/// Block state extensions that could transform the given state of the
+/// block by rotating or mirroring its properties, such as its facing.
+///
+/// Both transforms return the state unchanged by default.
+///
+/// # MCJE Reference
+///
+/// This represents `AbstractBlock.rotate` and `AbstractBlock.mirror` (yarn).
+pub trait ProvideTransforms: Sized {
+    /// Returns the state rotated by given rotation.
+    #[inline]
+    fn rotate<'s, 'a>(
+        &self,
+        state: &'s State<'a, Self>,
+        rotation: BlockRotation,
+    ) -> &'s State<'a, Self> {
+        let _ = rotation;
+        state
+    }
+
+    /// Returns the state mirrored by given mirror.
+    #[inline]
+    fn mirror<'s, 'a>(
+        &self,
+        state: &'s State<'a, Self>,
+        mirror: BlockMirror,
+    ) -> &'s State<'a, Self> {
+        let _ = mirror;
+        state
+    }
+}
+
+impl ProvideTransforms for () {}
+
+/// Block state extensions that could tell whether flowing fluids could
+/// replace the given state of the block.
+///
+/// All states could be filled by default, as long as they do not block
+/// movement.
+///
+/// # MCJE Reference
+///
+/// This represents the block checks of `FlowableFluid.canFill` (yarn), such
+/// as doors, signs and portals refusing fluids.
+pub trait ProvideFluidFill: Sized {
+    /// Whether flowing fluids could replace the state.
+    #[inline]
+    fn can_fill(&self, state: &State<'_, Self>) -> bool {
+        let _ = state;
+        true
+    }
+}
+
+impl ProvideFluidFill for () {}
+
+/// Block state extensions that could return shapes only depending on
+/// the given state of the block.
+///
+/// Each shape defaults to `None`, which falls back to the shape derived
+/// from [`Settings`]. See [`Shapes`] for the defaults.
+///
+/// Shapes returned here are precomputed into a [`ShapeCache`].
+pub trait ProvideStateShapes: Sized {
+    /// The outline shape, used for selection and ray casting.
+    #[inline]
+    fn outline_shape(&self, state: &State<'_, Self>) -> Option<Arc<VoxelShapeSlice<'static>>> {
+        let _ = state;
+        None
+    }
+
+    /// The collision shape.
+    #[inline]
+    fn collision_shape(&self, state: &State<'_, Self>) -> Option<Arc<VoxelShapeSlice<'static>>> {
+        let _ = state;
+        None
+    }
+
+    /// The shape colliding with the camera.
+    #[inline]
+    fn camera_collision_shape(
+        &self,
+        state: &State<'_, Self>,
+    ) -> Option<Arc<VoxelShapeSlice<'static>>> {
+        let _ = state;
+        None
+    }
+
+    /// The shape for ray casting, which overrides the side of hits
+    /// on the outline shape.
+    #[inline]
+    fn raycast_shape(&self, state: &State<'_, Self>) -> Option<Arc<VoxelShapeSlice<'static>>> {
+        let _ = state;
+        None
+    }
+}
+
+/// Block state extensions that could return shapes of the given state of
+/// the block at given position in a block view of type `V`.
+///
+/// Each shape defaults to `None`, which falls back to the cached shape from
+/// [`ProvideStateShapes`]. Shapes returned here don't affect the defaults
+/// of the other shapes.
+///
+/// # MCJE Reference
+///
+/// This represents `AbstractBlock.getOutlineShape`, `getCollisionShape`,
+/// `getCameraCollisionShape` and `getRaycastShape` (yarn).
+pub trait ProvideShapes<V: ?Sized>: ProvideStateShapes {
+    /// The outline shape at given position.
+    #[inline]
+    fn outline_shape_at(
+        &self,
+        state: &State<'_, Self>,
+        view: &V,
+        pos: BlockPos,
+    ) -> Option<Arc<VoxelShapeSlice<'static>>> {
+        let _ = (state, view, pos);
+        None
+    }
+
+    /// The collision shape at given position.
+    #[inline]
+    fn collision_shape_at(
+        &self,
+        state: &State<'_, Self>,
+        view: &V,
+        pos: BlockPos,
+    ) -> Option<Arc<VoxelShapeSlice<'static>>> {
+        let _ = (state, view, pos);
+        None
+    }
+
+    /// The shape colliding with the camera at given position.
+    #[inline]
+    fn camera_collision_shape_at(
+        &self,
+        state: &State<'_, Self>,
+        view: &V,
+        pos: BlockPos,
+    ) -> Option<Arc<VoxelShapeSlice<'static>>> {
+        let _ = (state, view, pos);
+        None
+    }
+
+    /// The shape for ray casting at given position.
+    #[inline]
+    fn raycast_shape_at(
+        &self,
+        state: &State<'_, Self>,
+        view: &V,
+        pos: BlockPos,
+    ) -> Option<Arc<VoxelShapeSlice<'static>>> {
+        let _ = (state, view, pos);
+        None
+    }
+}
+
+impl ProvideStateShapes for () {}
+
+impl<V: ?Sized> ProvideShapes<V> for () {}
+
+/// State-only shapes of a block state.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Shapes {
+    /// The outline shape, which is a full cube by default, or empty
+    /// if the block [is empty](Settings::is_empty).
+    pub outline: Arc<VoxelShapeSlice<'static>>,
+    /// The collision shape, which is the outline shape by default, or
+    /// empty if the block is not [collidable](Settings::collidable).
+    pub collision: Arc<VoxelShapeSlice<'static>>,
+    /// The shape colliding with the camera, which is the collision
+    /// shape by default.
+    pub camera_collision: Arc<VoxelShapeSlice<'static>>,
+    /// The shape for ray casting, which is empty by default.
+    pub raycast: Arc<VoxelShapeSlice<'static>>,
+}
+
+/// Precomputed state-only shapes of all states of a block.
+///
+/// # MCJE Reference
+///
+/// This type represents `AbstractBlockState.ShapeCache` (yarn).
+#[derive(Debug, Default)]
+pub struct ShapeCache {
+    /// Shapes indexed by raw ids of states.
+    shapes: Box<[Shapes]>,
+}
+
+impl ShapeCache {
+    /// Computes shapes of all given states of a block with given settings.
+    pub fn new<T>(settings: &Settings, states: &States<'_, T>) -> Self
+    where
+        T: ProvideStateShapes,
+    {
+        let empty: Arc<VoxelShapeSlice<'static>> = rimecraft_voxel_shape::empty().into();
+        let full_cube: Arc<VoxelShapeSlice<'static>> = rimecraft_voxel_shape::full_cube().into();
+        let shapes = states
+            .iter()
+            .map(|state| {
+                let data = state.data();
+                let outline = data.outline_shape(state).unwrap_or_else(|| {
+                    if settings.is_empty {
+                        empty.clone()
+                    } else {
+                        full_cube.clone()
+                    }
+                });
+                let collision = data.collision_shape(state).unwrap_or_else(|| {
+                    if settings.collidable {
+                        outline.clone()
+                    } else {
+                        empty.clone()
+                    }
+                });
+                let camera_collision = data
+                    .camera_collision_shape(state)
+                    .unwrap_or_else(|| collision.clone());
+                let raycast = data.raycast_shape(state).unwrap_or_else(|| empty.clone());
+                Shapes {
+                    outline,
+                    collision,
+                    camera_collision,
+                    raycast,
+                }
+            })
+            .collect();
+        Self { shapes }
+    }
+
+    /// Gets the shapes of given state by its [raw id](State::raw_id), or
+    /// `None` if the raw id is out of the states this cache is computed from.
+    #[inline]
+    pub fn get<T>(&self, state: &State<'_, T>) -> Option<&Shapes> {
+        self.shapes.get(state.raw_id())
+    }
+}
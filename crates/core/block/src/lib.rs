@@ -1,13 +1,29 @@
 //! Minecraft block primitives.
 
-use behave::ProvideLuminance;
+use behave::{
+    ProvideFluidFill, ProvideLuminance, ProvideShapes, ProvideStateShapes, ProvideTransforms,
+    ShapeCache, Shapes,
+};
 use rimecraft_global_cx::{GlobalContext, ProvideIdTy};
-use rimecraft_registry::{ProvideRegistry, Reg};
+use rimecraft_registry::{ProvideRegistry, Reg, Registry, RegistryMut};
 use rimecraft_state::{State, States, StatesMut};
+use rimecraft_voxel_math::{
+    direction::{BlockMirror, BlockRotation},
+    BlockPos,
+};
+use rimecraft_voxel_shape::VoxelShapeSlice;
 
-use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    sync::{Arc, OnceLock},
+};
 
 pub mod behave;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod string;
 
 pub use rimecraft_state as state;
 
@@ -19,6 +35,7 @@ where
 {
     settings: Settings,
     states: States<'a, Cx::BlockStateExt>,
+    shapes: OnceLock<ShapeCache>,
     _marker: PhantomData<Cx>,
 }
 
@@ -32,6 +49,7 @@ where
         Self {
             settings,
             states,
+            shapes: OnceLock::new(),
             _marker: PhantomData,
         }
     }
@@ -49,6 +67,41 @@ where
     }
 }
 
+impl<Cx> RawBlock<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::BlockStateExt: ProvideStateShapes,
+{
+    /// Returns the state-only shapes of all states of the block.
+    ///
+    /// The shapes are computed when the registry is frozen through [`freeze`],
+    /// or on first access otherwise.
+    #[inline]
+    pub fn shape_cache(&self) -> &ShapeCache {
+        self.shapes
+            .get_or_init(|| ShapeCache::new(&self.settings, &self.states))
+    }
+}
+
+/// Freezes given block registry, and precomputes state-only shapes of all
+/// its blocks.
+///
+/// See [`RawBlock::shape_cache`].
+pub fn freeze<'a, K, Cx>(
+    registry: RegistryMut<K, RawBlock<'a, Cx>>,
+) -> Registry<K, RawBlock<'a, Cx>>
+where
+    K: Hash + Eq + Clone,
+    Cx: ProvideBlockStateExtTy,
+    Cx::BlockStateExt: ProvideStateShapes,
+{
+    let registry: Registry<_, _> = registry.into();
+    for block in &registry {
+        block.shape_cache();
+    }
+    registry
+}
+
 impl<Cx> From<Settings> for RawBlock<'_, Cx>
 where
     Cx: ProvideBlockStateExtTy,
@@ -64,7 +117,7 @@ where
     Cx: ProvideBlockStateExtTy + ProvideRegistry<'r, K, Self>,
 {
     #[inline(always)]
-    fn registry() -> &'r rimecraft_registry::Registry<K, Self> {
+    fn registry() -> &'r Registry<K, Self> {
         Cx::registry()
     }
 }
@@ -138,6 +191,136 @@ where
     }
 }
 
+impl<Cx> BlockState<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::BlockStateExt: ProvideStateShapes,
+{
+    /// Returns the cached state-only shapes of this block state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state is not one of the states of the block.
+    #[inline]
+    pub fn shapes(&self) -> &Shapes {
+        self.block
+            .shape_cache()
+            .get(self.state)
+            .expect("state is not from its block")
+    }
+
+    /// Returns the outline shape of this block state at given position
+    /// in the view.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::shapes`].
+    pub fn outline_shape<V>(&self, view: &V, pos: BlockPos) -> Arc<VoxelShapeSlice<'static>>
+    where
+        V: ?Sized,
+        Cx::BlockStateExt: ProvideShapes<V>,
+    {
+        self.state
+            .data()
+            .outline_shape_at(self.state, view, pos)
+            .unwrap_or_else(|| self.shapes().outline.clone())
+    }
+
+    /// Returns the collision shape of this block state at given position
+    /// in the view.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::shapes`].
+    pub fn collision_shape<V>(&self, view: &V, pos: BlockPos) -> Arc<VoxelShapeSlice<'static>>
+    where
+        V: ?Sized,
+        Cx::BlockStateExt: ProvideShapes<V>,
+    {
+        self.state
+            .data()
+            .collision_shape_at(self.state, view, pos)
+            .unwrap_or_else(|| self.shapes().collision.clone())
+    }
+
+    /// Returns the shape colliding with the camera of this block state at
+    /// given position in the view.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::shapes`].
+    pub fn camera_collision_shape<V>(
+        &self,
+        view: &V,
+        pos: BlockPos,
+    ) -> Arc<VoxelShapeSlice<'static>>
+    where
+        V: ?Sized,
+        Cx::BlockStateExt: ProvideShapes<V>,
+    {
+        self.state
+            .data()
+            .camera_collision_shape_at(self.state, view, pos)
+            .unwrap_or_else(|| self.shapes().camera_collision.clone())
+    }
+
+    /// Returns the shape for ray casting of this block state at given
+    /// position in the view.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::shapes`].
+    pub fn raycast_shape<V>(&self, view: &V, pos: BlockPos) -> Arc<VoxelShapeSlice<'static>>
+    where
+        V: ?Sized,
+        Cx::BlockStateExt: ProvideShapes<V>,
+    {
+        self.state
+            .data()
+            .raycast_shape_at(self.state, view, pos)
+            .unwrap_or_else(|| self.shapes().raycast.clone())
+    }
+}
+
+impl<Cx> BlockState<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::BlockStateExt: ProvideTransforms,
+{
+    /// Returns this block state rotated by given rotation.
+    #[inline]
+    #[must_use]
+    pub fn rotate(&self, rotation: BlockRotation) -> Self {
+        Self {
+            block: self.block,
+            state: self.state.data().rotate(self.state, rotation),
+        }
+    }
+
+    /// Returns this block state mirrored by given mirror.
+    #[inline]
+    #[must_use]
+    pub fn mirror(&self, mirror: BlockMirror) -> Self {
+        Self {
+            block: self.block,
+            state: self.state.data().mirror(self.state, mirror),
+        }
+    }
+}
+
+impl<Cx> BlockState<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::BlockStateExt: ProvideFluidFill,
+{
+    /// Whether flowing fluids could replace this block state, ignoring
+    /// whether it blocks movement.
+    #[inline]
+    pub fn can_fill(&self) -> bool {
+        self.state.data().can_fill(self.state)
+    }
+}
+
 impl<Cx> Debug for BlockState<'_, Cx>
 where
     Cx: ProvideBlockStateExtTy + Debug,
@@ -186,3 +369,6 @@ where
         std::ptr::from_ref(self.state).hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests;
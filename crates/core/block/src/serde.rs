@@ -0,0 +1,258 @@
+//! Serde support for block states.
+//!
+//! Block states are (de)serialized as a `{Name: "...", Properties: {...}}` compound,
+//! which is used by palette entries in chunk NBT and structure files.
+//! The `Properties` field is omitted when the block has no properties.
+//!
+//! Block states are deserialized through [`Seed`], which resolves blocks
+//! through the given registry.
+//!
+//! # MCJE Reference
+//!
+//! This module represents `BlockState.CODEC` and `NbtHelper.toBlockState` (yarn).
+
+use ::serde::{de::DeserializeSeed, ser::SerializeMap, Deserialize, Serialize};
+use rimecraft_registry::{Reg, Registry};
+use rimecraft_state::State;
+
+use crate::{BlockState, ProvideBlockStateExtTy, RawBlock};
+
+const NAME: &str = "Name";
+const PROPERTIES: &str = "Properties";
+
+impl<Cx> Serialize for BlockState<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::Id: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        let names = self.state.names();
+        let mut map = serializer.serialize_map(Some(if names.is_empty() { 1 } else { 2 }))?;
+        map.serialize_entry(NAME, Reg::id(self.block))?;
+        if !names.is_empty() {
+            map.serialize_entry(PROPERTIES, &Properties(&names))?;
+        }
+        map.end()
+    }
+}
+
+struct Properties<'a>(&'a [(&'a str, std::borrow::Cow<'a, str>)]);
+
+impl Serialize for Properties<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+/// [`DeserializeSeed`] of block states, resolving blocks through the given registry.
+pub struct Seed<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    registry: &'w Registry<Cx::Id, RawBlock<'w, Cx>>,
+    lenient: bool,
+}
+
+impl<'w, Cx> Seed<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    /// Creates a seed in strict mode, which errors on unknown blocks,
+    /// unknown properties and invalid values.
+    #[inline]
+    pub const fn new(registry: &'w Registry<Cx::Id, RawBlock<'w, Cx>>) -> Self {
+        Self {
+            registry,
+            lenient: false,
+        }
+    }
+
+    /// Creates a seed in lenient mode.
+    ///
+    /// Unknown properties are dropped and invalid values are dropped with a warning.
+    /// Unknown blocks fall back to the default state of the registry default entry
+    /// with a warning, and it errors only if the registry does not have a default entry.
+    #[inline]
+    pub const fn lenient(registry: &'w Registry<Cx::Id, RawBlock<'w, Cx>>) -> Self {
+        Self {
+            registry,
+            lenient: true,
+        }
+    }
+
+    fn resolve<E>(&self, id: Cx::Id, props: Vec<(String, String)>) -> Result<BlockState<'w, Cx>, E>
+    where
+        E: ::serde::de::Error,
+    {
+        let block = match self.registry.get(&id) {
+            Some(block) => block,
+            None if self.lenient => {
+                let block = self.registry.default_entry().ok_or_else(|| {
+                    E::custom(format!("unknown block {id} and no default block present"))
+                })?;
+                tracing::warn!("unknown block {id}, falling back to {block}");
+                return Ok(BlockState {
+                    block,
+                    state: Reg::into_inner(block).states().default_state(),
+                });
+            }
+            None => return Err(E::custom(format!("unknown block {id}"))),
+        };
+        let mut state: &'w State<'w, _> = Reg::into_inner(block).states().default_state();
+        for (name, value) in &props {
+            if state.get_name(name).is_none() {
+                if self.lenient {
+                    continue;
+                }
+                return Err(E::custom(format!(
+                    "block {id} does not have property {name}"
+                )));
+            }
+            match state.with_name(name, value) {
+                Ok(s) => state = s,
+                Err(_) if self.lenient => {
+                    tracing::warn!(
+                        "block {id} does not accept {value} for property {name}, ignoring"
+                    );
+                }
+                Err(_) => {
+                    return Err(E::custom(format!(
+                        "block {id} does not accept {value} for property {name}"
+                    )))
+                }
+            }
+        }
+        Ok(BlockState { block, state })
+    }
+}
+
+impl<Cx> Clone for Seed<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Cx> Copy for Seed<'_, Cx> where Cx: ProvideBlockStateExtTy {}
+
+impl<Cx> std::fmt::Debug for Seed<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Seed")
+            .field("lenient", &self.lenient)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'w, 'de, Cx> DeserializeSeed<'de> for Seed<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::Id: Deserialize<'de>,
+{
+    type Value = BlockState<'w, Cx>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(field_identifier)]
+        enum Field {
+            Name,
+            Properties,
+            #[serde(other)]
+            Other,
+        }
+
+        struct Visitor<'w, Cx>(Seed<'w, Cx>)
+        where
+            Cx: ProvideBlockStateExtTy;
+
+        impl<'w, 'de, Cx> ::serde::de::Visitor<'de> for Visitor<'w, Cx>
+        where
+            Cx: ProvideBlockStateExtTy,
+            Cx::Id: Deserialize<'de>,
+        {
+            type Value = BlockState<'w, Cx>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "a block state compound")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: ::serde::de::MapAccess<'de>,
+            {
+                let mut id: Option<Cx::Id> = None;
+                let mut props: Option<Vec<(String, String)>> = None;
+                while let Some(field) = map.next_key()? {
+                    match field {
+                        Field::Name => {
+                            if id.is_some() {
+                                return Err(::serde::de::Error::duplicate_field(NAME));
+                            }
+                            id = Some(map.next_value()?);
+                        }
+                        Field::Properties => {
+                            if props.is_some() {
+                                return Err(::serde::de::Error::duplicate_field(PROPERTIES));
+                            }
+                            props = Some(map.next_value::<PropertiesOwned>()?.0);
+                        }
+                        Field::Other => {
+                            map.next_value::<::serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                let id = id.ok_or_else(|| ::serde::de::Error::missing_field(NAME))?;
+                self.0.resolve(id, props.unwrap_or_default())
+            }
+        }
+
+        deserializer.deserialize_map(Visitor(self))
+    }
+}
+
+/// Properties compound, with insertion order preserved.
+struct PropertiesOwned(Vec<(String, String)>);
+
+impl<'de> Deserialize<'de> for PropertiesOwned {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> ::serde::de::Visitor<'de> for Visitor {
+            type Value = PropertiesOwned;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "a properties compound")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: ::serde::de::MapAccess<'de>,
+            {
+                let mut props = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(entry) = map.next_entry()? {
+                    props.push(entry);
+                }
+                Ok(PropertiesOwned(props))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
@@ -0,0 +1,442 @@
+//! String representation of block states.
+//!
+//! Block states are represented as `block_id[prop=value,...]` in commands,
+//! structure files and debug outputs. Formatting is done through the
+//! [`Display`] implementation of [`BlockState`].
+//!
+//! # MCJE Reference
+//!
+//! This module represents `net.minecraft.command.argument.BlockArgumentParser` (yarn).
+
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use rimecraft_registry::{entry::RefEntry, Reg, Registry, TagKey};
+
+use crate::{Block, BlockState, ProvideBlockStateExtTy, RawBlock};
+
+/// Parses a block state from its string representation, like
+/// `stone[facing=north,lit=true]`.
+///
+/// The block is resolved through the given registry and each property value
+/// is resolved through [`Wrap::parse_name`](rimecraft_state::property::Wrap::parse_name).
+/// Properties absent from the input keep their values of the default state.
+///
+/// # Errors
+///
+/// Errors if the input is malformed, or the block, any of the properties or
+/// any of the values could not be resolved.
+pub fn parse_state<'w, Cx>(
+    registry: &'w Registry<Cx::Id, RawBlock<'w, Cx>>,
+    input: &str,
+) -> Result<BlockState<'w, Cx>, ParseError>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::Id: FromStr,
+{
+    let raw = Raw::parse(input)?;
+    if raw.tag {
+        return Err(ParseError::TagNotAllowed);
+    }
+    let block = resolve_block(registry, raw.id)?;
+    let mut state = Reg::into_inner(block).states().default_state();
+    for prop in &raw.props {
+        state = resolve_property(raw.id, state, prop)?;
+    }
+    Ok(BlockState { block, state })
+}
+
+/// Parses a block state predicate from its string representation, like
+/// `stone[lit=true]` or `#logs[axis=y]`.
+///
+/// See [`StatePredicate`] for more information.
+///
+/// # Errors
+///
+/// Errors if the input is malformed, or the block could not be resolved.
+/// When the predicate targets a single block, errors if any of the properties
+/// or any of the values is not valid for the block.
+pub fn parse_predicate<'w, Cx>(
+    registry: &'w Registry<Cx::Id, RawBlock<'w, Cx>>,
+    input: &str,
+) -> Result<StatePredicate<'w, Cx>, ParseError>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::Id: FromStr + Clone,
+{
+    let raw = Raw::parse(input)?;
+    let target = if raw.tag {
+        Target::Tag(TagKey {
+            registry: registry.key().clone(),
+            id: raw
+                .id
+                .parse()
+                .map_err(|_| ParseError::UnknownBlock(format!("#{}", raw.id)))?,
+        })
+    } else {
+        let block = resolve_block(registry, raw.id)?;
+        let default = Reg::into_inner(block).states().default_state();
+        for prop in &raw.props {
+            resolve_property(raw.id, default, prop)?;
+        }
+        Target::Block(block)
+    };
+    Ok(StatePredicate {
+        target,
+        properties: raw
+            .props
+            .into_iter()
+            .map(|p| (p.name.to_owned(), p.value.to_owned()))
+            .collect(),
+    })
+}
+
+/// A predicate matching block states, with only part of the properties specified.
+///
+/// For example, `furnace[lit=true]` matches any lit furnace regardless of its facing,
+/// and `#logs[axis=y]` matches any vertical log.
+pub struct StatePredicate<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    target: Target<'w, Cx>,
+    properties: Vec<(String, String)>,
+}
+
+enum Target<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    Block(Block<'w, Cx>),
+    Tag(TagKey<Cx::Id, RawBlock<'w, Cx>>),
+}
+
+impl<'w, Cx> StatePredicate<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    /// Whether the given block state matches this predicate.
+    pub fn test(&self, state: &BlockState<'w, Cx>) -> bool {
+        let block_matches = match &self.target {
+            Target::Block(block) => *block == state.block,
+            Target::Tag(tag) => <&RefEntry<_, _>>::from(state.block).tags().contains(tag),
+        };
+        block_matches
+            && self
+                .properties
+                .iter()
+                .all(|(name, value)| state.state.get_name(name).is_some_and(|v| v == *value))
+    }
+
+    /// Gets the specified properties and their value names.
+    #[inline]
+    pub fn properties(&self) -> &[(String, String)] {
+        &self.properties
+    }
+}
+
+impl<Cx> Debug for StatePredicate<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::Id: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("StatePredicate");
+        match &self.target {
+            Target::Block(block) => s.field("block", block),
+            Target::Tag(tag) => s.field("tag", tag),
+        };
+        s.field("properties", &self.properties).finish()
+    }
+}
+
+impl<Cx> Display for StatePredicate<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.target {
+            Target::Block(block) => write!(f, "{block}")?,
+            Target::Tag(tag) => write!(f, "#{}", tag.id)?,
+        }
+        write_properties(
+            f,
+            self.properties
+                .iter()
+                .map(|(n, v)| (n.as_str(), v.as_str())),
+        )
+    }
+}
+
+impl<Cx> Display for BlockState<'_, Cx>
+where
+    Cx: ProvideBlockStateExtTy,
+{
+    /// Formats this block state as `block_id[prop=value,...]`, with properties
+    /// sorted by their names.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.block)?;
+        write_properties(
+            f,
+            self.state
+                .names()
+                .iter()
+                .map(|(name, value)| (*name, value.as_ref())),
+        )
+    }
+}
+
+fn write_properties<'a, I>(f: &mut std::fmt::Formatter<'_>, props: I) -> std::fmt::Result
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let mut iter = props.into_iter().peekable();
+    if iter.peek().is_none() {
+        return Ok(());
+    }
+    f.write_str("[")?;
+    for (i, (name, value)) in iter.enumerate() {
+        if i != 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{name}={value}")?;
+    }
+    f.write_str("]")
+}
+
+fn resolve_block<'w, Cx>(
+    registry: &'w Registry<Cx::Id, RawBlock<'w, Cx>>,
+    id: &str,
+) -> Result<Block<'w, Cx>, ParseError>
+where
+    Cx: ProvideBlockStateExtTy,
+    Cx::Id: FromStr,
+{
+    id.parse::<Cx::Id>()
+        .ok()
+        .and_then(|key| registry.get(&key))
+        .ok_or_else(|| ParseError::UnknownBlock(id.to_owned()))
+}
+
+fn resolve_property<'w, T>(
+    block: &str,
+    state: &'w rimecraft_state::State<'w, T>,
+    prop: &RawProperty<'_>,
+) -> Result<&'w rimecraft_state::State<'w, T>, ParseError> {
+    if state.get_name(prop.name).is_none() {
+        return Err(ParseError::UnknownProperty {
+            block: block.to_owned(),
+            property: prop.name.to_owned(),
+            cursor: prop.cursor,
+        });
+    }
+    state
+        .with_name(prop.name, prop.value)
+        .map_err(|_| ParseError::InvalidValue {
+            block: block.to_owned(),
+            property: prop.name.to_owned(),
+            value: prop.value.to_owned(),
+            cursor: prop.value_cursor,
+        })
+}
+
+/// Syntax tree of a block state string.
+struct Raw<'a> {
+    tag: bool,
+    id: &'a str,
+    props: Vec<RawProperty<'a>>,
+}
+
+struct RawProperty<'a> {
+    name: &'a str,
+    value: &'a str,
+    cursor: usize,
+    value_cursor: usize,
+}
+
+impl<'a> Raw<'a> {
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let mut reader = Reader { input, cursor: 0 };
+        reader.skip_whitespace();
+        let tag = reader.eat('#');
+        let id = reader.read_while(|c| c != '[' && !c.is_whitespace());
+        if id.is_empty() {
+            return Err(reader.expected("block id"));
+        }
+        let mut props: Vec<RawProperty<'a>> = Vec::new();
+        reader.skip_whitespace();
+        if reader.eat('[') {
+            reader.skip_whitespace();
+            if !reader.eat(']') {
+                loop {
+                    reader.skip_whitespace();
+                    let cursor = reader.cursor;
+                    let name = reader.read_while(is_allowed_in_name);
+                    if name.is_empty() {
+                        return Err(reader.expected("property name"));
+                    }
+                    if props.iter().any(|p| p.name == name) {
+                        return Err(ParseError::DuplicateProperty {
+                            property: name.to_owned(),
+                            cursor,
+                        });
+                    }
+                    reader.skip_whitespace();
+                    if !reader.eat('=') {
+                        return Err(reader.expected("'='"));
+                    }
+                    reader.skip_whitespace();
+                    let value_cursor = reader.cursor;
+                    let value = reader.read_while(is_allowed_in_name);
+                    if value.is_empty() {
+                        return Err(reader.expected("property value"));
+                    }
+                    props.push(RawProperty {
+                        name,
+                        value,
+                        cursor,
+                        value_cursor,
+                    });
+                    reader.skip_whitespace();
+                    if reader.eat(']') {
+                        break;
+                    }
+                    if !reader.eat(',') {
+                        return Err(reader.expected("',' or ']'"));
+                    }
+                }
+            }
+            reader.skip_whitespace();
+        }
+        if reader.cursor != input.len() {
+            return Err(reader.expected("end of input"));
+        }
+        Ok(Self { tag, id, props })
+    }
+}
+
+#[inline]
+fn is_allowed_in_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+struct Reader<'a> {
+    input: &'a str,
+    cursor: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_while<F>(&mut self, f: F) -> &'a str
+    where
+        F: Fn(char) -> bool,
+    {
+        let rest = &self.input[self.cursor..];
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.cursor += len;
+        &rest[..len]
+    }
+
+    #[inline]
+    fn skip_whitespace(&mut self) {
+        self.read_while(char::is_whitespace);
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.input[self.cursor..].starts_with(c) {
+            self.cursor += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn expected(&self, expected: &'static str) -> ParseError {
+        ParseError::Syntax {
+            cursor: self.cursor,
+            expected,
+        }
+    }
+}
+
+/// Error type for parsing block states.
+///
+/// Cursors are byte offsets in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The input is malformed.
+    Syntax {
+        /// Position of the unexpected input.
+        cursor: usize,
+        /// Description of the expected input.
+        expected: &'static str,
+    },
+    /// The block is not found in the registry.
+    UnknownBlock(String),
+    /// A block tag is given where a single block is required.
+    TagNotAllowed,
+    /// The property is not present in the block.
+    UnknownProperty {
+        /// The block id.
+        block: String,
+        /// The property name.
+        property: String,
+        /// Position of the property.
+        cursor: usize,
+    },
+    /// The value is not valid for the property.
+    InvalidValue {
+        /// The block id.
+        block: String,
+        /// The property name.
+        property: String,
+        /// The value name.
+        value: String,
+        /// Position of the value.
+        cursor: usize,
+    },
+    /// The property is specified more than once.
+    DuplicateProperty {
+        /// The property name.
+        property: String,
+        /// Position of the duplicated property.
+        cursor: usize,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Syntax { cursor, expected } => {
+                write!(f, "expected {expected} at position {cursor}")
+            }
+            ParseError::UnknownBlock(id) => write!(f, "unknown block: {id}"),
+            ParseError::TagNotAllowed => write!(f, "block tags are not allowed here"),
+            ParseError::UnknownProperty {
+                block,
+                property,
+                cursor,
+            } => write!(
+                f,
+                "block {block} does not have property {property} (at position {cursor})"
+            ),
+            ParseError::InvalidValue {
+                block,
+                property,
+                value,
+                cursor,
+            } => write!(
+                f,
+                "block {block} does not accept {value} for property {property} (at position {cursor})"
+            ),
+            ParseError::DuplicateProperty { property, cursor } => {
+                write!(f, "property {property} is set more than once (at position {cursor})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,459 @@
+use rimecraft_global_cx::{GlobalContext, ProvideIdTy};
+use rimecraft_registry::{entry::RefEntry, Registry, RegistryKey, RegistryMut, TagKey};
+use rimecraft_state::{
+    property::{data::BoolData, direction::HORIZONTAL_FACING, BoolProperty},
+    StatesMut,
+};
+
+use crate::{
+    string::{parse_predicate, parse_state, ParseError},
+    ProvideBlockStateExtTy, RawBlock, Settings,
+};
+
+#[derive(Debug)]
+enum Context {}
+
+unsafe impl GlobalContext for Context {}
+
+impl ProvideIdTy for Context {
+    type Id = String;
+}
+
+impl ProvideBlockStateExtTy for Context {
+    type BlockStateExt = ();
+}
+
+static LIT: BoolProperty<'static> = BoolProperty::new("lit", BoolData);
+
+fn registry() -> &'static Registry<String, RawBlock<'static, Context>> {
+    let mut registry: RegistryMut<String, RawBlock<'static, Context>> =
+        RegistryMut::new(RegistryKey::new("root".to_owned(), "block".to_owned()));
+    let key = |id: &str| RegistryKey::new("block".to_owned(), id.to_owned());
+
+    registry
+        .register_default(key("air"), Settings::default().into())
+        .unwrap();
+    registry
+        .register(key("stone"), Settings::default().into())
+        .unwrap();
+    let mut states = StatesMut::new(());
+    states.add(&HORIZONTAL_FACING).unwrap();
+    states.add(&LIT).unwrap();
+    registry
+        .register(
+            key("furnace"),
+            RawBlock::new(Settings::default(), states.freeze()),
+        )
+        .unwrap();
+
+    Box::leak(Box::new(registry.into()))
+}
+
+#[test]
+fn parse_and_format() {
+    let registry = registry();
+
+    let stone = parse_state(registry, "stone").unwrap();
+    assert_eq!(stone.to_string(), "stone");
+
+    let furnace = parse_state(registry, "furnace[ lit = true,facing=west]").unwrap();
+    assert_eq!(furnace.to_string(), "furnace[facing=west,lit=true]");
+    assert_eq!(
+        parse_state(registry, &furnace.to_string()).unwrap(),
+        furnace
+    );
+
+    let default = parse_state(registry, "furnace[]").unwrap();
+    assert_eq!(default.to_string(), "furnace[facing=north,lit=false]");
+}
+
+#[test]
+fn parse_errors() {
+    let registry = registry();
+
+    assert_eq!(
+        parse_state(registry, "dirt").unwrap_err(),
+        ParseError::UnknownBlock("dirt".to_owned())
+    );
+    assert_eq!(
+        parse_state(registry, "furnace[lit=true,axis=y]").unwrap_err(),
+        ParseError::UnknownProperty {
+            block: "furnace".to_owned(),
+            property: "axis".to_owned(),
+            cursor: 17,
+        }
+    );
+    assert_eq!(
+        parse_state(registry, "furnace[facing=up]").unwrap_err(),
+        ParseError::InvalidValue {
+            block: "furnace".to_owned(),
+            property: "facing".to_owned(),
+            value: "up".to_owned(),
+            cursor: 15,
+        }
+    );
+    assert_eq!(
+        parse_state(registry, "furnace[lit=true,lit=false]").unwrap_err(),
+        ParseError::DuplicateProperty {
+            property: "lit".to_owned(),
+            cursor: 17,
+        }
+    );
+    assert!(matches!(
+        parse_state(registry, "furnace[lit=true"),
+        Err(ParseError::Syntax { cursor: 16, .. })
+    ));
+    assert!(matches!(
+        parse_state(registry, "furnace[lit]"),
+        Err(ParseError::Syntax { cursor: 11, .. })
+    ));
+    assert_eq!(
+        parse_state(registry, "#furnaces").unwrap_err(),
+        ParseError::TagNotAllowed
+    );
+}
+
+#[test]
+fn predicate() {
+    let registry = registry();
+    let lit = parse_predicate(registry, "furnace[lit=true]").unwrap();
+    assert_eq!(lit.to_string(), "furnace[lit=true]");
+
+    assert!(lit.test(&parse_state(registry, "furnace[lit=true,facing=south]").unwrap()));
+    assert!(lit.test(&parse_state(registry, "furnace[lit=true,facing=east]").unwrap()));
+    assert!(!lit.test(&parse_state(registry, "furnace[lit=false]").unwrap()));
+    assert!(!lit.test(&parse_state(registry, "stone").unwrap()));
+
+    assert!(parse_predicate(registry, "furnace[lit=maybe]").is_err());
+
+    let tag = TagKey {
+        registry: registry.key().clone(),
+        id: "furnaces".to_owned(),
+    };
+    let furnace: &RefEntry<_, _> = registry.get(&"furnace".to_owned()).unwrap().into();
+    registry.populate_tags([(tag, vec![furnace])]).unwrap();
+
+    let tagged = parse_predicate(registry, "#furnaces[facing=east]").unwrap();
+    assert_eq!(tagged.to_string(), "#furnaces[facing=east]");
+    assert!(tagged.test(&parse_state(registry, "furnace[facing=east]").unwrap()));
+    assert!(!tagged.test(&parse_state(registry, "furnace").unwrap()));
+    assert!(!tagged.test(&parse_state(registry, "stone").unwrap()));
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use std::collections::HashMap;
+
+    use fastnbt::Value;
+    use serde::de::DeserializeSeed;
+
+    use crate::{serde::Seed, string::parse_state, BlockState};
+
+    use super::{registry, Context};
+
+    fn compound(name: &str, props: &[(&str, &str)]) -> Vec<u8> {
+        let mut map = HashMap::from([("Name".to_owned(), Value::String(name.to_owned()))]);
+        if !props.is_empty() {
+            map.insert(
+                "Properties".to_owned(),
+                Value::Compound(
+                    props
+                        .iter()
+                        .map(|(k, v)| ((*k).to_owned(), Value::String((*v).to_owned())))
+                        .collect(),
+                ),
+            );
+        }
+        fastnbt::to_bytes(&Value::Compound(map)).expect("serialize failed")
+    }
+
+    fn deserialize(
+        seed: Seed<'static, Context>,
+        buf: &[u8],
+    ) -> Result<BlockState<'static, Context>, fastnbt::Error> {
+        seed.deserialize(&mut fastnbt::de::Deserializer::from_reader(
+            buf,
+            fastnbt::DeOpts::new(),
+        ))
+    }
+
+    #[test]
+    fn round_trip() {
+        let registry = registry();
+        let furnace = parse_state(registry, "furnace[facing=west,lit=true]").unwrap();
+
+        let buf = fastnbt::to_bytes(&furnace).expect("serialize failed");
+        let Value::Compound(map) = fastnbt::from_bytes::<Value>(&buf).unwrap() else {
+            panic!("block state should be serialized as a compound")
+        };
+        assert_eq!(map.get("Name"), Some(&Value::String("furnace".to_owned())));
+        assert!(map.contains_key("Properties"));
+        assert_eq!(deserialize(Seed::new(registry), &buf).unwrap(), furnace);
+
+        let stone = parse_state(registry, "stone").unwrap();
+        let buf = fastnbt::to_bytes(&stone).expect("serialize failed");
+        let Value::Compound(map) = fastnbt::from_bytes::<Value>(&buf).unwrap() else {
+            panic!("block state should be serialized as a compound")
+        };
+        assert!(!map.contains_key("Properties"));
+        assert_eq!(deserialize(Seed::new(registry), &buf).unwrap(), stone);
+    }
+
+    #[test]
+    fn strict() {
+        let seed = Seed::new(registry());
+        assert!(deserialize(seed, &compound("dirt", &[])).is_err());
+        assert!(deserialize(seed, &compound("furnace", &[("axis", "y")])).is_err());
+        assert!(deserialize(seed, &compound("furnace", &[("facing", "up")])).is_err());
+    }
+
+    #[test]
+    fn lenient() {
+        let registry = registry();
+        let seed = Seed::lenient(registry);
+        assert_eq!(
+            deserialize(seed, &compound("dirt", &[("lit", "true")])).unwrap(),
+            parse_state(registry, "air").unwrap()
+        );
+        assert_eq!(
+            deserialize(
+                seed,
+                &compound(
+                    "furnace",
+                    &[("axis", "y"), ("facing", "up"), ("lit", "true")]
+                )
+            )
+            .unwrap(),
+            parse_state(registry, "furnace[lit=true]").unwrap()
+        );
+    }
+}
+
+mod shapes {
+    use std::sync::Arc;
+
+    use rimecraft_global_cx::{GlobalContext, ProvideIdTy};
+    use rimecraft_registry::{Registry, RegistryKey, RegistryMut};
+    use rimecraft_state::{State, StatesMut};
+    use rimecraft_voxel_math::{BBox, BlockPos, DVec3};
+    use rimecraft_voxel_shape::VoxelShapeSlice;
+
+    use crate::{
+        behave::{ProvideShapes, ProvideStateShapes},
+        freeze,
+        string::parse_state,
+        ProvideBlockStateExtTy, RawBlock, Settings,
+    };
+
+    use super::LIT;
+
+    #[derive(Debug, Clone, Copy)]
+    enum Ext {
+        Plain,
+        /// A bottom slab, or a top slab if lit.
+        Slab,
+    }
+
+    impl ProvideStateShapes for Ext {
+        fn outline_shape(&self, state: &State<'_, Self>) -> Option<Arc<VoxelShapeSlice<'static>>> {
+            let y = if state.get(&LIT)? { 0.5 } else { 0.0 };
+            matches!(self, Ext::Slab).then(|| {
+                rimecraft_voxel_shape::cuboid(BBox::new(
+                    DVec3::new(0.0, y, 0.0),
+                    DVec3::new(1.0, y + 0.5, 1.0),
+                ))
+                .into()
+            })
+        }
+    }
+
+    /// Slabs are hidden at positions in the view.
+    impl ProvideShapes<[BlockPos]> for Ext {
+        fn outline_shape_at(
+            &self,
+            _state: &State<'_, Self>,
+            view: &[BlockPos],
+            pos: BlockPos,
+        ) -> Option<Arc<VoxelShapeSlice<'static>>> {
+            (matches!(self, Ext::Slab) && view.contains(&pos))
+                .then(|| rimecraft_voxel_shape::empty().into())
+        }
+    }
+
+    #[derive(Debug)]
+    enum Context {}
+
+    unsafe impl GlobalContext for Context {}
+
+    impl ProvideIdTy for Context {
+        type Id = String;
+    }
+
+    impl ProvideBlockStateExtTy for Context {
+        type BlockStateExt = Ext;
+    }
+
+    fn registry() -> &'static Registry<String, RawBlock<'static, Context>> {
+        let mut registry: RegistryMut<String, RawBlock<'static, Context>> =
+            RegistryMut::new(RegistryKey::new("root".to_owned(), "block".to_owned()));
+        let key = |id: &str| RegistryKey::new("block".to_owned(), id.to_owned());
+        let plain = || StatesMut::new(Ext::Plain).freeze();
+
+        let air = Settings {
+            is_empty: true,
+            ..Default::default()
+        };
+        let stone = Settings {
+            collidable: true,
+            ..Default::default()
+        };
+        registry
+            .register_default(key("air"), RawBlock::new(air, plain()))
+            .unwrap();
+        registry
+            .register(key("stone"), RawBlock::new(stone, plain()))
+            .unwrap();
+        registry
+            .register(key("grass"), RawBlock::new(Settings::default(), plain()))
+            .unwrap();
+        let mut states = StatesMut::new(Ext::Slab);
+        states.add(&LIT).unwrap();
+        registry
+            .register(key("slab"), RawBlock::new(stone, states.freeze()))
+            .unwrap();
+
+        let registry: &'static Registry<_, _> = Box::leak(Box::new(freeze(registry)));
+        assert!(registry
+            .into_iter()
+            .all(|block| block.shapes.get().is_some()));
+        registry
+    }
+
+    fn boxes(shape: &VoxelShapeSlice<'_>) -> Vec<BBox> {
+        let mut boxes = Vec::new();
+        shape.for_each_box(|bbox| boxes.push(bbox));
+        boxes
+    }
+
+    #[test]
+    fn defaults() {
+        let registry = registry();
+        let view: &[BlockPos] = &[];
+
+        let air = parse_state(registry, "air").unwrap();
+        assert!(air.outline_shape(view, BlockPos::ORIGIN).is_empty());
+        assert!(air.collision_shape(view, BlockPos::ORIGIN).is_empty());
+
+        let stone = parse_state(registry, "stone").unwrap();
+        assert!(stone.outline_shape(view, BlockPos::ORIGIN).is_full_cube());
+        assert!(stone.collision_shape(view, BlockPos::ORIGIN).is_full_cube());
+        assert!(stone
+            .camera_collision_shape(view, BlockPos::ORIGIN)
+            .is_full_cube());
+        assert!(stone.raycast_shape(view, BlockPos::ORIGIN).is_empty());
+
+        let grass = parse_state(registry, "grass").unwrap();
+        assert!(grass.outline_shape(view, BlockPos::ORIGIN).is_full_cube());
+        assert!(grass.collision_shape(view, BlockPos::ORIGIN).is_empty());
+    }
+
+    #[test]
+    fn state_and_view() {
+        let registry = registry();
+        let pos = BlockPos::new(1, 2, 3);
+        let hidden: &[BlockPos] = &[pos];
+
+        let bottom = parse_state(registry, "slab").unwrap();
+        let top = parse_state(registry, "slab[lit=true]").unwrap();
+        let half = |y: f64| BBox::new(DVec3::new(0.0, y, 0.0), DVec3::new(1.0, y + 0.5, 1.0));
+        assert_eq!(boxes(&bottom.outline_shape(&[][..], pos)), [half(0.0)]);
+        assert_eq!(boxes(&top.outline_shape(&[][..], pos)), [half(0.5)]);
+        // Collision shape follows the state-only outline shape.
+        assert_eq!(boxes(&top.collision_shape(hidden, pos)), [half(0.5)]);
+        assert!(top.outline_shape(hidden, pos).is_empty());
+
+        assert!(Arc::ptr_eq(
+            &top.shapes().outline,
+            &top.collision_shape(&[][..], pos)
+        ));
+    }
+}
+
+mod transforms {
+    use rimecraft_global_cx::{GlobalContext, ProvideIdTy};
+    use rimecraft_registry::{Registry, RegistryKey, RegistryMut};
+    use rimecraft_state::{property::direction::HORIZONTAL_FACING, State, StatesMut};
+    use rimecraft_voxel_math::direction::{BlockMirror, BlockRotation, Direction};
+
+    use crate::{behave::ProvideTransforms, string::parse_state, ProvideBlockStateExtTy, RawBlock};
+
+    #[derive(Debug, Clone, Copy)]
+    struct Ext;
+
+    impl ProvideTransforms for Ext {
+        fn rotate<'s, 'a>(
+            &self,
+            state: &'s State<'a, Self>,
+            rotation: BlockRotation,
+        ) -> &'s State<'a, Self> {
+            let facing: Direction = state.get(&HORIZONTAL_FACING).unwrap();
+            state
+                .with(&HORIZONTAL_FACING, rotation.apply(facing))
+                .unwrap()
+        }
+
+        fn mirror<'s, 'a>(
+            &self,
+            state: &'s State<'a, Self>,
+            mirror: BlockMirror,
+        ) -> &'s State<'a, Self> {
+            let facing: Direction = state.get(&HORIZONTAL_FACING).unwrap();
+            self.rotate(state, mirror.to_rotation(facing))
+        }
+    }
+
+    #[derive(Debug)]
+    enum Context {}
+
+    unsafe impl GlobalContext for Context {}
+
+    impl ProvideIdTy for Context {
+        type Id = String;
+    }
+
+    impl ProvideBlockStateExtTy for Context {
+        type BlockStateExt = Ext;
+    }
+
+    #[test]
+    fn facing() {
+        let mut registry: RegistryMut<String, RawBlock<'static, Context>> =
+            RegistryMut::new(RegistryKey::new("root".to_owned(), "block".to_owned()));
+        let key = |id: &str| RegistryKey::new("block".to_owned(), id.to_owned());
+        let mut states = StatesMut::new(Ext);
+        states.add(&HORIZONTAL_FACING).unwrap();
+        registry
+            .register_default(
+                key("chest"),
+                RawBlock::new(Default::default(), states.freeze()),
+            )
+            .unwrap();
+        let registry: &'static Registry<_, _> = Box::leak(Box::new(registry.into()));
+
+        let state = parse_state(registry, "chest[facing=north]").unwrap();
+        let facing = |s: &crate::BlockState<'_, Context>| s.state.get(&HORIZONTAL_FACING);
+        assert_eq!(
+            facing(&state.rotate(BlockRotation::Clockwise90)),
+            Some(Direction::East)
+        );
+        assert_eq!(
+            facing(&state.rotate(BlockRotation::Counterclockwise90)),
+            Some(Direction::West)
+        );
+        assert_eq!(
+            facing(&state.mirror(BlockMirror::LeftRight)),
+            Some(Direction::South)
+        );
+        assert_eq!(state.mirror(BlockMirror::FrontBack), state);
+    }
+}
@@ -101,6 +101,67 @@ where
         }
     }
 
+    /// Merges the given changes into these changes, where entries of `other`
+    /// take precedence.
+    ///
+    /// Applying the merged changes is equivalent to applying these changes
+    /// and `other` in order.
+    pub fn merge<'cow>(
+        &self,
+        other: &ComponentChanges<'a, '_, Cx>,
+    ) -> ComponentChanges<'a, 'cow, Cx> {
+        let changed: AHashMap<_, _> = self
+            .changed
+            .iter()
+            .filter(|(k, _)| !other.changed.contains_key(*k))
+            .chain(other.changed.iter())
+            .map(|(&CompTyCell(k), v)| (CompTyCell(k), v.as_deref().map(k.f.util.clone)))
+            .collect();
+        ComponentChanges {
+            ser_count: changed.keys().filter(|k| !k.0.is_transient()).count(),
+            changed: Maybe::Owned(SimpleOwned(changed)),
+        }
+    }
+
+    /// Returns the component types changed differently by these changes and
+    /// `other`, which conflict when both changes are merged.
+    pub fn conflicts<'s>(
+        &'s self,
+        other: &'s ComponentChanges<'a, '_, Cx>,
+    ) -> impl Iterator<Item = ErasedComponentType<'a, Cx>> + 's {
+        self.changed.iter().filter_map(|(&CompTyCell(ty), obj)| {
+            let other = other.changed.get(&*ty)?;
+            let same = match (obj, other) {
+                (Some(obj), Some(other)) => (ty.f.util.eq)(&**obj, &**other),
+                (None, None) => true,
+                _ => false,
+            };
+            (!same).then_some(ty)
+        })
+    }
+
+    /// Rebases these changes onto the given base map, dropping changes
+    /// that make no difference to it.
+    ///
+    /// Added components equal to those of the base and removal markers of
+    /// components absent from the base are dropped.
+    pub fn rebase<'cow>(&self, base: &ComponentMap<'a, Cx>) -> ComponentChanges<'a, 'cow, Cx> {
+        let changed: AHashMap<_, _> = self
+            .changed
+            .iter()
+            .filter(|(&CompTyCell(ty), obj)| match (obj, base.get_raw(&ty)) {
+                (Some(obj), Some(old)) => !(ty.f.util.eq)(&**obj, old),
+                (Some(_), None) | (None, Some(_)) => true,
+                (None, None) => false,
+            })
+            .map(|(&CompTyCell(k), v)| (CompTyCell(k), v.as_deref().map(k.f.util.clone)))
+            .collect();
+        ComponentChanges {
+            ser_count: changed.keys().filter(|k| !k.0.is_transient()).count(),
+            changed: Maybe::Owned(SimpleOwned(changed)),
+        }
+    }
+
     /// Converts the changes into a pair of added components and removed component types.
     pub fn into_added_removed_pair(
         self,
@@ -0,0 +1,89 @@
+//! Stable hashing of components.
+//!
+//! Unlike [`Hash`](std::hash::Hash) implementations, hashes computed here are
+//! deterministic across processes, as long as the component types are
+//! registered in the same order. Each component is hashed as the CRC32C
+//! checksum of its packet encoding, and components are sorted by raw
+//! registry ids of their types.
+
+use edcode2::BufMutExt as _;
+use rimecraft_global_cx::ProvideIdTy;
+use rimecraft_registry::Reg;
+
+use crate::{changes::ComponentChanges, map::ComponentMap, ErasedComponentType, Object};
+
+const REMOVED: u8 = 0;
+const ADDED: u8 = 1;
+
+/// Hashes the given component with its packet encoding.
+fn hash_component<'a, Cx>(
+    ty: ErasedComponentType<'a, Cx>,
+    obj: &Object<'a>,
+    buf: &mut Vec<u8>,
+) -> Result<u32, edcode2::BoxedError<'static>>
+where
+    Cx: ProvideIdTy,
+{
+    buf.clear();
+    (ty.f.packet_codec.encode)(obj, buf)?;
+    Ok(crc32c::crc32c(buf))
+}
+
+impl<Cx> ComponentMap<'_, Cx>
+where
+    Cx: ProvideIdTy,
+{
+    /// Returns the stable hash of this map.
+    ///
+    /// Maps with equal components have equal hashes, regardless of whether
+    /// they are patched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component fails to encode.
+    pub fn stable_hash(&self) -> Result<u32, edcode2::BoxedError<'static>> {
+        let mut entries = self.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|&(ty, _)| Reg::raw_id(ty));
+
+        let mut buf = Vec::new();
+        let mut canonical = Vec::with_capacity(entries.len() * 8 + 4);
+        canonical.put_variable(entries.len() as u32);
+        for (ty, obj) in entries {
+            let hash = hash_component(ty, obj, &mut buf)?;
+            canonical.put_variable(Reg::raw_id(ty) as u32);
+            canonical.extend_from_slice(&hash.to_be_bytes());
+        }
+        Ok(crc32c::crc32c(&canonical))
+    }
+}
+
+impl<Cx> ComponentChanges<'_, '_, Cx>
+where
+    Cx: ProvideIdTy,
+{
+    /// Returns the stable hash of these changes, where removal markers are
+    /// distinct from added components.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component fails to encode.
+    pub fn stable_hash(&self) -> Result<u32, edcode2::BoxedError<'static>> {
+        let mut entries = self.changed.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(ty, _)| Reg::raw_id(ty.0));
+
+        let mut buf = Vec::new();
+        let mut canonical = Vec::with_capacity(entries.len() * 9 + 4);
+        canonical.put_variable(entries.len() as u32);
+        for (ty, obj) in entries {
+            canonical.put_variable(Reg::raw_id(ty.0) as u32);
+            if let Some(obj) = obj {
+                let hash = hash_component(ty.0, &**obj, &mut buf)?;
+                canonical.push(ADDED);
+                canonical.extend_from_slice(&hash.to_be_bytes());
+            } else {
+                canonical.push(REMOVED);
+            }
+        }
+        Ok(crc32c::crc32c(&canonical))
+    }
+}
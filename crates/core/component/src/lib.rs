@@ -3,12 +3,12 @@
 use std::{any::TypeId, cell::UnsafeCell, fmt::Debug, hash::Hash, marker::PhantomData};
 
 use bytes::{Buf, BufMut};
-use edcode2::{Decode, Encode};
+use edcode2::{Decode, Encode, Variable};
 use rimecraft_global_cx::{
     nbt::{ReadNbt, UpdateNbt, WriteNbt},
     ProvideIdTy,
 };
-use rimecraft_registry::{ProvideRegistry, Reg};
+use rimecraft_registry::{ProvideRegistry, Reg, Registry};
 use serde::{de::DeserializeOwned, Serialize};
 
 type Object<'a> = dyn Any + Send + Sync + 'a;
@@ -17,6 +17,7 @@ pub mod changes;
 pub mod map;
 
 mod dyn_any;
+mod hash;
 
 pub mod test_global_integration;
 
@@ -109,6 +110,30 @@ where
     }
 }
 
+/// Creates a new [`PacketCodec`] by encoding and decoding integers in variable length
+/// through `edcode2`.
+pub const fn packet_codec_variable<'a, T>() -> PacketCodec<'a, T>
+where
+    T: Copy + Send + Sync + 'a,
+    Variable<T>: for<'b> Encode<&'b mut dyn BufMut> + for<'b> Decode<'static, &'b mut dyn Buf>,
+{
+    PacketCodec {
+        codec: UnsafePacketCodec {
+            encode: |obj, buf| {
+                Variable(*unsafe { &*(std::ptr::from_ref::<Object<'_>>(obj) as *const T) })
+                    .encode(buf)
+            },
+            decode: |buf| Ok(Box::new(Variable::<T>::decode(buf)?.0)),
+            upd: |obj, buf| {
+                *unsafe { &mut *(std::ptr::from_mut::<Object<'_>>(obj) as *mut T) } =
+                    Variable::<T>::decode(buf)?.0;
+                Ok(())
+            },
+        },
+        _marker: PhantomData,
+    }
+}
+
 /// Creates a new [`PacketCodec`] by NBT serialization.
 pub const fn packet_codec_nbt<'a, T, Cx>() -> PacketCodec<'a, T>
 where
@@ -373,7 +398,7 @@ where
     Cx: ProvideRegistry<'r, K, Self>,
 {
     #[inline]
-    fn registry() -> &'r rimecraft_registry::Registry<K, Self> {
+    fn registry() -> &'r Registry<K, Self> {
         Cx::registry()
     }
 }
@@ -390,6 +415,55 @@ impl<Cx> Clone for RawErasedComponentType<'_, Cx> {
 pub type ErasedComponentType<'a, Cx> =
     Reg<'a, <Cx as ProvideIdTy>::Id, RawErasedComponentType<'a, Cx>>;
 
+/// Provides registrations of component types.
+pub trait ProvideComponentTypes<'a>: ProvideIdTy + Sized {
+    /// Returns the registration of given component type, or `None` if it's
+    /// not registered.
+    ///
+    /// This is called whenever a component absent from a component map is set,
+    /// so implementations should look the type up through an index like
+    /// [`ComponentTypeIndex`] rather than scanning the registry.
+    fn component_type(
+        ty: &RawErasedComponentType<'a, Self>,
+    ) -> Option<ErasedComponentType<'a, Self>>;
+}
+
+/// Index of registered component types, keyed by the [`TypeId`] of their
+/// components.
+pub struct ComponentTypeIndex<'a, Cx>
+where
+    Cx: ProvideIdTy,
+{
+    map: AHashMap<RawErasedComponentType<'a, Cx>, ErasedComponentType<'a, Cx>>,
+}
+
+impl<'a, Cx> ComponentTypeIndex<'a, Cx>
+where
+    Cx: ProvideIdTy,
+{
+    /// Creates an index of all component types in given registry.
+    pub fn new(registry: &'a Registry<Cx::Id, RawErasedComponentType<'a, Cx>>) -> Self {
+        Self {
+            map: registry.entries().map(|entry| (*entry, entry)).collect(),
+        }
+    }
+
+    /// Returns the registration of given component type.
+    #[inline]
+    pub fn get(&self, ty: &RawErasedComponentType<'a, Cx>) -> Option<ErasedComponentType<'a, Cx>> {
+        self.map.get(ty).copied()
+    }
+}
+
+impl<Cx> Debug for ComponentTypeIndex<'_, Cx>
+where
+    Cx: ProvideIdTy<Id: Debug>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.map.values()).finish()
+    }
+}
+
 struct UnsafeDebugIter<I>(UnsafeCell<I>);
 
 impl<I> Debug for UnsafeDebugIter<I>
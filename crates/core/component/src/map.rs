@@ -299,12 +299,22 @@ where
             "the component type should matches the type of given value",
         };
 
+        if let MapInner::Empty = self.0 {
+            self.0 = MapInner::Simple(AHashMap::new());
+        }
+
         match &mut self.0 {
-            MapInner::Empty => None,
+            MapInner::Empty => unreachable!(),
             MapInner::Patched { base, changes, .. } => {
                 let old = base.get_raw(&ty);
                 if old.is_some_and(|old| (ty.f.util.eq)(&val, old)) {
-                    changes.remove(&CompTyCell(ty))
+                    let Some(v) = changes.remove(&CompTyCell(ty)) else {
+                        // Nothing changed, the value in base map is still valid.
+                        return old
+                            .and_then(|old| unsafe { old.downcast_ref::<T>() })
+                            .map(Maybe::Borrowed);
+                    };
+                    Some(v)
                 } else if let Some(v) = changes.insert(CompTyCell(ty), Some(Box::new(val))) {
                     Some(v)
                 } else {
@@ -410,6 +420,17 @@ where
         self.into_iter()
     }
 
+    /// Returns whether the non-transient components of this map are equal to
+    /// those of the other map.
+    pub fn eq_ignoring_transient(&self, other: &Self) -> bool {
+        let persistent = |map: &Self| map.iter().filter(|(ty, _)| !ty.is_transient()).count();
+        persistent(self) == persistent(other)
+            && self
+                .iter()
+                .filter(|(ty, _)| !ty.is_transient())
+                .all(|(ty, obj)| other.get_raw(&ty).is_some_and(|o| (ty.f.util.eq)(obj, o)))
+    }
+
     /// Returns the changes of this map.
     pub fn changes(&self) -> Option<ComponentChanges<'a, '_, Cx>> {
         if let MapInner::Patched { changes, .. } = &self.0 {
@@ -421,6 +442,109 @@ where
             None
         }
     }
+
+    /// Returns the changes turning this map into the given map.
+    ///
+    /// Components absent from `other` are marked as removed, and components
+    /// absent from this map or differing from those of `other` are added,
+    /// including transient ones.
+    pub fn diff<'cow>(&self, other: &Self) -> ComponentChanges<'a, 'cow, Cx> {
+        let mut changes: AHashMap<_, _> = other
+            .iter()
+            .filter(|(ty, obj)| {
+                self.get_raw(ty)
+                    .is_none_or(|old| !(ty.f.util.eq)(*obj, old))
+            })
+            .map(|(ty, obj)| (CompTyCell(ty), Some((ty.f.util.clone)(obj))))
+            .collect();
+        changes.extend(
+            self.iter()
+                .filter(|(ty, _)| !other.contains_raw(ty))
+                .map(|(ty, _)| (CompTyCell(ty), None)),
+        );
+        ComponentChanges {
+            ser_count: changes.keys().filter(|cell| !cell.0.is_transient()).count(),
+            changed: Maybe::Owned(SimpleOwned(changes)),
+        }
+    }
+
+    /// Returns a **patched** component map with given base map, holding the
+    /// same changes as this map rebased onto the base.
+    ///
+    /// Maps without a base map keep their components as changes against the
+    /// given base.
+    ///
+    /// See [`ComponentChanges::rebase`].
+    #[inline]
+    pub fn rebase(&self, base: &'a ComponentMap<'a, Cx>) -> Self {
+        self.rebase_raw(Maybe::Borrowed(base))
+    }
+
+    /// Returns a **patched** component map with given base map, holding the
+    /// same changes as this map rebased onto the base.
+    ///
+    /// See [`Self::rebase`].
+    #[inline]
+    pub fn arc_rebase(&self, base: Arc<ComponentMap<'a, Cx>>) -> Self {
+        self.rebase_raw(Maybe::Owned(base))
+    }
+
+    fn rebase_raw(&self, base: Maybe<'a, ComponentMap<'a, Cx>, Arc<ComponentMap<'a, Cx>>>) -> Self {
+        let changes = match self.changes() {
+            Some(changes) => changes.rebase(&base),
+            None => base.diff(self),
+        };
+        Self::with_changes_raw(base, changes)
+    }
+
+    /// Applies the given changes to this map, inserting the added components
+    /// and removing the removed ones.
+    pub fn apply_changes(&mut self, changes: &ComponentChanges<'a, '_, Cx>) {
+        for (&CompTyCell(ty), obj) in changes.changed.iter() {
+            let occupied = self.contains_raw(&ty);
+            if let Some(obj) = obj.as_deref().map(ty.f.util.clone) {
+                if let MapInner::Empty = self.0 {
+                    self.0 = MapInner::Simple(AHashMap::new());
+                }
+                match &mut self.0 {
+                    MapInner::Empty => unreachable!(),
+                    MapInner::Patched { base, changes, .. } => {
+                        if base
+                            .get_raw(&ty)
+                            .is_some_and(|old| (ty.f.util.eq)(&*obj, old))
+                        {
+                            changes.remove(&CompTyCell(ty));
+                        } else {
+                            changes.insert(CompTyCell(ty), Some(obj));
+                        }
+                    }
+                    MapInner::Simple(map) => {
+                        map.insert(CompTyCell(ty), obj);
+                    }
+                }
+                if !occupied {
+                    self.track_add();
+                }
+            } else {
+                match &mut self.0 {
+                    MapInner::Empty => {}
+                    MapInner::Patched { base, changes, .. } => {
+                        if base.contains_raw(&ty) {
+                            changes.insert(CompTyCell(ty), None);
+                        } else {
+                            changes.remove(&CompTyCell(ty));
+                        }
+                    }
+                    MapInner::Simple(map) => {
+                        map.remove(&CompTyCell(ty));
+                    }
+                }
+                if occupied {
+                    self.track_rm();
+                }
+            }
+        }
+    }
 }
 
 impl<'a, 's, Cx> IntoIterator for &'s ComponentMap<'a, Cx>
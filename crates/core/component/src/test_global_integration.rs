@@ -2,9 +2,13 @@
 
 #![cfg(feature = "test")]
 
+use std::cell::OnceCell;
+
 use test_global::{integration::registry::RegistryType, TestContext};
 
-use crate::RawErasedComponentType;
+use crate::{
+    ComponentTypeIndex, ErasedComponentType, ProvideComponentTypes, RawErasedComponentType,
+};
 
 #[doc(hidden)]
 #[allow(missing_debug_implementations)]
@@ -26,4 +30,20 @@ impl RegistryType for RawErasedComponentType<'static, TestContext> {
     }
 }
 
+impl ProvideComponentTypes<'static> for TestContext {
+    fn component_type(
+        ty: &RawErasedComponentType<'static, Self>,
+    ) -> Option<ErasedComponentType<'static, Self>> {
+        thread_local! {
+            static INDEX: OnceCell<ComponentTypeIndex<'static, TestContext>> =
+                const { OnceCell::new() };
+        }
+        INDEX.with(|index| {
+            index
+                .get_or_init(|| ComponentTypeIndex::new(registry()))
+                .get(ty)
+        })
+    }
+}
+
 pub use _registry::*;
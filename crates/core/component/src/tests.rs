@@ -3,7 +3,7 @@ use std::sync::Arc;
 use bytes::{Buf, BufMut};
 use edcode2::{Decode, Encode};
 use rimecraft_global_cx::ProvideIdTy;
-use rimecraft_registry::RegistryKey;
+use rimecraft_registry::{Reg, RegistryKey};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -288,6 +288,271 @@ fn patched_changes() {
     assert_eq!(new_patched.len(), 1);
 }
 
+#[test]
+fn insert_tracking() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+    let foo = |value| Foo {
+        value,
+        info: "foo".to_owned(),
+    };
+
+    let mut empty = ComponentMap::EMPTY;
+    unsafe {
+        assert!(empty.insert(persistent_ty, foo(1)).is_none());
+        assert_eq!(empty.get(&TYPE_PERSISTENT), Some(&foo(1)));
+    }
+    assert_eq!(empty.len(), 1);
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(persistent_ty, foo(2));
+    let map = Arc::new(builder.build());
+    let mut patched = ComponentMap::arc_new(map);
+    unsafe {
+        assert_eq!(
+            patched.insert(persistent_ty, foo(2)).as_deref(),
+            Some(&foo(2))
+        );
+        assert_eq!(patched.len(), 1);
+        assert!(patched.changes().expect("no changes").is_empty());
+
+        patched.remove(&TYPE_PERSISTENT).expect("remove failed");
+        assert_eq!(patched.len(), 0);
+        assert!(patched.insert(persistent_ty, foo(2)).is_none());
+        assert_eq!(patched.len(), 1);
+        assert!(patched.changes().expect("no changes").is_empty());
+    }
+}
+
+#[test]
+fn apply_changes() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+    let foo = |value| Foo {
+        value,
+        info: "foo".to_owned(),
+    };
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(edcode_ty, foo(1));
+    let map = Arc::new(builder.build());
+
+    let mut changes = ComponentChanges::builder();
+    changes.remove(edcode_ty);
+    changes.insert(persistent_ty, foo(2));
+    let changes = changes.build();
+
+    let mut patched = ComponentMap::arc_new(map.clone());
+    patched.apply_changes(&changes);
+    assert_eq!(patched.len(), 1);
+    unsafe {
+        assert!(patched.get(&TYPE_TRANSIENT_EDCODE).is_none());
+        assert_eq!(patched.get(&TYPE_PERSISTENT), Some(&foo(2)));
+    }
+    assert_eq!(patched.changes().expect("no changes").len(), 2);
+
+    let mut reverted = ComponentChanges::builder();
+    reverted.insert(edcode_ty, foo(1));
+    reverted.remove(persistent_ty);
+    patched.apply_changes(&reverted.build());
+    assert_eq!(patched.len(), 1);
+    assert!(patched.changes().expect("no changes").is_empty());
+
+    let mut simple = ComponentMap::EMPTY;
+    let mut changes = ComponentChanges::builder();
+    changes.insert(persistent_ty, foo(3));
+    simple.apply_changes(&changes.build());
+    assert_eq!(simple.len(), 1);
+}
+
+#[test]
+fn diff_merge_rebase() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+    let foo = |value| Foo {
+        value,
+        info: "foo".to_owned(),
+    };
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(edcode_ty, foo(1));
+    builder.insert(persistent_ty, foo(1));
+    let a = Arc::new(builder.build());
+    let mut builder = ComponentMap::builder();
+    builder.insert(persistent_ty, foo(2));
+    let b = builder.build();
+
+    // Diff
+    let diff = a.diff(&b);
+    assert_eq!(diff.len(), 2);
+    assert_eq!(diff.ser_count, 1);
+    unsafe {
+        assert_eq!(diff.get(&TYPE_TRANSIENT_EDCODE), Some(None));
+        assert_eq!(diff.get(&TYPE_PERSISTENT), Some(Some(&foo(2))));
+    }
+    let mut patched = ComponentMap::arc_new(a.clone());
+    patched.apply_changes(&diff);
+    assert_eq!(patched, b);
+    assert!(b.diff(&b).is_empty());
+    assert_eq!(ComponentMap::EMPTY.diff(&b).len(), 1);
+
+    // Merge
+    let mut first = ComponentChanges::builder();
+    first.remove(edcode_ty);
+    first.insert(persistent_ty, foo(3));
+    let first = first.build();
+    let mut second = ComponentChanges::builder();
+    second.insert(edcode_ty, foo(4));
+    second.insert(persistent_ty, foo(3));
+    let second = second.build();
+    let merged = first.merge(&second);
+    assert_eq!(merged.len(), 2);
+    unsafe { assert_eq!(merged.get(&TYPE_TRANSIENT_EDCODE), Some(Some(&foo(4)))) };
+    assert_eq!(first.conflicts(&second).collect::<Vec<_>>(), [edcode_ty]);
+    let mut sequential = ComponentMap::arc_new(a.clone());
+    sequential.apply_changes(&first);
+    sequential.apply_changes(&second);
+    let mut at_once = ComponentMap::arc_new(a.clone());
+    at_once.apply_changes(&merged);
+    assert_eq!(sequential, at_once);
+
+    // Rebase
+    let mut patched = ComponentMap::arc_new(a.clone());
+    patched.apply_changes(&diff);
+    let rebased = patched.arc_rebase(Arc::new(b.clone()));
+    assert_eq!(rebased, b);
+    assert!(rebased.changes().expect("no changes").is_empty());
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(edcode_ty, foo(5));
+    let rebased = patched.arc_rebase(Arc::new(builder.build()));
+    assert_eq!(rebased, b);
+    let changes = rebased.changes().expect("no changes");
+    assert_eq!(changes.len(), 2);
+    unsafe { assert_eq!(changes.get(&TYPE_TRANSIENT_EDCODE), Some(None)) };
+
+    let rebased = b.arc_rebase(a.clone());
+    assert_eq!(rebased, b);
+    assert_eq!(rebased.changes().expect("no changes").len(), 2);
+}
+
+#[test]
+fn stable_hash() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+    let foo = |value| Foo {
+        value,
+        info: "foo".to_owned(),
+    };
+
+    // Canonical encoding of a map with a single component.
+    let mut builder = ComponentMap::builder();
+    builder.insert(edcode_ty, foo(1));
+    let single = builder.build();
+    let mut encoded = Vec::new();
+    foo(1).encode(&mut encoded).expect("encode failed");
+    let mut canonical = vec![1, Reg::raw_id(edcode_ty) as u8];
+    canonical.extend_from_slice(&crc32c::crc32c(&encoded).to_be_bytes());
+    let hash = single.stable_hash().expect("hash failed");
+    assert_eq!(hash, crc32c::crc32c(&canonical));
+    let empty_hash = ComponentMap::<Context>::EMPTY
+        .stable_hash()
+        .expect("hash failed");
+    assert_eq!(empty_hash, crc32c::crc32c(&[0]));
+
+    // Patched maps hash as their components.
+    let mut added = ComponentChanges::builder();
+    added.insert(edcode_ty, foo(1));
+    let added = added.build();
+    let mut patched = ComponentMap::arc_new(Arc::new(ComponentMap::EMPTY));
+    patched.apply_changes(&added);
+    assert_eq!(patched.stable_hash().expect("hash failed"), hash);
+    let single = Arc::new(single);
+    let mut patched = ComponentMap::arc_new(single.clone());
+    let mut changes = ComponentChanges::builder();
+    changes.insert(edcode_ty, foo(2));
+    patched.apply_changes(&changes.build());
+    assert_ne!(patched.stable_hash().expect("hash failed"), hash);
+
+    // Changes are sorted, and removal markers differ from added components.
+    let removed = |first: bool| {
+        let mut builder = ComponentChanges::builder();
+        if first {
+            builder.remove(edcode_ty);
+            builder.remove(persistent_ty);
+        } else {
+            builder.remove(persistent_ty);
+            builder.remove(edcode_ty);
+        }
+        builder.build().stable_hash().expect("hash failed")
+    };
+    assert_eq!(removed(true), removed(false));
+    let mut removed = ComponentChanges::builder();
+    removed.remove(edcode_ty);
+    assert_ne!(
+        removed.build().stable_hash().expect("hash failed"),
+        added.stable_hash().expect("hash failed")
+    );
+}
+
+#[test]
+fn eq_ignoring_transient() {
+    init_registry();
+    let reg = crate::test_global_integration::registry();
+    let edcode_ty = reg
+        .get(&TYPE_TRANSIENT_EDCODE_KEY)
+        .expect("invalid registry");
+    let persistent_ty = reg.get(&TYPE_PERSISTENT_KEY).expect("invalid registry");
+
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        persistent_ty,
+        Foo {
+            value: 114,
+            info: "hello".to_owned(),
+        },
+    );
+    let map = Arc::new(builder.build());
+
+    let mut patched = ComponentMap::arc_new(map.clone());
+    unsafe {
+        patched.insert(
+            edcode_ty,
+            Foo {
+                value: 514,
+                info: "world".to_owned(),
+            },
+        );
+    }
+    assert_ne!(*map, patched);
+    assert!(map.eq_ignoring_transient(&patched));
+    assert!(patched.eq_ignoring_transient(&map));
+
+    unsafe {
+        patched.insert(
+            persistent_ty,
+            Foo {
+                value: 1919,
+                info: "wlg".to_owned(),
+            },
+        );
+    }
+    assert!(!map.eq_ignoring_transient(&patched));
+}
+
 #[test]
 fn map_serde() {
     init_registry();
@@ -0,0 +1,113 @@
+//! Common behaviors of fluid state extensions.
+
+use rimecraft_state::State;
+use rimecraft_voxel_math::direction::Direction;
+
+use crate::{FluidState, ProvideFluidStateExtTy};
+
+/// The level of a full fluid, such as a source.
+pub const MAX_LEVEL: u8 = 8;
+
+/// Fluid state extensions that could describe how the given state of the
+/// fluid behaves and spreads.
+///
+/// The defaults describe an empty fluid that never flows.
+///
+/// # MCJE Reference
+///
+/// This represents `Fluid` and `FlowableFluid` (yarn).
+pub trait ProvideFluidBehavior: Sized {
+    /// The level of the fluid, from `0` for empty fluids to [`MAX_LEVEL`]
+    /// for sources and falling fluids.
+    #[inline]
+    fn level(&self, state: &State<'_, Self>) -> u8 {
+        let _ = state;
+        0
+    }
+
+    /// Whether the state is a source of the fluid.
+    #[inline]
+    fn is_still(&self, state: &State<'_, Self>) -> bool {
+        let _ = state;
+        false
+    }
+
+    /// Whether the fluid is falling from the fluid above it.
+    #[inline]
+    fn is_falling(&self, state: &State<'_, Self>) -> bool {
+        let _ = state;
+        false
+    }
+
+    /// The height of the fluid itself, which is proportional to its level.
+    #[inline]
+    fn height(&self, state: &State<'_, Self>) -> f32 {
+        f32::from(self.level(state)) / 9.0
+    }
+
+    /// The delay of scheduled ticks of the fluid, in ticks.
+    #[inline]
+    fn tick_rate(&self) -> u32 {
+        0
+    }
+
+    /// Whether the fluid is of the same type as the other one, such as
+    /// flowing and still water.
+    #[inline]
+    fn matches_type(&self, other: &Self) -> bool {
+        let _ = other;
+        false
+    }
+
+    /// Whether the state could be replaced with the other state flowing in
+    /// towards given direction.
+    #[inline]
+    fn can_be_replaced_with(
+        &self,
+        state: &State<'_, Self>,
+        other: &State<'_, Self>,
+        direction: Direction,
+    ) -> bool {
+        let _ = (state, other, direction);
+        true
+    }
+
+    /// Whether two adjacent sources of the fluid create a new source between
+    /// them.
+    #[inline]
+    fn is_infinite(&self) -> bool {
+        false
+    }
+
+    /// How far the fluid searches for a way down when spreading sideways.
+    #[inline]
+    fn max_flow_distance(&self) -> u8 {
+        4
+    }
+
+    /// How much the level decreases each block the fluid flows sideways.
+    #[inline]
+    fn level_decrease_per_block(&self) -> u8 {
+        1
+    }
+}
+
+impl ProvideFluidBehavior for () {}
+
+/// Global contexts that is able to obtain flowing and still states of
+/// fluids.
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.getFlowing` and `FlowableFluid.getStill` (yarn).
+pub trait ProvideFlowingStates<'w>: ProvideFluidStateExtTy {
+    /// Gets the flowing state with given level, of the same type as given
+    /// fluid state.
+    fn flowing_state(fs: &FluidState<'w, Self>, level: u8, falling: bool) -> FluidState<'w, Self>;
+
+    /// Gets the still state of the same type as given fluid state.
+    fn still_state(fs: &FluidState<'w, Self>, falling: bool) -> FluidState<'w, Self>;
+
+    /// Gets the empty fluid state.
+    fn empty_state() -> FluidState<'w, Self>;
+}
@@ -1,12 +1,16 @@
 //! Minecraft Fluid primitives.
 
+use behave::ProvideFluidBehavior;
 use rimecraft_block::{BlockState, ProvideBlockStateExtTy};
 use rimecraft_global_cx::ProvideIdTy;
 use rimecraft_maybe::Maybe;
 use rimecraft_registry::{ProvideRegistry, Reg};
 use rimecraft_state::{State, States};
+use rimecraft_voxel_math::direction::Direction;
 
-use std::{fmt::Debug, hash::Hash, marker::PhantomData, sync::Arc};
+use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+
+pub mod behave;
 
 pub use rimecraft_state as state;
 
@@ -86,7 +90,70 @@ where
     /// The fluid.
     pub fluid: Fluid<'w, Cx>,
     /// The state.
-    pub state: Arc<State<'w, Cx::FluidStateExt>>,
+    pub state: &'w State<'w, Cx::FluidStateExt>,
+}
+
+impl<Cx> FluidState<'_, Cx>
+where
+    Cx: ProvideFluidStateExtTy,
+{
+    /// Whether this fluid state is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fluid.settings().is_empty
+    }
+}
+
+impl<Cx> FluidState<'_, Cx>
+where
+    Cx: ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+{
+    /// Returns the level of this fluid state, from `0` to `8`.
+    #[inline]
+    pub fn level(&self) -> u8 {
+        self.state.data().level(self.state)
+    }
+
+    /// Whether this fluid state is a source.
+    #[inline]
+    pub fn is_still(&self) -> bool {
+        self.state.data().is_still(self.state)
+    }
+
+    /// Whether this fluid state is falling.
+    #[inline]
+    pub fn is_falling(&self) -> bool {
+        self.state.data().is_falling(self.state)
+    }
+
+    /// Returns the height of this fluid state itself, ignoring fluids above it.
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.state.data().height(self.state)
+    }
+
+    /// Returns the delay of scheduled ticks of this fluid state, in ticks.
+    #[inline]
+    pub fn tick_rate(&self) -> u32 {
+        self.state.data().tick_rate()
+    }
+
+    /// Whether this fluid state is of the same type as the other one, such as
+    /// flowing and still water.
+    #[inline]
+    pub fn matches_type(&self, other: &Self) -> bool {
+        self.state.data().matches_type(other.state.data())
+    }
+
+    /// Whether this fluid state could be replaced with the other one flowing
+    /// in towards given direction.
+    #[inline]
+    pub fn can_be_replaced_with(&self, other: &Self, direction: Direction) -> bool {
+        self.state
+            .data()
+            .can_be_replaced_with(self.state, other.state, direction)
+    }
 }
 
 impl<Cx> Debug for FluidState<'_, Cx>
@@ -111,7 +178,7 @@ where
     fn clone(&self) -> Self {
         Self {
             fluid: self.fluid,
-            state: self.state.clone(),
+            state: self.state,
         }
     }
 }
@@ -122,7 +189,7 @@ where
 {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.fluid == other.fluid && Arc::ptr_eq(&self.state, &other.state)
+        self.fluid == other.fluid && std::ptr::eq(self.state, other.state)
     }
 }
 
@@ -135,7 +202,7 @@ where
     #[inline]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.fluid.hash(state);
-        Arc::as_ptr(&self.state).hash(state);
+        std::ptr::from_ref(self.state).hash(state);
     }
 }
 
@@ -150,6 +217,21 @@ where
     ) -> Maybe<'a, FluidState<'w, Self>>;
 }
 
+/// Global Contexts that is able to convert [`FluidState`] to [`BlockState`] instances.
+///
+/// # MCJE Reference
+///
+/// This represents `FluidState.getBlockState` (yarn).
+pub trait FsToBs<'w>
+where
+    Self: ProvideFluidStateExtTy + ProvideBlockStateExtTy,
+{
+    /// Converts a fluid state to the block state placing it in the world.
+    ///
+    /// Empty fluid states should be converted to an empty block state.
+    fn fluid_to_block_state(fs: &FluidState<'w, Self>) -> BlockState<'w, Self>;
+}
+
 /// Extenstions to the `Maybe<'_, IBlockState<'_, _>>`.
 pub trait BlockStateExt<'a, 'w, Cx>
 where
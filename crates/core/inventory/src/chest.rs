@@ -0,0 +1,511 @@
+//! Chest block entity data and the container component.
+
+use std::{fmt::Debug, hash::Hash};
+
+use edcode2::{Buf, BufMut, Decode, Encode};
+use rimecraft_block_entity::{
+    component::{
+        changes::ComponentChanges,
+        map::{Builder, ComponentMap},
+        ComponentType, ErasedComponentType,
+    },
+    ComponentsAccess, Data,
+};
+use rimecraft_global_cx::ProvideIdTy;
+use rimecraft_item::{stack::ItemStackCx, ItemSettings, ItemStack, RawItem};
+use rimecraft_registry::{entry::RefEntry, Reg, Registry};
+use rimecraft_serde_update::Update;
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+use crate::{Inventory, InventoryCx, SimpleInventory};
+
+/// The number of slots of a single chest.
+pub const CHEST_SIZE: usize = 27;
+
+/// Provides component types of containers.
+pub trait ProvideContainerComponentType<'r>: ProvideIdTy {
+    /// The type of contents of a container, whose value is a [`ContainerComponent`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.CONTAINER` (yarn).
+    fn container() -> ErasedComponentType<'r, Self>;
+}
+
+/// Item stacks stored in a container, such as a chest or a shulker box.
+///
+/// # MCJE Reference
+///
+/// This represents `ContainerComponent` (yarn).
+pub struct ContainerComponent<'r, Cx>
+where
+    Cx: ItemStackCx,
+{
+    stacks: Vec<ItemStack<'r, Cx>>,
+}
+
+impl<'r, Cx> ContainerComponent<'r, Cx>
+where
+    Cx: ItemStackCx,
+{
+    /// Returns the stacks of this container, including empty ones.
+    #[inline]
+    pub fn stacks(&self) -> &[ItemStack<'r, Cx>] {
+        &self.stacks
+    }
+}
+
+impl<'r, Cx> ContainerComponent<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    /// Copies the stacks of this container into the given inventory,
+    /// emptying the slots that are not covered by this container.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ContainerComponent.copyTo` (yarn).
+    pub fn copy_to<I>(&self, inventory: &mut I)
+    where
+        I: Inventory<'r, Cx> + ?Sized,
+    {
+        for slot in 0..inventory.size() {
+            if let Some(stack) = inventory.stack_mut(slot) {
+                match self.stacks.get(slot) {
+                    Some(copied) => *stack = copied.clone(),
+                    None => stack.decrement(stack.count()),
+                }
+            }
+        }
+        inventory.mark_dirty();
+    }
+}
+
+impl<'r, Cx> From<Vec<ItemStack<'r, Cx>>> for ContainerComponent<'r, Cx>
+where
+    Cx: ItemStackCx,
+{
+    #[inline]
+    fn from(stacks: Vec<ItemStack<'r, Cx>>) -> Self {
+        Self { stacks }
+    }
+}
+
+impl<Cx> Clone for ContainerComponent<'_, Cx>
+where
+    Cx: ItemStackCx,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            stacks: self.stacks.clone(),
+        }
+    }
+}
+
+impl<Cx> PartialEq for ContainerComponent<'_, Cx>
+where
+    Cx: ItemStackCx,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.stacks == other.stacks
+    }
+}
+
+impl<Cx> Eq for ContainerComponent<'_, Cx> where Cx: ItemStackCx {}
+
+impl<Cx> Hash for ContainerComponent<'_, Cx>
+where
+    Cx: ItemStackCx,
+{
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.stacks.hash(state);
+    }
+}
+
+impl<'r, Cx> Debug for ContainerComponent<'r, Cx>
+where
+    Cx: ItemStackCx<Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&self.stacks).finish()
+    }
+}
+
+impl<Cx> Serialize for ContainerComponent<'_, Cx>
+where
+    Cx: ItemStackCx<Id: Serialize>,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.stacks.serialize(serializer)
+    }
+}
+
+impl<'r, 'de, Cx> Deserialize<'de> for ContainerComponent<'r, Cx>
+where
+    Cx: ItemStackCx,
+    ItemStack<'r, Cx>: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::deserialize(deserializer).map(|stacks| Self { stacks })
+    }
+}
+
+impl<'r, Cx, B> Encode<B> for ContainerComponent<'r, Cx>
+where
+    Cx: ItemStackCx,
+    ItemStack<'r, Cx>: for<'b> Encode<&'b mut B>,
+    B: BufMut,
+{
+    #[inline]
+    fn encode(&self, buf: B) -> Result<(), edcode2::BoxedError<'static>> {
+        self.stacks.encode(buf)
+    }
+}
+
+impl<'r, 'de, Cx, B> Decode<'de, B> for ContainerComponent<'r, Cx>
+where
+    Cx: ItemStackCx,
+    ItemStack<'r, Cx>: for<'b> Decode<'de, &'b mut B>,
+    B: Buf,
+{
+    #[inline]
+    fn decode(buf: B) -> Result<Self, edcode2::BoxedError<'de>> {
+        Vec::decode(buf).map(|stacks| Self { stacks })
+    }
+}
+
+/// Block entity data of a chest.
+///
+/// The inventory is stored in the `Items` field, and is read from and
+/// written to the container component of the block entity.
+///
+/// # MCJE Reference
+///
+/// This represents the inventory part of `ChestBlockEntity` (yarn).
+pub struct ChestData<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    inventory: SimpleInventory<'r, Cx>,
+}
+
+impl<'r, Cx> ChestData<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    /// Creates a new chest data with an empty inventory, whose slots hold
+    /// the default item of the given registry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the registry does not have a default entry.
+    #[inline]
+    pub fn new(items: &'r Registry<Cx::Id, RawItem<'r, Cx>>) -> Self {
+        Self {
+            inventory: SimpleInventory::new(items, CHEST_SIZE),
+        }
+    }
+
+    /// Returns the inventory of this chest.
+    #[inline]
+    pub fn inventory(&self) -> &SimpleInventory<'r, Cx> {
+        &self.inventory
+    }
+
+    /// Returns the mutable inventory of this chest.
+    #[inline]
+    pub fn inventory_mut(&mut self) -> &mut SimpleInventory<'r, Cx> {
+        &mut self.inventory
+    }
+
+    #[inline]
+    fn container_type() -> Option<ComponentType<'r, ContainerComponent<'r, Cx>>>
+    where
+        Cx: ProvideContainerComponentType<'r>,
+    {
+        // SAFETY: the lifetime of the container component is the same as the
+        // lifetime of component maps of the block entity.
+        unsafe { Reg::into_inner(Cx::container()).downcast() }
+    }
+}
+
+impl<'r, Cx> Data<'r, Cx> for ChestData<'r, Cx>
+where
+    Cx: InventoryCx<'r> + ProvideContainerComponentType<'r>,
+    ContainerComponent<'r, Cx>: Send + Sync,
+{
+    fn read_components(&mut self, mut accessor: ComponentsAccess<'_, 'r, Cx>) {
+        let Some(ty) = Self::container_type() else {
+            return;
+        };
+        // SAFETY: see `container_type`.
+        if let Some(container) = unsafe { accessor.get(&ty) } {
+            container.copy_to(&mut self.inventory);
+        } else {
+            self.inventory.clear();
+        }
+    }
+
+    fn insert_components(&self, builder: &mut Builder<'r, Cx>) {
+        if Self::container_type().is_some() {
+            builder.insert(
+                Cx::container(),
+                ContainerComponent::from(self.inventory.stacks().to_vec()),
+            );
+        }
+    }
+}
+
+impl<'r, Cx> Debug for ChestData<'r, Cx>
+where
+    Cx: InventoryCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChestData")
+            .field("inventory", &self.inventory)
+            .finish()
+    }
+}
+
+/// An item stack with its slot index, in the `Items` field of containers.
+struct SlotStack<'a, 'r, Cx>(u8, &'a ItemStack<'r, Cx>)
+where
+    Cx: ItemStackCx;
+
+const FIELD_ITEMS: &str = "Items";
+
+impl<Cx> Serialize for SlotStack<'_, '_, Cx>
+where
+    Cx: ItemStackCx<Id: Serialize>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{Error, SerializeStruct};
+        let stack = self.1;
+        let entry: &RefEntry<_, _> = stack.item().into();
+        let mut state = serializer.serialize_struct("SlotStack", 4)?;
+        state.serialize_field("Slot", &self.0)?;
+        state.serialize_field("id", entry)?;
+        state.serialize_field("count", &stack.count())?;
+        state.serialize_field(
+            "components",
+            &stack
+                .components()
+                .changes()
+                .ok_or_else(|| S::Error::custom("components not patched"))?,
+        )?;
+        state.end()
+    }
+}
+
+/// Data of an item stack with its slot index, whose item is not resolved yet.
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "Cx::Id: Deserialize<'de>, \
+    ComponentChanges<'r, 'r, Cx>: Deserialize<'de>"))]
+struct RawSlotStack<'r, Cx>
+where
+    Cx: ItemStackCx,
+{
+    #[serde(rename = "Slot")]
+    slot: u8,
+    id: Cx::Id,
+    // Stacks without count contain exactly one item, as in vanilla.
+    #[serde(default = "default_count")]
+    count: u32,
+    #[serde(default)]
+    components: Option<ComponentChanges<'r, 'r, Cx>>,
+}
+
+#[inline]
+const fn default_count() -> u32 {
+    1
+}
+
+impl<'r, Cx> RawSlotStack<'r, Cx>
+where
+    Cx: ItemStackCx<Id: std::fmt::Display>,
+{
+    fn resolve(
+        self,
+        items: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<(u8, ItemStack<'r, Cx>), String> {
+        let item = items
+            .get(&self.id)
+            .ok_or_else(|| format!("unknown item {}", self.id))?;
+        let base = Reg::into_inner(item).settings().components();
+        let components = match self.components {
+            Some(changes) => ComponentMap::with_changes(base, changes),
+            None => ComponentMap::new(base),
+        };
+        Ok((
+            self.slot,
+            ItemStack::with_component(item, self.count, components),
+        ))
+    }
+}
+
+impl<'r, Cx> Serialize for ChestData<'r, Cx>
+where
+    Cx: InventoryCx<'r, Id: Serialize>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct Items<'a, 'r, Cx: InventoryCx<'r>>(&'a SimpleInventory<'r, Cx>);
+
+        impl<'r, Cx> Serialize for Items<'_, 'r, Cx>
+        where
+            Cx: InventoryCx<'r, Id: Serialize>,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                use serde::ser::{Error, SerializeSeq};
+                let mut seq = serializer.serialize_seq(None)?;
+                for (slot, stack) in self.0.stacks().iter().enumerate() {
+                    if stack.is_empty() {
+                        continue;
+                    }
+                    let slot = u8::try_from(slot).map_err(|_| {
+                        S::Error::custom(format_args!("slot {slot} does not fit in a byte"))
+                    })?;
+                    seq.serialize_element(&SlotStack(slot, stack))?;
+                }
+                seq.end()
+            }
+        }
+
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("ChestData", 1)?;
+        state.serialize_field(FIELD_ITEMS, &Items(&self.inventory))?;
+        state.end()
+    }
+}
+
+/// [`DeserializeSeed`] of [`ChestData`], resolving items of stored stacks
+/// through the given registry.
+pub struct ChestSeed<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    items: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+}
+
+impl<'r, Cx> ChestSeed<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    /// Creates a seed resolving items through the given registry.
+    #[inline]
+    pub const fn new(items: &'r Registry<Cx::Id, RawItem<'r, Cx>>) -> Self {
+        Self { items }
+    }
+}
+
+impl<'r, Cx> Clone for ChestSeed<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'r, Cx> Copy for ChestSeed<'r, Cx> where Cx: InventoryCx<'r> {}
+
+impl<'r, Cx> Debug for ChestSeed<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChestSeed").finish_non_exhaustive()
+    }
+}
+
+impl<'r, 'de, Cx> DeserializeSeed<'de> for ChestSeed<'r, Cx>
+where
+    Cx: InventoryCx<'r, Id: Deserialize<'de> + std::fmt::Display>,
+    ComponentChanges<'r, 'r, Cx>: Deserialize<'de>,
+{
+    type Value = ChestData<'r, Cx>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor<'r, Cx>(ChestSeed<'r, Cx>)
+        where
+            Cx: InventoryCx<'r>;
+
+        impl<'r, 'de, Cx> serde::de::Visitor<'de> for Visitor<'r, Cx>
+        where
+            Cx: InventoryCx<'r, Id: Deserialize<'de> + std::fmt::Display>,
+            ComponentChanges<'r, 'r, Cx>: Deserialize<'de>,
+        {
+            type Value = ChestData<'r, Cx>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("a chest")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut data = ChestData::new(self.0.items);
+                while let Some(key) = map.next_key::<std::borrow::Cow<'de, str>>()? {
+                    if key == FIELD_ITEMS {
+                        let items: Vec<RawSlotStack<'r, Cx>> = map.next_value()?;
+                        for raw in items {
+                            let (slot, stack) = raw
+                                .resolve(self.0.items)
+                                .map_err(serde::de::Error::custom)?;
+                            if let Some(s) = data.inventory.stack_mut(slot as usize) {
+                                *s = stack;
+                            }
+                        }
+                    } else {
+                        map.next_value::<serde::de::IgnoredAny>()?;
+                    }
+                }
+                Ok(data)
+            }
+        }
+
+        deserializer.deserialize_struct("ChestData", &[FIELD_ITEMS], Visitor(self))
+    }
+}
+
+/// Updating a chest replaces its inventory, resolving items through the
+/// registry of the items it already holds.
+impl<'r, 'de, Cx> Update<'de> for ChestData<'r, Cx>
+where
+    Cx: InventoryCx<'r, Id: Deserialize<'de> + std::fmt::Display>,
+    ComponentChanges<'r, 'r, Cx>: Deserialize<'de>,
+{
+    fn update<D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // Slots of a chest always hold a stack, so the registry is reachable.
+        let items = Reg::registry(self.inventory.stacks()[0].item());
+        *self = ChestSeed::new(items).deserialize(deserializer)?;
+        Ok(())
+    }
+}
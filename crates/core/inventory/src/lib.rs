@@ -0,0 +1,136 @@
+//! Minecraft inventory primitives.
+
+use rimecraft_item::{
+    stack::ItemStackCx, Item, ItemStack, ProvideItemComponentTypes, MAX_STACK_COUNT,
+};
+
+pub mod chest;
+mod simple;
+mod transaction;
+
+pub use simple::SimpleInventory;
+pub use transaction::Transaction;
+
+/// Global context used for inventories.
+pub trait InventoryCx<'r>: ItemStackCx + ProvideItemComponentTypes<'r> {}
+
+impl<'r, T> InventoryCx<'r> for T where T: ItemStackCx + ProvideItemComponentTypes<'r> {}
+
+/// An object holding a fixed number of slots of item stacks.
+///
+/// # MCJE Reference
+///
+/// This represents `Inventory` (yarn).
+pub trait Inventory<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    /// Returns the number of slots of this inventory.
+    fn size(&self) -> usize;
+
+    /// Returns the stack in the given slot, or `None` if the slot is out of bounds.
+    fn stack(&self, slot: usize) -> Option<&ItemStack<'r, Cx>>;
+
+    /// Returns the mutable stack in the given slot, or `None` if the slot is out of bounds.
+    ///
+    /// Callers modifying the stack should call [`Inventory::mark_dirty`] afterwards.
+    fn stack_mut(&mut self, slot: usize) -> Option<&mut ItemStack<'r, Cx>>;
+
+    /// Sets the stack in the given slot, capping its count to the max count of
+    /// this inventory, and returns the previous one.
+    ///
+    /// Returns `None` and drops the stack if the slot is out of bounds.
+    fn set_stack(
+        &mut self,
+        slot: usize,
+        mut stack: ItemStack<'r, Cx>,
+    ) -> Option<ItemStack<'r, Cx>> {
+        let max = self.max_count(&stack);
+        if stack.count() > max {
+            stack.set_count(max);
+        }
+        let old = std::mem::replace(self.stack_mut(slot)?, stack);
+        self.mark_dirty();
+        Some(old)
+    }
+
+    /// Removes at most `amount` items from the stack in the given slot, and
+    /// returns them as a new stack, or `None` if the slot is out of bounds.
+    fn remove_stack(&mut self, slot: usize, amount: u32) -> Option<ItemStack<'r, Cx>> {
+        let stack = self.stack_mut(slot)?.split(amount);
+        if !stack.is_empty() {
+            self.mark_dirty();
+        }
+        Some(stack)
+    }
+
+    /// Takes the whole stack out of the given slot, leaving an empty stack in it,
+    /// or returns `None` if the slot is out of bounds.
+    fn take_stack(&mut self, slot: usize) -> Option<ItemStack<'r, Cx>> {
+        self.stack_mut(slot).map(|stack| stack.split(stack.count()))
+    }
+
+    /// Returns the max count of stacks in this inventory.
+    #[inline]
+    fn max_count_per_stack(&self) -> u32 {
+        MAX_STACK_COUNT
+    }
+
+    /// Returns the max count of the given stack in this inventory, which
+    /// respects both this inventory and the stack itself.
+    #[inline]
+    fn max_count(&self, stack: &ItemStack<'r, Cx>) -> u32 {
+        self.max_count_per_stack().min(stack.max_count())
+    }
+
+    /// Whether the given stack is allowed to be put into the given slot.
+    ///
+    /// This is the filter of slots and is respected by transactions.
+    #[inline]
+    fn is_valid(&self, slot: usize, stack: &ItemStack<'r, Cx>) -> bool {
+        let _ = (slot, stack);
+        true
+    }
+
+    /// Marks this inventory as changed.
+    #[inline]
+    fn mark_dirty(&mut self) {}
+
+    /// Whether all slots of this inventory are empty.
+    fn is_empty(&self) -> bool {
+        (0..self.size())
+            .filter_map(|slot| self.stack(slot))
+            .all(ItemStack::is_empty)
+    }
+
+    /// Counts the items of the given item in this inventory.
+    fn count(&self, item: Item<'r, Cx>) -> u32 {
+        (0..self.size())
+            .filter_map(|slot| self.stack(slot))
+            .filter(|stack| stack.item() == item)
+            .map(ItemStack::count)
+            .sum()
+    }
+
+    /// Empties all slots of this inventory.
+    fn clear(&mut self) {
+        for slot in 0..self.size() {
+            if let Some(stack) = self.stack_mut(slot) {
+                stack.decrement(stack.count());
+            }
+        }
+        self.mark_dirty();
+    }
+
+    /// Begins a new transaction on this inventory.
+    #[inline]
+    fn transaction(&mut self) -> Transaction<'_, 'r, Self, Cx>
+    where
+        Self: Sized,
+    {
+        Transaction::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests;
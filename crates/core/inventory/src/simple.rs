@@ -0,0 +1,129 @@
+use std::fmt::Debug;
+
+use rimecraft_item::{ItemStack, RawItem};
+use rimecraft_registry::Registry;
+
+use crate::{Inventory, InventoryCx};
+
+/// An inventory backed by a list of item stacks.
+///
+/// # MCJE Reference
+///
+/// This represents `SimpleInventory` (yarn).
+pub struct SimpleInventory<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    stacks: Vec<ItemStack<'r, Cx>>,
+}
+
+impl<'r, Cx> SimpleInventory<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    /// Creates a new inventory with given number of empty slots, which hold
+    /// the default item of the given registry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the registry does not have a default entry.
+    pub fn new(items: &'r Registry<Cx::Id, RawItem<'r, Cx>>, size: usize) -> Self {
+        let empty = items
+            .default_entry()
+            .expect("registry of items should have a default entry");
+        Self {
+            stacks: std::iter::repeat_with(|| ItemStack::new(empty, 0))
+                .take(size)
+                .collect(),
+        }
+    }
+
+    /// Returns the stacks of this inventory.
+    #[inline]
+    pub fn stacks(&self) -> &[ItemStack<'r, Cx>] {
+        &self.stacks
+    }
+
+    /// Returns the mutable stacks of this inventory.
+    #[inline]
+    pub fn stacks_mut(&mut self) -> &mut [ItemStack<'r, Cx>] {
+        &mut self.stacks
+    }
+
+    /// Adds the given stack into this inventory, merging it into existing
+    /// stacks first, and returns the remainder that does not fit.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `SimpleInventory.addStack` (yarn).
+    pub fn add_stack(&mut self, mut stack: ItemStack<'r, Cx>) -> ItemStack<'r, Cx> {
+        let mut transaction = self.transaction();
+        let inserted = transaction.insert(&stack, stack.count());
+        transaction.commit();
+        stack.decrement(inserted);
+        stack
+    }
+}
+
+impl<'r, Cx> Inventory<'r, Cx> for SimpleInventory<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    #[inline]
+    fn size(&self) -> usize {
+        self.stacks.len()
+    }
+
+    #[inline]
+    fn stack(&self, slot: usize) -> Option<&ItemStack<'r, Cx>> {
+        self.stacks.get(slot)
+    }
+
+    #[inline]
+    fn stack_mut(&mut self, slot: usize) -> Option<&mut ItemStack<'r, Cx>> {
+        self.stacks.get_mut(slot)
+    }
+}
+
+impl<'r, Cx> From<Vec<ItemStack<'r, Cx>>> for SimpleInventory<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    #[inline]
+    fn from(stacks: Vec<ItemStack<'r, Cx>>) -> Self {
+        Self { stacks }
+    }
+}
+
+impl<'r, Cx> From<SimpleInventory<'r, Cx>> for Vec<ItemStack<'r, Cx>>
+where
+    Cx: InventoryCx<'r>,
+{
+    #[inline]
+    fn from(inventory: SimpleInventory<'r, Cx>) -> Self {
+        inventory.stacks
+    }
+}
+
+impl<'r, Cx> Clone for SimpleInventory<'r, Cx>
+where
+    Cx: InventoryCx<'r>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            stacks: self.stacks.clone(),
+        }
+    }
+}
+
+impl<'r, Cx> Debug for SimpleInventory<'r, Cx>
+where
+    Cx: InventoryCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleInventory")
+            .field("stacks", &self.stacks)
+            .finish()
+    }
+}
@@ -0,0 +1,196 @@
+use rimecraft_block_entity::component::{
+    map::ComponentMap, test_global_integration as component_types, ComponentType,
+    ErasedComponentType, PacketCodec, SerdeCodec,
+};
+use rimecraft_block_entity::Data;
+use rimecraft_item::{
+    test_global_integration::{component_type, id, init_registries, item, key, Settings},
+    ItemStack, MaxStackSize, RawItem,
+};
+use rimecraft_registry::{Reg, Registry, RegistryKey};
+use serde::de::DeserializeSeed;
+use test_global::{Id, TestContext};
+
+use crate::{
+    chest::{ChestData, ChestSeed, ContainerComponent, ProvideContainerComponentType},
+    Inventory, SimpleInventory,
+};
+
+const CONTAINER_PACKET_CODEC: PacketCodec<'static, ContainerComponent<'static, TestContext>> =
+    rimecraft_block_entity::component::packet_codec_edcode();
+const CONTAINER_SERDE_CODEC: SerdeCodec<'static, ContainerComponent<'static, TestContext>> =
+    rimecraft_block_entity::component::serde_codec();
+
+static CONTAINER: ComponentType<'static, ContainerComponent<'static, TestContext>> =
+    ComponentType::builder::<TestContext>()
+        .packet_codec(&CONTAINER_PACKET_CODEC)
+        .serde_codec(&CONTAINER_SERDE_CODEC)
+        .build();
+
+impl ProvideContainerComponentType<'static> for TestContext {
+    fn container() -> ErasedComponentType<'static, Self> {
+        component_type("container")
+    }
+}
+
+fn init() {
+    component_types::peek_registry_mut(|types| {
+        types
+            .register(
+                RegistryKey::new(component_types::REGISTRY_ID, id("container")),
+                (&CONTAINER).into(),
+            )
+            .unwrap();
+    });
+    init_registries(|items| {
+        let settings = |max: Option<u32>| {
+            let mut builder = ComponentMap::builder();
+            if let Some(max) = max {
+                builder.insert(component_type("max_stack_size"), MaxStackSize(max));
+            }
+            Settings(builder.build())
+        };
+        items
+            .register_default(key("air"), RawItem::new(settings(Some(64))))
+            .unwrap();
+        for (id, max) in [("stone", Some(64)), ("egg", Some(16)), ("sword", None)] {
+            items
+                .register(key(id), RawItem::new(settings(max)))
+                .unwrap();
+        }
+    });
+}
+
+fn items() -> &'static Registry<Id, RawItem<'static, TestContext>> {
+    Reg::registry(item("air"))
+}
+
+fn stack(id: &str, count: u32) -> ItemStack<'static, TestContext> {
+    ItemStack::new(item(id), count)
+}
+
+/// An inventory only accepting eggs in its first slot.
+#[derive(Debug)]
+struct EggSlotInventory(SimpleInventory<'static, TestContext>);
+
+impl Inventory<'static, TestContext> for EggSlotInventory {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn stack(&self, slot: usize) -> Option<&ItemStack<'static, TestContext>> {
+        self.0.stack(slot)
+    }
+
+    fn stack_mut(&mut self, slot: usize) -> Option<&mut ItemStack<'static, TestContext>> {
+        self.0.stack_mut(slot)
+    }
+
+    fn is_valid(&self, slot: usize, stack: &ItemStack<'static, TestContext>) -> bool {
+        slot != 0 || stack.item() == item("egg")
+    }
+}
+
+#[test]
+fn add_stack() {
+    init();
+    let mut inventory = SimpleInventory::new(items(), 3);
+    assert!(inventory.add_stack(stack("stone", 40)).is_empty());
+    assert!(inventory.add_stack(stack("stone", 40)).is_empty());
+    assert_eq!(inventory.stacks()[0].count(), 64);
+    assert_eq!(inventory.stacks()[1].count(), 16);
+
+    assert!(inventory.add_stack(stack("sword", 1)).is_empty());
+    assert_eq!(inventory.stacks()[2].item(), item("sword"));
+
+    let remainder = inventory.add_stack(stack("stone", 64));
+    assert_eq!(remainder.count(), 16);
+    assert_eq!(inventory.count(item("stone")), 128);
+}
+
+#[test]
+fn transaction() {
+    init();
+    let mut inventory = SimpleInventory::new(items(), 2);
+    inventory.set_stack(0, stack("egg", 10));
+
+    let mut transaction = inventory.transaction();
+    assert_eq!(transaction.insert(&stack("egg", 1), 30), 22);
+    assert_eq!(transaction.stack(0).unwrap().count(), 16);
+    assert_eq!(transaction.stack(1).unwrap().count(), 16);
+    transaction.abort();
+    assert_eq!(inventory.stacks()[0].count(), 10);
+    assert!(inventory.stacks()[1].is_empty());
+
+    let mut transaction = inventory.transaction();
+    assert_eq!(transaction.insert(&stack("egg", 1), 8), 8);
+    assert_eq!(transaction.extract(&stack("egg", 1), 20), 18);
+    assert_eq!(transaction.extract(&stack("stone", 1), 20), 0);
+    transaction.commit();
+    assert!(inventory.is_empty());
+}
+
+#[test]
+fn slot_filter() {
+    init();
+    let mut inventory = EggSlotInventory(SimpleInventory::new(items(), 2));
+    let mut transaction = inventory.transaction();
+    assert_eq!(transaction.insert(&stack("stone", 1), 100), 64);
+    assert_eq!(transaction.insert_slot(0, &stack("egg", 1), 100), 16);
+    transaction.commit();
+    assert_eq!(inventory.stack(0).unwrap().item(), item("egg"));
+    assert_eq!(inventory.stack(1).unwrap().item(), item("stone"));
+
+    let removed = inventory.remove_stack(1, 60).unwrap();
+    assert_eq!(removed.count(), 60);
+    assert_eq!(inventory.take_stack(1).unwrap().count(), 4);
+    assert!(inventory.take_stack(2).is_none());
+    assert!(inventory.stack(1).unwrap().is_empty());
+}
+
+#[test]
+fn chest_components() {
+    init();
+    let mut chest = ChestData::new(items());
+    chest.inventory_mut().set_stack(3, stack("stone", 5));
+
+    let mut builder = ComponentMap::builder();
+    chest.insert_components(&mut builder);
+    let map = builder.build();
+    let container = unsafe { map.get(&CONTAINER) }.expect("missing container");
+    assert_eq!(container.stacks().len(), crate::chest::CHEST_SIZE);
+    assert_eq!(container.stacks()[3].count(), 5);
+
+    let mut inventory = SimpleInventory::new(items(), 4);
+    container.copy_to(&mut inventory);
+    assert_eq!(inventory.stacks()[3], stack("stone", 5));
+}
+
+#[test]
+fn chest_serde() {
+    init();
+    let mut chest = ChestData::new(items());
+    chest.inventory_mut().set_stack(1, stack("stone", 5));
+    chest.inventory_mut().set_stack(20, stack("sword", 1));
+
+    let json = serde_json::to_value(&chest).expect("serialize failed");
+    let stored = json["Items"].as_array().expect("missing items");
+    assert_eq!(stored.len(), 2);
+    assert_eq!(stored[0]["Slot"], 1);
+    assert_eq!(stored[1]["id"], "minecraft:sword");
+
+    let json = json.to_string();
+    let de = ChestSeed::new(items())
+        .deserialize(&mut serde_json::Deserializer::from_str(&json))
+        .expect("deserialize failed");
+    assert_eq!(de.inventory().stacks(), chest.inventory().stacks());
+
+    let mut updated = ChestData::new(items());
+    updated.inventory_mut().set_stack(3, stack("egg", 2));
+    rimecraft_serde_update::Update::update(
+        &mut updated,
+        &mut serde_json::Deserializer::from_str(&json),
+    )
+    .expect("update failed");
+    assert_eq!(updated.inventory().stacks(), chest.inventory().stacks());
+}
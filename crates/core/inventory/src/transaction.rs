@@ -0,0 +1,185 @@
+use std::fmt::Debug;
+
+use rimecraft_item::ItemStack;
+
+use crate::{Inventory, InventoryCx};
+
+/// A set of changes to an inventory that are simulated first and applied
+/// to the inventory only when committed.
+///
+/// Slots touched by the transaction are copied aside, so dropping the
+/// transaction without committing it leaves the inventory unchanged, which
+/// makes it usable for simulating insertions and extractions.
+///
+/// This is modeled after transactions of the Fabric Transfer API.
+pub struct Transaction<'i, 'r, I, Cx>
+where
+    I: ?Sized,
+    Cx: InventoryCx<'r>,
+{
+    inventory: &'i mut I,
+    staged: Vec<Option<ItemStack<'r, Cx>>>,
+}
+
+impl<'i, 'r, I, Cx> Transaction<'i, 'r, I, Cx>
+where
+    I: Inventory<'r, Cx> + ?Sized,
+    Cx: InventoryCx<'r>,
+{
+    /// Begins a new transaction on the given inventory.
+    pub fn new(inventory: &'i mut I) -> Self {
+        Self {
+            staged: std::iter::repeat_with(|| None)
+                .take(inventory.size())
+                .collect(),
+            inventory,
+        }
+    }
+
+    /// Returns the stack in the given slot as seen by this transaction.
+    pub fn stack(&self, slot: usize) -> Option<&ItemStack<'r, Cx>> {
+        match self.staged.get(slot)? {
+            Some(stack) => Some(stack),
+            None => self.inventory.stack(slot),
+        }
+    }
+
+    fn stack_mut(&mut self, slot: usize) -> Option<&mut ItemStack<'r, Cx>> {
+        let staged = self.staged.get_mut(slot)?;
+        if staged.is_none() {
+            *staged = Some(self.inventory.stack(slot)?.clone());
+        }
+        staged.as_mut()
+    }
+
+    /// Inserts at most `max_amount` items of the given stack into the given
+    /// slot, and returns the count of inserted items.
+    ///
+    /// The count of the given stack is ignored, as it only describes the
+    /// item and components to insert.
+    pub fn insert_slot(&mut self, slot: usize, stack: &ItemStack<'r, Cx>, max_amount: u32) -> u32 {
+        self.insert_slot_raw(slot, stack, max_amount, true)
+    }
+
+    fn insert_slot_raw(
+        &mut self,
+        slot: usize,
+        stack: &ItemStack<'r, Cx>,
+        max_amount: u32,
+        into_empty: bool,
+    ) -> u32 {
+        if stack.is_empty() || max_amount == 0 || !self.inventory.is_valid(slot, stack) {
+            return 0;
+        }
+        let max = self.inventory.max_count(stack);
+        let Some(current) = self.stack(slot) else {
+            return 0;
+        };
+
+        if current.is_empty() {
+            if !into_empty {
+                return 0;
+            }
+            let amount = max_amount.min(max);
+            if let Some(current) = self.stack_mut(slot) {
+                *current = stack.copy_with_count(amount);
+            }
+            amount
+        } else if current.can_combine(stack) {
+            let amount = max_amount.min(max.saturating_sub(current.count()));
+            if amount > 0 {
+                if let Some(current) = self.stack_mut(slot) {
+                    current.increment(amount);
+                }
+            }
+            amount
+        } else {
+            0
+        }
+    }
+
+    /// Inserts at most `max_amount` items of the given stack into the
+    /// inventory, and returns the count of inserted items.
+    ///
+    /// Items are merged into existing stacks that could be combined with
+    /// the given stack first, and then put into empty slots. Slots that
+    /// are not valid for the stack are skipped.
+    ///
+    /// The count of the given stack is ignored, as it only describes the
+    /// item and components to insert.
+    pub fn insert(&mut self, stack: &ItemStack<'r, Cx>, max_amount: u32) -> u32 {
+        let mut remaining = max_amount;
+        for into_empty in [false, true] {
+            for slot in 0..self.staged.len() {
+                if remaining == 0 {
+                    break;
+                }
+                remaining -= self.insert_slot_raw(slot, stack, remaining, into_empty);
+            }
+        }
+        max_amount - remaining
+    }
+
+    /// Extracts at most `max_amount` items from the given slot, and returns
+    /// them as a new stack, or `None` if the slot is out of bounds.
+    pub fn extract_slot(&mut self, slot: usize, max_amount: u32) -> Option<ItemStack<'r, Cx>> {
+        self.stack_mut(slot)
+            .map(|current| current.split(max_amount))
+    }
+
+    /// Extracts at most `max_amount` items that could be combined with the
+    /// given stack from the inventory, and returns the count of extracted items.
+    ///
+    /// The count of the given stack is ignored, as it only describes the
+    /// item and components to extract.
+    pub fn extract(&mut self, stack: &ItemStack<'r, Cx>, max_amount: u32) -> u32 {
+        let mut remaining = max_amount;
+        for slot in 0..self.staged.len() {
+            if remaining == 0 {
+                break;
+            }
+            if self
+                .stack(slot)
+                .is_some_and(|current| !current.is_empty() && current.can_combine(stack))
+            {
+                remaining -= self
+                    .extract_slot(slot, remaining)
+                    .map_or(0, |stack| stack.count());
+            }
+        }
+        max_amount - remaining
+    }
+
+    /// Applies the changes of this transaction to the inventory.
+    pub fn commit(self) {
+        let mut dirty = false;
+        for (slot, stack) in self.staged.into_iter().enumerate() {
+            if let Some((stack, current)) = stack.zip(self.inventory.stack_mut(slot)) {
+                *current = stack;
+                dirty = true;
+            }
+        }
+        if dirty {
+            self.inventory.mark_dirty();
+        }
+    }
+
+    /// Discards the changes of this transaction.
+    ///
+    /// This is the same as dropping the transaction.
+    #[inline]
+    pub fn abort(self) {}
+}
+
+impl<'r, I, Cx> Debug for Transaction<'_, 'r, I, Cx>
+where
+    I: Debug + ?Sized,
+    Cx: InventoryCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transaction")
+            .field("inventory", &self.inventory)
+            .field("staged", &self.staged)
+            .finish()
+    }
+}
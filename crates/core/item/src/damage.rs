@@ -0,0 +1,227 @@
+//! Durability of item stacks.
+//!
+//! Durability is driven entirely by the damage and max damage components of
+//! item stacks, so items are damageable as long as their components say so.
+
+use component::ErasedComponentType;
+use rimecraft_global_cx::ProvideIdTy;
+use rimecraft_registry::Reg;
+
+use crate::{stack::ItemStackCx, Damage, Item, ItemStack, MaxDamage};
+
+/// Provides component types that the durability of item stacks relies on.
+pub trait ProvideDamageComponentTypes<'r>: ProvideIdTy {
+    /// The type of damage of an item stack, whose value is a [`Damage`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.DAMAGE` (yarn).
+    fn damage() -> ErasedComponentType<'r, Self>;
+
+    /// The type of max damage of an item stack, whose value is a [`MaxDamage`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.MAX_DAMAGE` (yarn).
+    fn max_damage() -> ErasedComponentType<'r, Self>;
+
+    /// The type marking an item stack as unbreakable.
+    ///
+    /// Only the presence of this component is taken into account.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.UNBREAKABLE` (yarn).
+    fn unbreakable() -> ErasedComponentType<'r, Self>;
+}
+
+/// The count of steps of the item bar.
+pub const ITEM_BAR_STEPS: u32 = 13;
+
+impl<'r, Cx> ItemStack<'r, Cx>
+where
+    Cx: ItemStackCx + ProvideDamageComponentTypes<'r>,
+{
+    /// Returns the max damage of this stack, or `0` if absent.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.getMaxDamage` (yarn).
+    pub fn max_damage(&self) -> u32 {
+        // SAFETY: `MaxDamage` does not contain any lifetime parameter.
+        unsafe { Reg::into_inner(Cx::max_damage()).downcast::<MaxDamage>() }
+            .and_then(|ty| self.get(&ty).copied())
+            .map_or(0, |MaxDamage(max)| max)
+    }
+
+    /// Returns the damage of this stack, which never exceeds the max damage.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.getDamage` (yarn).
+    pub fn current_damage(&self) -> u32 {
+        // SAFETY: `Damage` does not contain any lifetime parameter.
+        unsafe { Reg::into_inner(Cx::damage()).downcast::<Damage>() }
+            .and_then(|ty| self.get(&ty).copied())
+            .map_or(0, |Damage(damage)| damage)
+            .min(self.max_damage())
+    }
+
+    /// Sets the damage of this stack, capped to the max damage.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.setDamage` (yarn).
+    #[inline]
+    pub fn set_damage(&mut self, damage: u32) {
+        let damage = damage.min(self.max_damage());
+        // SAFETY: `Damage` does not contain any lifetime parameter.
+        unsafe { self.components_mut().insert(Cx::damage(), Damage(damage)) };
+    }
+
+    /// Whether this stack could be damaged, which means it has both damage
+    /// and max damage, and is not unbreakable.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.isDamageable` (yarn).
+    pub fn is_damageable(&self) -> bool {
+        let components = self.components();
+        components.contains_raw(&Cx::max_damage())
+            && !components.contains_raw(&Cx::unbreakable())
+            && components.contains_raw(&Cx::damage())
+    }
+
+    /// Whether this stack is damageable and has been damaged.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.isDamaged` (yarn).
+    #[inline]
+    pub fn is_damaged(&self) -> bool {
+        self.is_damageable() && self.current_damage() > 0
+    }
+
+    /// Returns the fraction of remaining durability of this stack, from
+    /// `0.0` to `1.0`.
+    ///
+    /// Stacks without max damage have full durability.
+    pub fn item_bar_fraction(&self) -> f32 {
+        let max = self.max_damage();
+        if max == 0 {
+            1.0
+        } else {
+            (max - self.current_damage()) as f32 / max as f32
+        }
+    }
+
+    /// Whether the item bar of this stack should be displayed.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `Item.isItemBarVisible` (yarn).
+    #[inline]
+    pub fn is_item_bar_visible(&self) -> bool {
+        self.is_damaged()
+    }
+
+    /// Returns the filled steps of the item bar of this stack, from `0`
+    /// to [`ITEM_BAR_STEPS`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `Item.getItemBarStep` (yarn).
+    #[inline]
+    pub fn item_bar_step(&self) -> u32 {
+        (self.item_bar_fraction() * ITEM_BAR_STEPS as f32).round() as u32
+    }
+
+    /// Returns the RGB color of the item bar of this stack, which fades from
+    /// green to red as the stack gets damaged.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `Item.getItemBarColor` (yarn).
+    #[inline]
+    pub fn item_bar_color(&self) -> u32 {
+        hsv_to_rgb(self.item_bar_fraction() / 3.0, 1.0, 1.0)
+    }
+
+    /// Damages this stack by the given amount, and returns whether the stack
+    /// broke.
+    ///
+    /// The amount is first passed through the `reduce` hook with the random
+    /// source, which is where effects like [`unbreaking`] apply. Once the
+    /// damage reaches the max damage, one item of the stack is consumed and
+    /// `on_break` is called with the broken item.
+    ///
+    /// Stacks that are not damageable are never damaged.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.damage` (yarn).
+    pub fn damage<R, H, B>(&mut self, amount: u32, rng: &mut R, reduce: H, on_break: B) -> bool
+    where
+        H: FnOnce(&Self, u32, &mut R) -> u32,
+        B: FnOnce(Item<'r, Cx>),
+    {
+        if amount == 0 || !self.is_damageable() {
+            return false;
+        }
+        let amount = reduce(self, amount, rng);
+        if amount == 0 {
+            return false;
+        }
+
+        let damage = self.current_damage().saturating_add(amount);
+        self.set_damage(damage);
+        if damage >= self.max_damage() {
+            let item = self.item();
+            self.decrement(1);
+            on_break(item);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Reduces the given amount of damage like the Unbreaking enchantment of
+/// given level, where each point of damage is ignored with a chance of
+/// `level / (level + 1)`.
+///
+/// The function `rng` receives an exclusive bound and returns a uniformly
+/// distributed random integer below it.
+///
+/// # MCJE Reference
+///
+/// This represents `UnbreakingEnchantment.shouldPreventDamage` (yarn),
+/// without the special case of armors.
+pub fn unbreaking<R>(amount: u32, level: u32, mut rng: R) -> u32
+where
+    R: FnMut(u32) -> u32,
+{
+    if level == 0 {
+        return amount;
+    }
+    (0..amount).filter(|_| rng(level + 1) == 0).count() as u32
+}
+
+/// Converts the given HSV color into an RGB color.
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> u32 {
+    let sector = (hue * 6.0) as u32 % 6;
+    let f = hue * 6.0 - sector as f32;
+    let p = value * (1.0 - saturation);
+    let q = value * (1.0 - f * saturation);
+    let t = value * (1.0 - (1.0 - f) * saturation);
+    let (r, g, b) = match sector {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    };
+    let channel = |c: f32| ((c * 255.0) as u32).min(255);
+    (channel(r) << 16) | (channel(g) << 8) | channel(b)
+}
@@ -2,7 +2,10 @@ use component::{changes::ComponentChanges, map::ComponentMap, RawErasedComponent
 use edcode2::{Buf, BufExt, BufMut, BufMutExt, Decode, Encode};
 use rimecraft_registry::{ProvideRegistry, Reg};
 
-use crate::{stack::ItemStackCx, Item, ItemSettings, ItemStack, RawItem};
+use crate::{
+    stack::ItemStackCx, Damage, Item, ItemSettings, ItemStack, MaxDamage, MaxStackSize, Rarity,
+    RawItem, RepairCost,
+};
 
 impl<'r, Cx, B> Encode<B> for ItemStack<'r, Cx>
 where
@@ -48,3 +51,84 @@ where
         }
     }
 }
+
+impl<Cx> ItemStack<'_, Cx>
+where
+    Cx: ItemStackCx,
+{
+    /// Returns the stable hash of this stack, covering its item, count and
+    /// components, which is deterministic across processes as long as items
+    /// and component types are registered in the same order.
+    ///
+    /// Empty stacks hash to `0`.
+    ///
+    /// See [`ComponentMap::stable_hash`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component fails to encode.
+    pub fn stable_hash(&self) -> Result<u32, edcode2::BoxedError<'static>> {
+        if self.is_empty() {
+            return Ok(0);
+        }
+        let mut canonical = Vec::with_capacity(12);
+        canonical.put_variable(Reg::raw_id(self.item()) as u32);
+        canonical.put_variable(self.count());
+        canonical.extend_from_slice(&self.components().stable_hash()?.to_be_bytes());
+        Ok(crc32c::crc32c(&canonical))
+    }
+}
+
+impl<B> Encode<B> for Rarity
+where
+    B: BufMut,
+{
+    #[inline]
+    fn encode(&self, mut buf: B) -> Result<(), edcode2::BoxedError<'static>> {
+        buf.put_variable(*self as u32);
+        Ok(())
+    }
+}
+
+impl<'de, B> Decode<'de, B> for Rarity
+where
+    B: Buf,
+{
+    #[inline]
+    fn decode(mut buf: B) -> Result<Self, edcode2::BoxedError<'de>> {
+        let index: u32 = buf.get_variable();
+        Rarity::ALL
+            .get(index as usize)
+            .copied()
+            .ok_or_else(|| format!("invalid rarity index {index}").into())
+    }
+}
+
+macro_rules! variable_u32_edcode {
+    ($($t:ident),* $(,)?) => {
+        $(
+            impl<B> Encode<B> for $t
+            where
+                B: BufMut,
+            {
+                #[inline]
+                fn encode(&self, mut buf: B) -> Result<(), edcode2::BoxedError<'static>> {
+                    buf.put_variable(self.0);
+                    Ok(())
+                }
+            }
+
+            impl<'de, B> Decode<'de, B> for $t
+            where
+                B: Buf,
+            {
+                #[inline]
+                fn decode(mut buf: B) -> Result<Self, edcode2::BoxedError<'de>> {
+                    Ok(Self(buf.get_variable()))
+                }
+            }
+        )*
+    };
+}
+
+variable_u32_edcode!(MaxStackSize, Damage, MaxDamage, RepairCost);
@@ -2,14 +2,20 @@
 
 use std::marker::PhantomData;
 
-use component::map::ComponentMap;
+use component::{map::ComponentMap, ErasedComponentType};
 use rimecraft_fmt::Formatting;
 use rimecraft_global_cx::ProvideIdTy;
 use rimecraft_registry::{ProvideRegistry, Reg};
 
+pub mod damage;
 #[cfg(feature = "edcode")]
 mod edcode;
 pub mod stack;
+pub mod test_global_integration;
+#[cfg(feature = "tooltip")]
+pub mod tooltip;
+#[cfg(feature = "vanilla")]
+pub mod vanilla;
 
 pub use component;
 pub use stack::ItemStack;
@@ -20,6 +26,16 @@ pub trait ProvideSettingsTy: ProvideIdTy {
     type Settings<'a>: ItemSettings<'a, Self>;
 }
 
+/// Provides built-in component types that item stacks rely on.
+pub trait ProvideItemComponentTypes<'r>: ProvideIdTy {
+    /// The type of max stack size of an item, whose value is a [`MaxStackSize`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.MAX_STACK_SIZE` (yarn).
+    fn max_stack_size() -> ErasedComponentType<'r, Self>;
+}
+
 /// Settings of an item.
 pub trait ItemSettings<'a, Cx>
 where
@@ -72,9 +88,51 @@ where
 /// An item usable by players and other entities.
 pub type Item<'r, Cx> = Reg<'r, <Cx as ProvideIdTy>::Id, RawItem<'r, Cx>>;
 
-/// The max item count of an `ItemStack`.
+/// The max item count of an `ItemStack` of common items.
+///
+/// The actual max count of a stack is read from its max stack size component.
+/// See [`ItemStack::max_count`].
 pub const MAX_STACK_COUNT: u32 = 64u32;
 
+/// The max stack size of an item stack, as a data component.
+///
+/// Component types are identified by the types of their values, so every
+/// integer component has its own wrapper type.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct MaxStackSize(pub u32);
+
+/// The damage of an item stack, as a data component.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Damage(pub u32);
+
+/// The max damage of an item stack, as a data component.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct MaxDamage(pub u32);
+
+/// The experience cost of repairing an item stack in an anvil, as a data component.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct RepairCost(pub u32);
+
 /// Rarity of an item.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
 #[non_exhaustive]
@@ -90,6 +148,22 @@ pub enum Rarity {
     Epic,
 }
 
+impl Rarity {
+    /// All rarities, ordered from the most common one.
+    pub const ALL: [Self; 4] = [Self::Common, Self::Uncommon, Self::Rare, Self::Epic];
+
+    /// Returns the name of this rarity.
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Common => "common",
+            Self::Uncommon => "uncommon",
+            Self::Rare => "rare",
+            Self::Epic => "epic",
+        }
+    }
+}
+
 impl From<Rarity> for Formatting {
     #[inline]
     fn from(value: Rarity) -> Self {
@@ -101,3 +175,57 @@ impl From<Rarity> for Formatting {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+mod _serde_rarity {
+    use serde::{Deserialize, Serialize};
+
+    use crate::Rarity;
+
+    impl Serialize for Rarity {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(self.name())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Rarity {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct Visitor;
+
+            impl serde::de::Visitor<'_> for Visitor {
+                type Value = Rarity;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    formatter.write_str("a rarity")
+                }
+
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    Rarity::ALL
+                        .into_iter()
+                        .find(|rarity| rarity.name() == v)
+                        .ok_or_else(|| {
+                            serde::de::Error::unknown_variant(
+                                v,
+                                &["common", "uncommon", "rare", "epic"],
+                            )
+                        })
+                }
+            }
+
+            deserializer.deserialize_str(Visitor)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test"))]
+mod tests;
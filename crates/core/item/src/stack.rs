@@ -1,12 +1,17 @@
 //! Item stack related types and traits.
 
-use component::map::ComponentMap;
+use component::{
+    changes::ComponentChanges, map::ComponentMap, ComponentType, ErasedComponentType,
+    ProvideComponentTypes, RawErasedComponentType,
+};
 use rimecraft_global_cx::ProvideIdTy;
 use rimecraft_registry::{ProvideRegistry, Reg};
 
-use std::{fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, hash::Hash, marker::PhantomData};
 
-use crate::{Item, ItemSettings, ProvideSettingsTy, RawItem};
+use crate::{
+    Item, ItemSettings, MaxStackSize, ProvideItemComponentTypes, ProvideSettingsTy, RawItem,
+};
 
 /// Global context used for item stacks.
 pub trait ItemStackCx: ProvideIdTy + ProvideSettingsTy {}
@@ -16,6 +21,12 @@ impl<T> ItemStackCx for T where T: ProvideIdTy + ProvideSettingsTy {}
 /// A stack of items.
 ///
 /// This is a data container that holds the item count and the stack's NBT.
+///
+/// # Serialization
+///
+/// This type is serialized as a map of `id`, `count` and `components`. When
+/// deserializing, a missing `count` defaults to `1` and missing `components`
+/// to no changes, as in vanilla.
 pub struct ItemStack<'r, Cx>
 where
     Cx: ItemStackCx,
@@ -63,11 +74,158 @@ where
     pub fn empty() -> Self {
         Self::new(Item::default(), 0)
     }
+}
 
-    /// Returns whether the stack is empty.
+impl<Cx> ItemStack<'_, Cx>
+where
+    Cx: ItemStackCx,
+{
+    /// Returns whether the stack is empty, which holds no items or the
+    /// default item of its registry.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.count == 0 || self.item == Item::default()
+        self.count == 0 || Reg::registry(self.item).default_entry() == Some(self.item)
+    }
+
+    /// Returns an empty stack of the default item in the registry of this
+    /// stack's item.
+    fn to_empty(&self) -> Self {
+        let item = Reg::registry(self.item)
+            .default_entry()
+            .unwrap_or(self.item);
+        Self::new(item, 0)
+    }
+
+    /// Returns a copy of this stack with the given count, or an empty stack
+    /// if this stack is empty.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.copyWithCount` (yarn).
+    pub fn copy_with_count(&self, count: u32) -> Self {
+        if self.is_empty() {
+            self.to_empty()
+        } else {
+            Self::with_component(self.item, count, self.components.clone())
+        }
+    }
+
+    /// Splits at most `amount` items off this stack into a new stack, and
+    /// decrements this stack by the split count.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.split` (yarn).
+    pub fn split(&mut self, amount: u32) -> Self {
+        let count = amount.min(self.count);
+        let stack = self.copy_with_count(count);
+        self.decrement(count);
+        stack
+    }
+
+    /// Decrements the count of this stack, turning it into an empty stack
+    /// when the count reaches zero.
+    pub fn decrement(&mut self, amount: u32) {
+        self.count = self.count.saturating_sub(amount);
+        if self.count == 0 {
+            *self = self.to_empty();
+        }
+    }
+}
+
+impl<'r, Cx> ItemStack<'r, Cx>
+where
+    Cx: ItemStackCx + ProvideItemComponentTypes<'r>,
+{
+    /// Returns the max count of this stack, read from its max stack size
+    /// component.
+    ///
+    /// Stacks without the component could not be stacked, whose max count
+    /// is `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the max stack size component type is not of [`MaxStackSize`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.getMaxCount` (yarn).
+    pub fn max_count(&self) -> u32 {
+        // SAFETY: `MaxStackSize` does not contain any lifetime parameter.
+        unsafe {
+            let ty = Reg::into_inner(Cx::max_stack_size())
+                .downcast::<MaxStackSize>()
+                .expect("max stack size component type should be of `MaxStackSize`");
+            self.components.get(&ty)
+        }
+        .map_or(1, |&MaxStackSize(max)| max)
+    }
+
+    /// Returns whether this stack could hold more than one item.
+    #[inline]
+    pub fn is_stackable(&self) -> bool {
+        self.max_count() > 1
+    }
+}
+
+impl<'r, Cx> ItemStack<'r, Cx>
+where
+    Cx: ItemStackCx + ProvideComponentTypes<'r>,
+{
+    /// Sets the component of given type, and returns the previous one.
+    ///
+    /// The change is recorded into the patch of the components, and setting
+    /// a component equal to the one of the item reverts the change.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given component type is not registered.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.set` (yarn).
+    #[inline]
+    pub fn set<T>(&mut self, ty: &ComponentType<'r, T>, value: T) -> Option<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let ty = self.registration(ty);
+        // SAFETY: `T` does not contain any non-static lifetime parameter.
+        unsafe { self.components.insert(ty, value) }.map(|old| old.into_owned().0)
+    }
+
+    /// Updates the component of given type with the given function, which
+    /// receives the current component or `default` if absent, and returns
+    /// the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given component type is not registered.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.apply` (yarn).
+    pub fn update<T, F>(&mut self, ty: &ComponentType<'r, T>, default: T, f: F) -> Option<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce(&mut T),
+    {
+        let mut value = self.get(ty).cloned().unwrap_or(default);
+        f(&mut value);
+        self.set(ty, value)
+    }
+
+    /// Returns the registration of given component type, which is looked up
+    /// through the global context if absent from this stack.
+    fn registration<T>(&self, ty: &ComponentType<'r, T>) -> ErasedComponentType<'r, Cx>
+    where
+        T: 'static,
+    {
+        // SAFETY: `T` does not contain any non-static lifetime parameter.
+        unsafe { self.components.get_key_value(ty) }
+            .map(|(registration, _)| registration)
+            .or_else(|| Cx::component_type(&RawErasedComponentType::from(ty)))
+            .expect("component type is not registered")
     }
 }
 
@@ -99,11 +257,88 @@ where
         &mut self.components
     }
 
+    /// Applies the given component changes to the stack.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.applyChanges` (yarn).
+    #[inline]
+    pub fn apply_changes(&mut self, changes: &ComponentChanges<'r, '_, Cx>) {
+        self.components.apply_changes(changes)
+    }
+
+    /// Returns the component of given type.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.get` (yarn).
+    #[inline]
+    pub fn get<T>(&self, ty: &ComponentType<'r, T>) -> Option<&T>
+    where
+        T: 'static,
+    {
+        // SAFETY: `T` does not contain any non-static lifetime parameter.
+        unsafe { self.components.get(ty) }
+    }
+
+    /// Returns a copy of the component of given type, or the default value
+    /// if the component is absent.
+    #[inline]
+    pub fn get_or_default<T>(&self, ty: &ComponentType<'r, T>) -> T
+    where
+        T: Clone + Default + 'static,
+    {
+        self.get(ty).cloned().unwrap_or_default()
+    }
+
+    /// Removes the component of given type, and returns it.
+    ///
+    /// Removing a component of the item is recorded into the patch of the
+    /// components.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.remove` (yarn).
+    #[inline]
+    pub fn remove<T>(&mut self, ty: &ComponentType<'r, T>) -> Option<T>
+    where
+        T: Clone + 'static,
+    {
+        // SAFETY: `T` does not contain any non-static lifetime parameter.
+        unsafe { self.components.remove(ty) }.map(|old| old.into_owned().0)
+    }
+
     /// Sets the count of the stack.
     #[inline]
     pub fn set_count(&mut self, count: u32) {
         self.count = count;
     }
+
+    /// Increments the count of the stack.
+    #[inline]
+    pub fn increment(&mut self, amount: u32) {
+        self.count = self.count.saturating_add(amount);
+    }
+
+    /// Returns whether the two stacks could be combined into one stack,
+    /// which means they have the same item and equal components.
+    ///
+    /// The counts of the stacks are not taken into account.
+    #[inline]
+    pub fn can_combine(&self, other: &Self) -> bool {
+        self.item == other.item && self.components == other.components
+    }
+
+    /// Returns whether the two stacks have the same item and equal components,
+    /// ignoring transient components.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.areItemsAndComponentsEqual` (yarn).
+    #[inline]
+    pub fn are_items_and_components_equal(&self, other: &Self) -> bool {
+        self.item == other.item && self.components.eq_ignoring_transient(&other.components)
+    }
 }
 
 impl<'r, Cx> Default for ItemStack<'r, Cx>
@@ -148,6 +383,18 @@ where
 
 impl<Cx> Eq for ItemStack<'_, Cx> where Cx: ItemStackCx {}
 
+impl<Cx> Hash for ItemStack<'_, Cx>
+where
+    Cx: ItemStackCx,
+{
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.item.hash(state);
+        self.count.hash(state);
+        self.components.hash(state);
+    }
+}
+
 impl<Cx> Clone for ItemStack<'_, Cx>
 where
     Cx: ItemStackCx,
@@ -174,7 +421,7 @@ where
 #[cfg(feature = "serde")]
 #[allow(clippy::missing_errors_doc)]
 mod _serde {
-    use std::{hash::Hash, str::FromStr};
+    use std::str::FromStr;
 
     use component::{changes::ComponentChanges, RawErasedComponentType};
     use rimecraft_registry::entry::RefEntry;
@@ -242,7 +489,8 @@ mod _serde {
                     A: serde::de::MapAccess<'de>,
                 {
                     let mut id = None;
-                    let mut count = 0u32;
+                    // Stacks without count contain exactly one item, as in vanilla.
+                    let mut count = 1u32;
                     let mut components: Option<ComponentChanges<'r, 'r, Cx>> = None;
 
                     enum Field {
@@ -308,14 +556,15 @@ mod _serde {
                         }
                     }
 
-                    let item = id.ok_or_else(|| serde::de::Error::missing_field("id"))?;
+                    let item: Item<'r, Cx> =
+                        id.ok_or_else(|| serde::de::Error::missing_field("id"))?;
+                    let base = Reg::into_inner(item).settings().components();
                     Ok(ItemStack {
                         item,
                         count,
-                        components: ComponentMap::with_changes(
-                            Reg::into_inner(item).settings().components(),
-                            components
-                                .ok_or_else(|| serde::de::Error::missing_field("components"))?,
+                        components: components.map_or_else(
+                            || ComponentMap::new(base),
+                            |changes| ComponentMap::with_changes(base, changes),
                         ),
                     })
                 }
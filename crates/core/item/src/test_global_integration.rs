@@ -0,0 +1,170 @@
+//! Integration with `rimecraft-test-global`.
+//!
+//! Tests of item-related crates share the items registered here, along with
+//! the [`vanilla`] component types.
+
+#![cfg(feature = "test")]
+
+use component::{map::ComponentMap, ErasedComponentType};
+use rimecraft_registry::{RegistryKey, RegistryMut};
+use test_global::{integration::registry::RegistryType, Id, TestContext};
+
+use crate::{
+    damage::ProvideDamageComponentTypes,
+    tooltip::{ProvideTooltipComponentTypes, TooltipAppenderType},
+    vanilla::{self, Lore, Unbreakable},
+    Item, ItemSettings, ProvideItemComponentTypes, ProvideSettingsTy, RawItem,
+};
+
+/// Settings of items in tests, which are their default components.
+#[derive(Debug)]
+pub struct Settings<'a>(pub ComponentMap<'a, TestContext>);
+
+impl<'a> ItemSettings<'a, TestContext> for Settings<'a> {
+    #[inline]
+    fn components(&self) -> &ComponentMap<'a, TestContext> {
+        &self.0
+    }
+}
+
+impl ProvideSettingsTy for TestContext {
+    type Settings<'a> = Settings<'a>;
+}
+
+#[doc(hidden)]
+#[allow(missing_debug_implementations)]
+pub struct TypeProvider;
+
+impl test_global::integration::registry::GlobalRegistryTypeProvider for TypeProvider {
+    type Type<'a> = RawItem<'a, TestContext>;
+}
+
+test_global::global_registry! {
+    type: super::TypeProvider,
+    path: "item",
+    mod: _registry
+}
+
+impl RegistryType for RawItem<'static, TestContext> {
+    fn registry() -> &'static rimecraft_registry::Registry<Id, Self> {
+        registry()
+    }
+}
+
+pub use _registry::*;
+
+/// Creates an identifier of given path in the `minecraft` namespace.
+///
+/// # Panics
+///
+/// Panics if the path is not a valid identifier path.
+pub fn id(path: &str) -> Id {
+    format!("minecraft:{path}")
+        .parse()
+        .expect("invalid identifier path")
+}
+
+/// Creates the registry key of an item of given path.
+#[inline]
+pub fn key(path: &str) -> RegistryKey<Id, RawItem<'static, TestContext>> {
+    RegistryKey::new(REGISTRY_ID, id(path))
+}
+
+/// Gets the registered item of given path.
+///
+/// # Panics
+///
+/// Panics if the item is not registered.
+pub fn item(path: &str) -> Item<'static, TestContext> {
+    registry().get(&id(path)).expect("item not registered")
+}
+
+/// Gets the registered component type of given path.
+///
+/// # Panics
+///
+/// Panics if the component type is not registered.
+pub fn component_type(path: &str) -> ErasedComponentType<'static, TestContext> {
+    component::test_global_integration::registry()
+        .get(&id(path))
+        .expect("component type not registered")
+}
+
+/// Initializes the component type registry with [`vanilla`] component types,
+/// and the item registry with items registered by given function.
+///
+/// Other component types should be registered through
+/// [`component::test_global_integration::peek_registry_mut`] before this.
+///
+/// # Panics
+///
+/// Panics if the registries are already initialized.
+pub fn init_registries<F>(f: F)
+where
+    F: FnOnce(&mut RegistryMut<Id, RawItem<'static, TestContext>>),
+{
+    component::test_global_integration::peek_registry_mut(|registry| {
+        vanilla::register(registry, id).expect("vanilla component types are already registered")
+    });
+    component::test_global_integration::init_registry();
+    peek_registry_mut(f);
+    init_registry();
+}
+
+impl ProvideItemComponentTypes<'static> for TestContext {
+    #[inline]
+    fn max_stack_size() -> ErasedComponentType<'static, Self> {
+        component_type("max_stack_size")
+    }
+}
+
+impl ProvideDamageComponentTypes<'static> for TestContext {
+    #[inline]
+    fn damage() -> ErasedComponentType<'static, Self> {
+        component_type("damage")
+    }
+
+    #[inline]
+    fn max_damage() -> ErasedComponentType<'static, Self> {
+        component_type("max_damage")
+    }
+
+    #[inline]
+    fn unbreakable() -> ErasedComponentType<'static, Self> {
+        component_type("unbreakable")
+    }
+}
+
+impl ProvideTooltipComponentTypes<'static> for TestContext {
+    #[inline]
+    fn custom_name() -> ErasedComponentType<'static, Self> {
+        component_type("custom_name")
+    }
+
+    #[inline]
+    fn item_name() -> ErasedComponentType<'static, Self> {
+        component_type("item_name")
+    }
+
+    #[inline]
+    fn rarity() -> ErasedComponentType<'static, Self> {
+        component_type("rarity")
+    }
+
+    fn lore() -> TooltipAppenderType<'static, Self> {
+        TooltipAppenderType::new::<Lore<Self>>(component_type("lore"))
+            .expect("lore component type mismatch")
+    }
+
+    fn tooltip_appenders() -> &'static [TooltipAppenderType<'static, Self>] {
+        // Registries are per test, and so are the appenders.
+        thread_local! {
+            static APPENDERS: &'static [TooltipAppenderType<'static, TestContext>] =
+                Box::leak(Box::new([TooltipAppenderType::new::<Unbreakable>(
+                    component_type("unbreakable"),
+                )
+                .expect("unbreakable component type mismatch")]));
+        }
+        APPENDERS.with(|appenders| *appenders)
+    }
+}
@@ -0,0 +1,469 @@
+use component::{map::ComponentMap, RawErasedComponentType};
+use edcode2::{Decode, Encode};
+use rimecraft_fmt::Formatting;
+use rimecraft_text::{style::Color, Text};
+use test_global::{integration::text::Content, TestContext};
+
+use crate::{
+    damage,
+    test_global_integration::{component_type, id, init_registries, item, key, Settings},
+    tooltip::{CustomName, ItemName, TooltipAppenderType, TooltipType},
+    vanilla::{
+        self, EnchantmentGlintOverride, Lore, TextComponentTypes, Unbreakable, MAX_LORE_LINES,
+        MAX_STACK_SIZE, REPAIR_COST,
+    },
+    Damage, ItemStack, MaxDamage, MaxStackSize, Rarity, RawItem, RepairCost,
+};
+
+fn init() {
+    init_registries(|items| {
+        let settings = |max: u32| {
+            let mut builder = ComponentMap::builder();
+            builder.insert(component_type("max_stack_size"), MaxStackSize(max));
+            Settings(builder.build())
+        };
+        items
+            .register_default(key("air"), RawItem::new(settings(64)))
+            .unwrap();
+        items
+            .register(key("stick"), RawItem::new(settings(64)))
+            .unwrap();
+        items
+            .register(key("egg"), RawItem::new(settings(16)))
+            .unwrap();
+        let mut builder = ComponentMap::builder();
+        builder.insert(component_type("damage"), Damage(0));
+        builder.insert(component_type("max_damage"), MaxDamage(250));
+        builder.insert(
+            component_type("item_name"),
+            ItemName::<TestContext>(text("Iron Sword")),
+        );
+        items
+            .register(key("iron_sword"), RawItem::new(Settings(builder.build())))
+            .unwrap();
+        for (path, unbreakable) in [("pickaxe", false), ("bedrock_pickaxe", true)] {
+            let mut builder = ComponentMap::builder();
+            builder.insert(component_type("damage"), Damage(0));
+            builder.insert(component_type("max_damage"), MaxDamage(4));
+            if unbreakable {
+                builder.insert(component_type("unbreakable"), Unbreakable::default());
+            }
+            items
+                .register(key(path), RawItem::new(Settings(builder.build())))
+                .unwrap();
+        }
+    });
+}
+
+fn text(value: &str) -> Text<TestContext> {
+    Content::from(value).into()
+}
+
+fn stack(path: &str, count: u32) -> ItemStack<'static, TestContext> {
+    ItemStack::new(item(path), count)
+}
+
+#[test]
+fn stack_rules() {
+    init();
+    assert_eq!(stack("stick", 1).max_count(), 64);
+    assert_eq!(stack("egg", 1).max_count(), 16);
+    assert_eq!(stack("iron_sword", 1).max_count(), 1);
+    assert!(stack("stick", 1).is_stackable());
+    assert!(!stack("iron_sword", 1).is_stackable());
+
+    let mut sticks = stack("stick", 10);
+    let split = sticks.split(4);
+    assert_eq!(split.count(), 4);
+    assert_eq!(sticks.count(), 6);
+    assert!(split.can_combine(&sticks));
+    assert!(split.are_items_and_components_equal(&sticks));
+    assert!(!split.can_combine(&stack("egg", 4)));
+    assert!(!split.are_items_and_components_equal(&stack("egg", 4)));
+
+    let all = sticks.split(10);
+    assert_eq!(all.count(), 6);
+    assert!(sticks.is_empty());
+    assert_eq!(sticks.item(), item("air"));
+    assert!(sticks.split(1).is_empty());
+
+    let mut copy = all.copy_with_count(2);
+    assert_eq!(copy.count(), 2);
+    assert_eq!(all.count(), 6);
+    copy.increment(3);
+    assert_eq!(copy.count(), 5);
+    copy.decrement(2);
+    assert_eq!(copy.count(), 3);
+    copy.decrement(5);
+    assert!(copy.is_empty());
+    assert_eq!(copy.item(), item("air"));
+    assert!(stack("air", 3).is_empty());
+    assert!(ItemStack::<'static, TestContext>::empty()
+        .copy_with_count(3)
+        .is_empty());
+
+    // Stacks with different components could not combine.
+    let mut named = stack("stick", 1);
+    named.set(
+        &TextComponentTypes::<TestContext>::CUSTOM_NAME,
+        CustomName(text("Wand")),
+    );
+    assert!(!named.can_combine(&stack("stick", 1)));
+    assert!(!named.are_items_and_components_equal(&stack("stick", 1)));
+    assert!(named.can_combine(&named.copy_with_count(7)));
+}
+
+#[test]
+fn stack_components() {
+    init();
+    let mut sticks = stack("stick", 1);
+    assert_eq!(sticks.get(&MAX_STACK_SIZE), Some(&MaxStackSize(64)));
+
+    assert_eq!(
+        sticks.set(&MAX_STACK_SIZE, MaxStackSize(64)),
+        Some(MaxStackSize(64))
+    );
+    assert!(sticks.components().changes().unwrap().is_empty());
+
+    assert_eq!(
+        sticks.set(&MAX_STACK_SIZE, MaxStackSize(32)),
+        Some(MaxStackSize(64))
+    );
+    assert_eq!(sticks.max_count(), 32);
+    assert_eq!(
+        sticks.update(&MAX_STACK_SIZE, MaxStackSize(1), |max| max.0 += 1),
+        Some(MaxStackSize(32))
+    );
+    assert_eq!(sticks.get(&MAX_STACK_SIZE), Some(&MaxStackSize(33)));
+    assert_eq!(sticks.components().changes().unwrap().len(), 1);
+
+    assert_eq!(sticks.remove(&MAX_STACK_SIZE), Some(MaxStackSize(33)));
+    assert_eq!(sticks.get(&MAX_STACK_SIZE), None);
+    assert_eq!(sticks.components().changes().unwrap().len(), 1);
+    assert!(sticks.components().is_empty());
+    assert!(!sticks.can_combine(&stack("stick", 1)));
+
+    assert_eq!(
+        sticks.update(&MAX_STACK_SIZE, MaxStackSize(64), |_| {}),
+        None
+    );
+    assert!(sticks.components().changes().unwrap().is_empty());
+    assert!(sticks.can_combine(&stack("stick", 1)));
+
+    // Components absent from the item are looked up in the registry.
+    assert_eq!(sticks.set(&REPAIR_COST, RepairCost(3)), None);
+    assert_eq!(sticks.get_or_default(&REPAIR_COST), RepairCost(3));
+    assert_eq!(sticks.components().changes().unwrap().len(), 1);
+
+    // Custom names do not override item names.
+    let mut sword = stack("iron_sword", 1);
+    assert_eq!(
+        sword.set(
+            &TextComponentTypes::<TestContext>::CUSTOM_NAME,
+            CustomName(text("Excalibur"))
+        ),
+        None
+    );
+    assert_eq!(
+        sword.get(&TextComponentTypes::<TestContext>::CUSTOM_NAME),
+        Some(&CustomName(text("Excalibur")))
+    );
+    assert_eq!(
+        sword.get(&TextComponentTypes::<TestContext>::ITEM_NAME),
+        Some(&ItemName(text("Iron Sword")))
+    );
+    assert_eq!(sword.components().changes().unwrap().len(), 1);
+}
+
+#[test]
+fn stack_damage() {
+    init();
+    let mut pickaxe = stack("pickaxe", 1);
+    assert!(pickaxe.is_damageable());
+    assert!(!pickaxe.is_damaged());
+    assert!(!stack("bedrock_pickaxe", 1).is_damageable());
+    assert!(!stack("stick", 1).is_damageable());
+    assert_eq!(pickaxe.item_bar_step(), damage::ITEM_BAR_STEPS);
+    assert_eq!(pickaxe.item_bar_color(), 0x00ff00);
+
+    let mut broken = None;
+    assert!(!pickaxe.damage(
+        1,
+        &mut (),
+        |_, amount, _| amount,
+        |item| broken = Some(item)
+    ));
+    assert_eq!(pickaxe.current_damage(), 1);
+    assert!(pickaxe.is_item_bar_visible());
+    assert_eq!(pickaxe.item_bar_fraction(), 0.75);
+    assert_eq!(pickaxe.item_bar_step(), 10);
+
+    // Rolls always prevent the damage.
+    let mut rolls = 0;
+    let prevented = |_: &ItemStack<'static, TestContext>, amount, rolls: &mut u32| {
+        damage::unbreaking(amount, 3, |bound| {
+            *rolls += 1;
+            bound - 1
+        })
+    };
+    assert!(!pickaxe.damage(2, &mut rolls, prevented, |_| unreachable!()));
+    assert_eq!(rolls, 2);
+    assert_eq!(pickaxe.current_damage(), 1);
+
+    assert!(pickaxe.damage(
+        5,
+        &mut (),
+        |_, amount, _| amount,
+        |item| broken = Some(item)
+    ));
+    assert_eq!(broken, Some(item("pickaxe")));
+    assert!(pickaxe.is_empty());
+
+    let mut bedrock = stack("bedrock_pickaxe", 1);
+    assert!(!bedrock.damage(5, &mut (), |_, amount, _| amount, |_| unreachable!()));
+    assert!(!bedrock.is_item_bar_visible());
+}
+
+#[test]
+fn stable_hash() {
+    init();
+    let sticks = stack("stick", 3);
+    let hash = sticks.stable_hash().expect("hash failed");
+    // Pinned, as the hash must not change across versions.
+    assert_eq!(hash, 1022782564);
+    assert_eq!(
+        stack("stick", 3).stable_hash().expect("hash failed"),
+        hash,
+        "hash should be deterministic"
+    );
+    assert_eq!(
+        ItemStack::<'static, TestContext>::empty()
+            .stable_hash()
+            .expect("hash failed"),
+        0
+    );
+    assert_ne!(stack("stick", 4).stable_hash().expect("hash failed"), hash);
+    assert_ne!(stack("egg", 3).stable_hash().expect("hash failed"), hash);
+
+    let mut named = sticks.copy_with_count(3);
+    named.set(
+        &TextComponentTypes::<TestContext>::CUSTOM_NAME,
+        CustomName(text("Wand")),
+    );
+    assert_ne!(named.stable_hash().expect("hash failed"), hash);
+    named.remove(&TextComponentTypes::<TestContext>::CUSTOM_NAME);
+    assert_eq!(named.stable_hash().expect("hash failed"), hash);
+}
+
+#[test]
+fn stack_serde() {
+    init();
+    let de = |json: &str| serde_json::from_str::<ItemStack<'static, TestContext>>(json);
+
+    let egg = de(r#"{ "id": "minecraft:egg" }"#).unwrap();
+    assert_eq!(egg.item(), item("egg"));
+    assert_eq!(egg.count(), 1, "missing count should default to one");
+    assert_eq!(egg.max_count(), 16);
+
+    let eggs = de(
+        r#"{ "id": "minecraft:egg", "count": 3, "components": { "minecraft:max_stack_size": 8 } }"#,
+    )
+    .unwrap();
+    assert_eq!(eggs.count(), 3);
+    assert_eq!(eggs.max_count(), 8);
+
+    let json = serde_json::to_string(&eggs).unwrap();
+    let back = de(&json).unwrap();
+    assert_eq!(back, eggs);
+
+    assert!(de(r#"{ "count": 3 }"#).is_err());
+}
+
+#[test]
+fn rarity_serde() {
+    for rarity in Rarity::ALL {
+        let json = serde_json::to_value(rarity).unwrap();
+        assert_eq!(json, rarity.name());
+        assert_eq!(serde_json::from_value::<Rarity>(json).unwrap(), rarity);
+    }
+    assert!(serde_json::from_str::<Rarity>("\"legendary\"").is_err());
+
+    let mut buf = Vec::new();
+    Rarity::Epic.encode(&mut buf).unwrap();
+    assert_eq!(Rarity::decode(buf.as_slice()).unwrap(), Rarity::Epic);
+}
+
+#[test]
+fn unbreakable() {
+    let de: Unbreakable = serde_json::from_str("{}").unwrap();
+    assert_eq!(de, Unbreakable::default());
+    assert!(de.show_in_tooltip);
+
+    let mut buf = Vec::new();
+    Unbreakable {
+        show_in_tooltip: false,
+    }
+    .encode(&mut buf)
+    .unwrap();
+    assert!(!Unbreakable::decode(buf.as_slice()).unwrap().show_in_tooltip);
+}
+
+#[test]
+fn lore() {
+    assert!(Lore::<TestContext>::new(vec![text("a"); MAX_LORE_LINES + 1]).is_none());
+    assert!(
+        serde_json::from_value::<Lore<TestContext>>(serde_json::Value::Array(vec![
+        serde_json::Value::from("a");
+        MAX_LORE_LINES + 1
+    ]))
+        .is_err()
+    );
+
+    let lore = Lore::<TestContext>::new(vec![text("first"), text("second")]).unwrap();
+    let mut buf = Vec::new();
+    lore.encode(&mut buf).unwrap();
+    let decoded = Lore::<TestContext>::decode(buf.as_slice()).unwrap();
+    assert_eq!(decoded, lore);
+}
+
+#[test]
+fn register() {
+    component::test_global_integration::peek_registry_mut(|registry| {
+        vanilla::register(registry, id).unwrap();
+        let err = vanilla::register(registry, id).unwrap_err();
+        assert_eq!(*err.value(), id("custom_name"));
+    });
+    component::test_global_integration::init_registry();
+
+    assert_eq!(
+        *component_type("custom_name"),
+        RawErasedComponentType::from(&TextComponentTypes::<TestContext>::CUSTOM_NAME)
+    );
+
+    // Components sharing a value type are distinct types.
+    let mut builder = ComponentMap::builder();
+    builder.insert(
+        component_type("custom_name"),
+        CustomName::<TestContext>(text("Excalibur")),
+    );
+    builder.insert(
+        component_type("item_name"),
+        ItemName::<TestContext>(text("Iron Sword")),
+    );
+    builder.insert(
+        component_type("enchantment_glint_override"),
+        EnchantmentGlintOverride(true),
+    );
+    builder.insert(
+        component_type("unbreakable"),
+        Unbreakable {
+            show_in_tooltip: false,
+        },
+    );
+    let map = builder.build();
+    assert_eq!(map.len(), 4);
+    assert_eq!(
+        unsafe { map.get(&TextComponentTypes::<TestContext>::CUSTOM_NAME) },
+        Some(&CustomName(text("Excalibur")))
+    );
+    assert_eq!(
+        unsafe { map.get(&TextComponentTypes::<TestContext>::ITEM_NAME) },
+        Some(&ItemName(text("Iron Sword")))
+    );
+
+    // Integer components are distinct types.
+    let mut builder = ComponentMap::builder();
+    builder.insert(component_type("damage"), Damage(1));
+    builder.insert(component_type("max_damage"), MaxDamage(2));
+    builder.insert(component_type("max_stack_size"), MaxStackSize(3));
+    builder.insert(component_type("repair_cost"), RepairCost(4));
+    let map = builder.build();
+    assert_eq!(map.len(), 4);
+    assert_eq!(
+        unsafe { map.get(&vanilla::MAX_DAMAGE) },
+        Some(&MaxDamage(2))
+    );
+}
+
+#[test]
+fn tooltip() {
+    init();
+    assert!(TooltipAppenderType::new::<Lore<TestContext>>(component_type("unbreakable")).is_none());
+    let color = |formatting: Formatting| Color::try_from(formatting).ok();
+
+    let stick = ItemStack::new(item("stick"), 1);
+    assert_eq!(stick.rarity(), Rarity::Common);
+    assert_eq!(stick.name().to_string(), "minecraft:stick");
+    let lines = stick.tooltip(TooltipType::BASIC);
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].to_string(), "minecraft:stick");
+    assert_eq!(lines[0].style().color, color(Formatting::White));
+    assert_eq!(lines[0].style().italic, None);
+
+    // Item names are not italic, unlike custom names.
+    let mut sword = ItemStack::new(item("iron_sword"), 1);
+    assert_eq!(sword.name().to_string(), "Iron Sword");
+    let name = sword.formatted_name();
+    assert_eq!(name.to_string(), "Iron Sword");
+    assert_eq!(name.style().italic, None);
+
+    let mut named = ItemStack::new(item("stick"), 1);
+    named.set(
+        &TextComponentTypes::<TestContext>::CUSTOM_NAME,
+        CustomName(text("Wand")),
+    );
+    let name = named.formatted_name();
+    assert_eq!(name.to_string(), "Wand");
+    assert_eq!(name.style().italic, Some(true));
+
+    sword.set(&vanilla::RARITY, Rarity::Rare);
+    sword.set(
+        &TextComponentTypes::<TestContext>::CUSTOM_NAME,
+        CustomName(text("Excalibur")),
+    );
+    sword.set(
+        &TextComponentTypes::<TestContext>::LORE,
+        Lore::<TestContext>::new(vec![text("first"), text("second")]).unwrap(),
+    );
+    sword.set(&vanilla::UNBREAKABLE, Unbreakable::default());
+
+    let lines = sword.tooltip(TooltipType::BASIC);
+    let strings: Vec<_> = lines.iter().map(ToString::to_string).collect();
+    assert_eq!(strings, ["Excalibur", "first", "second", "Unbreakable"]);
+    assert_eq!(lines[0].style().color, color(Formatting::Aqua));
+    assert_eq!(lines[0].style().italic, Some(true));
+    assert_eq!(lines[1].style().color, color(Formatting::DarkPurple));
+    assert_eq!(lines[3].style().color, color(Formatting::Blue));
+
+    sword.set(
+        &vanilla::UNBREAKABLE,
+        Unbreakable {
+            show_in_tooltip: false,
+        },
+    );
+    assert_eq!(sword.tooltip(TooltipType::BASIC).len(), 3);
+
+    // Advanced tooltips show durability of damaged stacks only.
+    let mut sword = ItemStack::new(item("iron_sword"), 1);
+    let strings = |stack: &ItemStack<'static, TestContext>| -> Vec<String> {
+        stack
+            .tooltip(TooltipType::ADVANCED)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    };
+    assert_eq!(
+        strings(&sword),
+        ["Iron Sword", "minecraft:iron_sword", "3 component(s)"]
+    );
+    sword.set_damage(50);
+    assert_eq!(
+        strings(&sword),
+        [
+            "Iron Sword",
+            "Durability: 200 / 250",
+            "minecraft:iron_sword",
+            "3 component(s)"
+        ]
+    );
+}
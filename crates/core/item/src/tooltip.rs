@@ -0,0 +1,431 @@
+//! Tooltips of item stacks.
+//!
+//! This module requires the `tooltip` feature.
+//!
+//! Texts built by this module are literal, as localization is not a concern
+//! of item stacks.
+
+use std::fmt::{Debug, Display};
+
+use component::ErasedComponentType;
+use rimecraft_fmt::Formatting;
+use rimecraft_registry::Reg;
+use rimecraft_text::{style::Color, Plain, ProvideTextTy, Style, Text};
+
+use crate::{damage::ProvideDamageComponentTypes, stack::ItemStackCx, ItemStack, Rarity};
+
+/// Type of a tooltip, deciding which lines show.
+///
+/// # MCJE Reference
+///
+/// This represents `TooltipType` (yarn).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TooltipType {
+    advanced: bool,
+    creative: bool,
+}
+
+impl TooltipType {
+    /// The basic tooltip type.
+    pub const BASIC: Self = Self {
+        advanced: false,
+        creative: false,
+    };
+
+    /// The advanced tooltip type, showing identifiers, durability and
+    /// component counts of item stacks.
+    pub const ADVANCED: Self = Self {
+        advanced: true,
+        creative: false,
+    };
+
+    /// Returns this tooltip type with the creative flag set.
+    #[inline]
+    pub const fn with_creative(self) -> Self {
+        Self {
+            creative: true,
+            ..self
+        }
+    }
+
+    /// Whether advanced information shows.
+    #[inline]
+    pub const fn is_advanced(self) -> bool {
+        self.advanced
+    }
+
+    /// Whether the tooltip is shown in creative mode.
+    #[inline]
+    pub const fn is_creative(self) -> bool {
+        self.creative
+    }
+}
+
+/// Component values contributing lines to tooltips of item stacks.
+///
+/// # MCJE Reference
+///
+/// This represents `TooltipAppender` (yarn).
+pub trait TooltipAppender<Cx>
+where
+    Cx: ProvideTextTy,
+{
+    /// Appends lines of this value to a tooltip.
+    fn append_tooltip<F>(&self, ty: TooltipType, append: F)
+    where
+        F: FnMut(Text<Cx>);
+}
+
+type AppendFn<'r, Cx> =
+    fn(&ItemStack<'r, Cx>, ErasedComponentType<'r, Cx>, TooltipType, &mut Vec<Text<Cx>>);
+
+/// A component type whose values are [`TooltipAppender`]s.
+pub struct TooltipAppenderType<'r, Cx>
+where
+    Cx: ItemStackCx + ProvideTextTy,
+{
+    ty: ErasedComponentType<'r, Cx>,
+    append: AppendFn<'r, Cx>,
+}
+
+impl<'r, Cx> TooltipAppenderType<'r, Cx>
+where
+    Cx: ItemStackCx + ProvideTextTy,
+{
+    /// Creates a tooltip appender type from the given component type.
+    ///
+    /// Returns `None` if values of the component type are not of type `T`.
+    pub fn new<T>(ty: ErasedComponentType<'r, Cx>) -> Option<Self>
+    where
+        T: TooltipAppender<Cx> + 'static,
+    {
+        // SAFETY: `T` does not contain any non-static lifetime parameter.
+        unsafe { Reg::into_inner(ty).downcast::<T>() }?;
+        Some(Self {
+            ty,
+            append: append_of::<Cx, T>,
+        })
+    }
+
+    /// Returns the component type.
+    #[inline]
+    pub fn component_type(&self) -> ErasedComponentType<'r, Cx> {
+        self.ty
+    }
+
+    /// Appends lines of the component of the given stack, if present.
+    #[inline]
+    pub fn append_tooltip(
+        &self,
+        stack: &ItemStack<'r, Cx>,
+        ty: TooltipType,
+        lines: &mut Vec<Text<Cx>>,
+    ) {
+        (self.append)(stack, self.ty, ty, lines)
+    }
+}
+
+fn append_of<'r, Cx, T>(
+    stack: &ItemStack<'r, Cx>,
+    component: ErasedComponentType<'r, Cx>,
+    ty: TooltipType,
+    lines: &mut Vec<Text<Cx>>,
+) where
+    Cx: ItemStackCx + ProvideTextTy,
+    T: TooltipAppender<Cx> + 'static,
+{
+    // SAFETY: `T` does not contain any non-static lifetime parameter.
+    if let Some(value) = unsafe { Reg::into_inner(component).downcast::<T>() }
+        .and_then(|component| stack.get(&component))
+    {
+        value.append_tooltip(ty, |line| lines.push(line));
+    }
+}
+
+impl<Cx> Clone for TooltipAppenderType<'_, Cx>
+where
+    Cx: ItemStackCx + ProvideTextTy,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Cx> Copy for TooltipAppenderType<'_, Cx> where Cx: ItemStackCx + ProvideTextTy {}
+
+impl<Cx> Debug for TooltipAppenderType<'_, Cx>
+where
+    Cx: ItemStackCx<Id: Debug> + ProvideTextTy + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TooltipAppenderType")
+            .field("ty", &self.ty)
+            .finish_non_exhaustive()
+    }
+}
+
+macro_rules! text_components {
+    ($($(#[$meta:meta])* $name:ident),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            pub struct $name<Cx>(pub Text<Cx>)
+            where
+                Cx: ProvideTextTy;
+
+            impl<Cx> Clone for $name<Cx>
+            where
+                Cx: ProvideTextTy,
+                Text<Cx>: Clone,
+            {
+                #[inline]
+                fn clone(&self) -> Self {
+                    Self(self.0.clone())
+                }
+            }
+
+            impl<Cx> PartialEq for $name<Cx>
+            where
+                Cx: ProvideTextTy,
+                Text<Cx>: PartialEq,
+            {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+
+            impl<Cx> Eq for $name<Cx>
+            where
+                Cx: ProvideTextTy,
+                Text<Cx>: Eq,
+            {
+            }
+
+            impl<Cx> std::hash::Hash for $name<Cx>
+            where
+                Cx: ProvideTextTy,
+                Text<Cx>: std::hash::Hash,
+            {
+                #[inline]
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    self.0.hash(state);
+                }
+            }
+
+            impl<Cx> Debug for $name<Cx>
+            where
+                Cx: ProvideTextTy,
+                Text<Cx>: Debug,
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple(stringify!($name)).field(&self.0).finish()
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<Cx> serde::Serialize for $name<Cx>
+            where
+                Cx: ProvideTextTy,
+                Text<Cx>: serde::Serialize,
+            {
+                #[inline]
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    self.0.serialize(serializer)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<'de, Cx> serde::Deserialize<'de> for $name<Cx>
+            where
+                Cx: ProvideTextTy,
+                Text<Cx>: serde::Deserialize<'de>,
+            {
+                #[inline]
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    Text::<Cx>::deserialize(deserializer).map(Self)
+                }
+            }
+        )*
+    };
+}
+
+text_components! {
+    /// The custom name of an item stack, as a data component.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents values of `DataComponentTypes.CUSTOM_NAME` (yarn).
+    CustomName,
+    /// The default name of an item stack, as a data component, which is
+    /// overridden by the [`CustomName`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents values of `DataComponentTypes.ITEM_NAME` (yarn).
+    ItemName,
+}
+
+/// Provides component types that tooltips of item stacks rely on.
+pub trait ProvideTooltipComponentTypes<'r>: ItemStackCx + ProvideTextTy {
+    /// The type of custom name of an item stack, whose value is a [`CustomName`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.CUSTOM_NAME` (yarn).
+    fn custom_name() -> ErasedComponentType<'r, Self>;
+
+    /// The type of default name of an item stack, whose value is an [`ItemName`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.ITEM_NAME` (yarn).
+    fn item_name() -> ErasedComponentType<'r, Self>;
+
+    /// The type of rarity of an item stack, whose value is a [`Rarity`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.RARITY` (yarn).
+    fn rarity() -> ErasedComponentType<'r, Self>;
+
+    /// The type of lore of an item stack, which appends lines right after
+    /// the name.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.LORE` (yarn).
+    fn lore() -> TooltipAppenderType<'r, Self>;
+
+    /// Component types appending lines after the lore, in order.
+    fn tooltip_appenders() -> &'r [TooltipAppenderType<'r, Self>];
+}
+
+#[inline]
+fn literal<Cx>(literal: &str, formatting: Option<Formatting>) -> Text<Cx>
+where
+    Cx: ProvideTextTy<StyleExt: Default>,
+{
+    Text::<Cx>::new(
+        Plain::from_literal(literal),
+        Style {
+            color: formatting.and_then(|f| Color::try_from(f).ok()),
+            ..Default::default()
+        },
+    )
+}
+
+impl<'r, Cx> ItemStack<'r, Cx>
+where
+    Cx: ProvideTooltipComponentTypes<'r, Id: Display, StyleExt: Default>,
+    Text<Cx>: Clone + 'static,
+{
+    /// Returns the rarity of this stack, or [`Rarity::Common`] if absent.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.getRarity` (yarn).
+    pub fn rarity(&self) -> Rarity {
+        // SAFETY: `Rarity` does not contain any lifetime parameter.
+        unsafe { Reg::into_inner(Cx::rarity()).downcast::<Rarity>() }
+            .and_then(|ty| self.get(&ty).copied())
+            .unwrap_or_default()
+    }
+
+    fn custom_name(&self) -> Option<&Text<Cx>> {
+        // SAFETY: `CustomName` does not contain any non-static lifetime parameter.
+        unsafe { Reg::into_inner(Cx::custom_name()).downcast::<CustomName<Cx>>() }
+            .and_then(|ty| self.get(&ty))
+            .map(|name| &name.0)
+    }
+
+    fn item_name(&self) -> Option<&Text<Cx>> {
+        // SAFETY: `ItemName` does not contain any non-static lifetime parameter.
+        unsafe { Reg::into_inner(Cx::item_name()).downcast::<ItemName<Cx>>() }
+            .and_then(|ty| self.get(&ty))
+            .map(|name| &name.0)
+    }
+
+    /// Returns the name of this stack, which is the custom name, the item
+    /// name, or the identifier of the item in order.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.getName` (yarn).
+    pub fn name(&self) -> Text<Cx> {
+        self.custom_name()
+            .or_else(|| self.item_name())
+            .cloned()
+            .unwrap_or_else(|| literal::<Cx>(&Reg::id(self.item()).to_string(), None))
+    }
+
+    /// Returns the name of this stack colored by its rarity, which is italic
+    /// if the stack has a custom name.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.getFormattedName` (yarn).
+    pub fn formatted_name(&self) -> Text<Cx> {
+        let custom = self.custom_name().is_some();
+        Text::<Cx>::with_sibs(
+            Plain::from_literal(""),
+            Style {
+                color: Color::try_from(Formatting::from(self.rarity())).ok(),
+                italic: custom.then_some(true),
+                ..Default::default()
+            },
+            vec![self.name()],
+        )
+    }
+}
+
+impl<'r, Cx> ItemStack<'r, Cx>
+where
+    Cx: ProvideTooltipComponentTypes<'r, Id: Display, StyleExt: Default>
+        + ProvideDamageComponentTypes<'r>,
+    Text<Cx>: Clone + 'static,
+{
+    /// Returns lines of the tooltip of this stack.
+    ///
+    /// The tooltip consists of the formatted name, the lore and lines of
+    /// [`ProvideTooltipComponentTypes::tooltip_appenders`]. Advanced tooltips
+    /// additionally show the durability, the identifier of the item and the
+    /// count of components.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ItemStack.getTooltip` (yarn).
+    pub fn tooltip(&self, ty: TooltipType) -> Vec<Text<Cx>> {
+        let mut lines = vec![self.formatted_name()];
+        Cx::lore().append_tooltip(self, ty, &mut lines);
+        for appender in Cx::tooltip_appenders() {
+            appender.append_tooltip(self, ty, &mut lines);
+        }
+
+        if ty.is_advanced() {
+            if self.is_damaged() {
+                let max = self.max_damage();
+                lines.push(literal::<Cx>(
+                    &format!("Durability: {} / {}", max - self.current_damage(), max),
+                    None,
+                ));
+            }
+            lines.push(literal::<Cx>(
+                &Reg::id(self.item()).to_string(),
+                Some(Formatting::DarkGray),
+            ));
+            let len = self.components().len();
+            lines.push(literal::<Cx>(
+                &format!("{len} component(s)"),
+                Some(Formatting::DarkGray),
+            ));
+        }
+        lines
+    }
+}
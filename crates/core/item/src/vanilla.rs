@@ -0,0 +1,522 @@
+//! Common vanilla data component types of items.
+//!
+//! This module requires the `vanilla` feature.
+
+use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+
+use component::{
+    packet_codec_edcode, serde_codec, ComponentType, PacketCodec, RawErasedComponentType,
+    SerdeCodec,
+};
+use edcode2::{Buf, BufExt, BufMut, BufMutExt, Decode, Encode};
+use rimecraft_fmt::Formatting;
+use rimecraft_global_cx::nbt::{ReadNbt, UpdateNbt, WriteNbt};
+use rimecraft_registry::{RegistryKey, RegistryMut};
+use rimecraft_text::{style::Color, Plain, ProvideTextTy, Style, Text};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    tooltip::{CustomName, ItemName, TooltipAppender, TooltipType},
+    Damage, MaxDamage, MaxStackSize, Rarity, RepairCost,
+};
+
+/// The max count of lines of a [`Lore`].
+pub const MAX_LORE_LINES: usize = 256;
+
+const DAMAGE_PACKET_CODEC: PacketCodec<'static, Damage> = packet_codec_edcode();
+const DAMAGE_SERDE_CODEC: SerdeCodec<'static, Damage> = serde_codec();
+
+/// The damage of an item stack.
+///
+/// # MCJE Reference
+///
+/// This represents `DataComponentTypes.DAMAGE` (yarn).
+pub const DAMAGE: ComponentType<'static, Damage> = ComponentType::builder::<()>()
+    .packet_codec(&DAMAGE_PACKET_CODEC)
+    .serde_codec(&DAMAGE_SERDE_CODEC)
+    .build();
+
+const MAX_DAMAGE_PACKET_CODEC: PacketCodec<'static, MaxDamage> = packet_codec_edcode();
+const MAX_DAMAGE_SERDE_CODEC: SerdeCodec<'static, MaxDamage> = serde_codec();
+
+/// The max damage of an item stack, at which the stack breaks.
+///
+/// # MCJE Reference
+///
+/// This represents `DataComponentTypes.MAX_DAMAGE` (yarn).
+pub const MAX_DAMAGE: ComponentType<'static, MaxDamage> = ComponentType::builder::<()>()
+    .packet_codec(&MAX_DAMAGE_PACKET_CODEC)
+    .serde_codec(&MAX_DAMAGE_SERDE_CODEC)
+    .build();
+
+const MAX_STACK_SIZE_PACKET_CODEC: PacketCodec<'static, MaxStackSize> = packet_codec_edcode();
+const MAX_STACK_SIZE_SERDE_CODEC: SerdeCodec<'static, MaxStackSize> = serde_codec();
+
+/// The max count of an item stack.
+///
+/// # MCJE Reference
+///
+/// This represents `DataComponentTypes.MAX_STACK_SIZE` (yarn).
+pub const MAX_STACK_SIZE: ComponentType<'static, MaxStackSize> = ComponentType::builder::<()>()
+    .packet_codec(&MAX_STACK_SIZE_PACKET_CODEC)
+    .serde_codec(&MAX_STACK_SIZE_SERDE_CODEC)
+    .build();
+
+const REPAIR_COST_PACKET_CODEC: PacketCodec<'static, RepairCost> = packet_codec_edcode();
+const REPAIR_COST_SERDE_CODEC: SerdeCodec<'static, RepairCost> = serde_codec();
+
+/// The experience cost of repairing an item stack in an anvil.
+///
+/// # MCJE Reference
+///
+/// This represents `DataComponentTypes.REPAIR_COST` (yarn).
+pub const REPAIR_COST: ComponentType<'static, RepairCost> = ComponentType::builder::<()>()
+    .packet_codec(&REPAIR_COST_PACKET_CODEC)
+    .serde_codec(&REPAIR_COST_SERDE_CODEC)
+    .build();
+
+/// Whether an item stack shows the enchantment glint regardless of its
+/// enchantments, as a data component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnchantmentGlintOverride(pub bool);
+
+impl<B> Encode<B> for EnchantmentGlintOverride
+where
+    B: BufMut,
+{
+    #[inline]
+    fn encode(&self, buf: B) -> Result<(), edcode2::BoxedError<'static>> {
+        self.0.encode(buf)
+    }
+}
+
+impl<'de, B> Decode<'de, B> for EnchantmentGlintOverride
+where
+    B: Buf,
+{
+    #[inline]
+    fn decode(buf: B) -> Result<Self, edcode2::BoxedError<'de>> {
+        bool::decode(buf).map(Self)
+    }
+}
+
+const ENCHANTMENT_GLINT_OVERRIDE_PACKET_CODEC: PacketCodec<'static, EnchantmentGlintOverride> =
+    packet_codec_edcode();
+const ENCHANTMENT_GLINT_OVERRIDE_SERDE_CODEC: SerdeCodec<'static, EnchantmentGlintOverride> =
+    serde_codec();
+
+/// Whether an item stack shows the enchantment glint, regardless of its enchantments.
+///
+/// # MCJE Reference
+///
+/// This represents `DataComponentTypes.ENCHANTMENT_GLINT_OVERRIDE` (yarn).
+pub const ENCHANTMENT_GLINT_OVERRIDE: ComponentType<'static, EnchantmentGlintOverride> =
+    ComponentType::builder::<()>()
+        .packet_codec(&ENCHANTMENT_GLINT_OVERRIDE_PACKET_CODEC)
+        .serde_codec(&ENCHANTMENT_GLINT_OVERRIDE_SERDE_CODEC)
+        .build();
+
+const RARITY_PACKET_CODEC: PacketCodec<'static, Rarity> = packet_codec_edcode();
+const RARITY_SERDE_CODEC: SerdeCodec<'static, Rarity> = serde_codec();
+
+/// The rarity of an item stack.
+///
+/// # MCJE Reference
+///
+/// This represents `DataComponentTypes.RARITY` (yarn).
+pub const RARITY: ComponentType<'static, Rarity> = ComponentType::builder::<()>()
+    .packet_codec(&RARITY_PACKET_CODEC)
+    .serde_codec(&RARITY_SERDE_CODEC)
+    .build();
+
+/// Marks an item stack as unbreakable.
+///
+/// # MCJE Reference
+///
+/// This represents `UnbreakableComponent` (yarn).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Unbreakable {
+    /// Whether the unbreakable state shows in the tooltip.
+    #[serde(default = "Unbreakable::default_show_in_tooltip")]
+    pub show_in_tooltip: bool,
+}
+
+impl Unbreakable {
+    #[inline]
+    const fn default_show_in_tooltip() -> bool {
+        true
+    }
+}
+
+impl Default for Unbreakable {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            show_in_tooltip: Self::default_show_in_tooltip(),
+        }
+    }
+}
+
+impl<B> Encode<B> for Unbreakable
+where
+    B: BufMut,
+{
+    #[inline]
+    fn encode(&self, buf: B) -> Result<(), edcode2::BoxedError<'static>> {
+        self.show_in_tooltip.encode(buf)
+    }
+}
+
+impl<'de, B> Decode<'de, B> for Unbreakable
+where
+    B: Buf,
+{
+    #[inline]
+    fn decode(buf: B) -> Result<Self, edcode2::BoxedError<'de>> {
+        bool::decode(buf).map(|show_in_tooltip| Self { show_in_tooltip })
+    }
+}
+
+impl<Cx> TooltipAppender<Cx> for Unbreakable
+where
+    Cx: ProvideTextTy<StyleExt: Default>,
+{
+    fn append_tooltip<F>(&self, _ty: TooltipType, mut append: F)
+    where
+        F: FnMut(Text<Cx>),
+    {
+        if self.show_in_tooltip {
+            append(Text::<Cx>::new(
+                Plain::from_literal("Unbreakable"),
+                Style {
+                    color: Color::try_from(Formatting::Blue).ok(),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+const UNBREAKABLE_PACKET_CODEC: PacketCodec<'static, Unbreakable> = packet_codec_edcode();
+const UNBREAKABLE_SERDE_CODEC: SerdeCodec<'static, Unbreakable> = serde_codec();
+
+/// Marks an item stack as unbreakable.
+///
+/// # MCJE Reference
+///
+/// This represents `DataComponentTypes.UNBREAKABLE` (yarn).
+pub const UNBREAKABLE: ComponentType<'static, Unbreakable> = ComponentType::builder::<()>()
+    .packet_codec(&UNBREAKABLE_PACKET_CODEC)
+    .serde_codec(&UNBREAKABLE_SERDE_CODEC)
+    .build();
+
+/// Lines of lore of an item stack, with at most [`MAX_LORE_LINES`] lines.
+///
+/// # MCJE Reference
+///
+/// This represents `LoreComponent` (yarn).
+pub struct Lore<Cx>
+where
+    Cx: ProvideTextTy,
+{
+    lines: Vec<Text<Cx>>,
+}
+
+impl<Cx> Lore<Cx>
+where
+    Cx: ProvideTextTy,
+{
+    /// Creates a new lore from given lines.
+    ///
+    /// Returns `None` if there are more than [`MAX_LORE_LINES`] lines.
+    #[inline]
+    pub fn new(lines: Vec<Text<Cx>>) -> Option<Self> {
+        (lines.len() <= MAX_LORE_LINES).then_some(Self { lines })
+    }
+
+    /// Returns the lines of this lore.
+    #[inline]
+    pub fn lines(&self) -> &[Text<Cx>] {
+        &self.lines
+    }
+}
+
+impl<Cx> Default for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+{
+    #[inline]
+    fn default() -> Self {
+        Self { lines: Vec::new() }
+    }
+}
+
+impl<Cx> Clone for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+    Text<Cx>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            lines: self.lines.clone(),
+        }
+    }
+}
+
+impl<Cx> PartialEq for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+    Text<Cx>: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.lines == other.lines
+    }
+}
+
+impl<Cx> Eq for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+    Text<Cx>: Eq,
+{
+}
+
+impl<Cx> Hash for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+    Text<Cx>: Hash,
+{
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.lines.hash(state);
+    }
+}
+
+impl<Cx> Debug for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+    Text<Cx>: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&self.lines).finish()
+    }
+}
+
+impl<Cx> Serialize for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+    Text<Cx>: Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.lines.serialize(serializer)
+    }
+}
+
+impl<'de, Cx> Deserialize<'de> for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+    Text<Cx>: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let lines = Vec::<Text<Cx>>::deserialize(deserializer)?;
+        let len = lines.len();
+        Self::new(lines)
+            .ok_or_else(|| serde::de::Error::invalid_length(len, &"at most 256 lines of lore"))
+    }
+}
+
+impl<Cx> TooltipAppender<Cx> for Lore<Cx>
+where
+    Cx: ProvideTextTy,
+    Text<Cx>: Clone,
+{
+    /// Appends lines of this lore, which are dark purple and italic unless
+    /// styled otherwise.
+    fn append_tooltip<F>(&self, _ty: TooltipType, append: F)
+    where
+        F: FnMut(Text<Cx>),
+    {
+        self.lines
+            .iter()
+            .map(|line| {
+                let mut line = line.clone();
+                let style = line.style_mut();
+                if style.color.is_none() {
+                    style.color = Color::try_from(Formatting::DarkPurple).ok();
+                }
+                style.italic.get_or_insert(true);
+                line
+            })
+            .for_each(append);
+    }
+}
+
+impl<Cx, B> Encode<B> for Lore<Cx>
+where
+    Cx: ProvideTextTy + for<'t> WriteNbt<&'t Text<Cx>>,
+    B: BufMut,
+{
+    fn encode(&self, mut buf: B) -> Result<(), edcode2::BoxedError<'static>> {
+        buf.put_variable(self.lines.len() as u32);
+        for line in &self.lines {
+            Cx::write_nbt(line, (&mut buf).writer())?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de, Cx, B> Decode<'de, B> for Lore<Cx>
+where
+    Cx: ProvideTextTy + ReadNbt<Text<Cx>>,
+    B: Buf,
+{
+    fn decode(mut buf: B) -> Result<Self, edcode2::BoxedError<'de>> {
+        let len: u32 = buf.get_variable();
+        if len as usize > MAX_LORE_LINES {
+            return Err(format!("too many lines of lore: {len}").into());
+        }
+        let lines = (0..len)
+            .map(|_| Cx::read_nbt((&mut buf).reader()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { lines })
+    }
+}
+
+macro_rules! text_component_edcode {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl<Cx, B> Encode<B> for $name<Cx>
+            where
+                Cx: ProvideTextTy + for<'t> WriteNbt<&'t Text<Cx>>,
+                B: BufMut,
+            {
+                #[inline]
+                fn encode(&self, buf: B) -> Result<(), edcode2::BoxedError<'static>> {
+                    Cx::write_nbt(&self.0, buf.writer()).map_err(Into::into)
+                }
+            }
+
+            impl<'de, Cx, B> Decode<'de, B> for $name<Cx>
+            where
+                Cx: ProvideTextTy + ReadNbt<Text<Cx>>,
+                B: Buf,
+            {
+                #[inline]
+                fn decode(buf: B) -> Result<Self, edcode2::BoxedError<'de>> {
+                    Cx::read_nbt(buf.reader()).map(Self).map_err(Into::into)
+                }
+            }
+        )*
+    };
+}
+
+text_component_edcode!(CustomName, ItemName);
+
+/// Vanilla component types whose values are texts, which depend on the
+/// global context.
+pub struct TextComponentTypes<Cx>(PhantomData<Cx>);
+
+impl<Cx> TextComponentTypes<Cx>
+where
+    Cx: ProvideTextTy + ReadNbt<Text<Cx>> + for<'t> WriteNbt<&'t Text<Cx>> + UpdateNbt<Text<Cx>>,
+    Text<Cx>: Clone + Eq + Hash + Debug + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    const CUSTOM_NAME_PACKET_CODEC: PacketCodec<'static, CustomName<Cx>> = packet_codec_edcode();
+    const CUSTOM_NAME_SERDE_CODEC: SerdeCodec<'static, CustomName<Cx>> = serde_codec();
+    const ITEM_NAME_PACKET_CODEC: PacketCodec<'static, ItemName<Cx>> = packet_codec_edcode();
+    const ITEM_NAME_SERDE_CODEC: SerdeCodec<'static, ItemName<Cx>> = serde_codec();
+    const LORE_PACKET_CODEC: PacketCodec<'static, Lore<Cx>> = packet_codec_edcode();
+    const LORE_SERDE_CODEC: SerdeCodec<'static, Lore<Cx>> = serde_codec();
+
+    /// The custom name of an item stack.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.CUSTOM_NAME` (yarn).
+    pub const CUSTOM_NAME: ComponentType<'static, CustomName<Cx>> = ComponentType::builder::<Cx>()
+        .packet_codec(&Self::CUSTOM_NAME_PACKET_CODEC)
+        .serde_codec(&Self::CUSTOM_NAME_SERDE_CODEC)
+        .build();
+
+    /// The default name of an item stack, overridden by the custom name.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.ITEM_NAME` (yarn).
+    pub const ITEM_NAME: ComponentType<'static, ItemName<Cx>> = ComponentType::builder::<Cx>()
+        .packet_codec(&Self::ITEM_NAME_PACKET_CODEC)
+        .serde_codec(&Self::ITEM_NAME_SERDE_CODEC)
+        .build();
+
+    /// The lore of an item stack.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `DataComponentTypes.LORE` (yarn).
+    pub const LORE: ComponentType<'static, Lore<Cx>> = ComponentType::builder::<Cx>()
+        .packet_codec(&Self::LORE_PACKET_CODEC)
+        .serde_codec(&Self::LORE_SERDE_CODEC)
+        .build();
+}
+
+impl<Cx> Debug for TextComponentTypes<Cx> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TextComponentTypes")
+    }
+}
+
+/// Registers all component types in this module into the given registry.
+///
+/// The function `id` maps paths of the vanilla component types, such as
+/// `custom_name`, to identifiers in the registry.
+///
+/// # Errors
+///
+/// Returns the key of the component type that failed to register, because
+/// of a duplicated key.
+pub fn register<Cx, F>(
+    registry: &mut RegistryMut<Cx::Id, RawErasedComponentType<'static, Cx>>,
+    mut id: F,
+) -> Result<(), RegistryKey<Cx::Id, RawErasedComponentType<'static, Cx>>>
+where
+    Cx: ProvideTextTy
+        + rimecraft_global_cx::ProvideIdTy<Id: Clone>
+        + ReadNbt<Text<Cx>>
+        + for<'t> WriteNbt<&'t Text<Cx>>
+        + UpdateNbt<Text<Cx>>,
+    Text<Cx>: Clone + Eq + Hash + Debug + Serialize + DeserializeOwned + Send + Sync + 'static,
+    F: FnMut(&'static str) -> Cx::Id,
+{
+    let types: [(&'static str, RawErasedComponentType<'static, Cx>); 10] = [
+        (
+            "custom_name",
+            (&TextComponentTypes::<Cx>::CUSTOM_NAME).into(),
+        ),
+        ("item_name", (&TextComponentTypes::<Cx>::ITEM_NAME).into()),
+        ("lore", (&TextComponentTypes::<Cx>::LORE).into()),
+        ("damage", (&DAMAGE).into()),
+        ("max_damage", (&MAX_DAMAGE).into()),
+        ("unbreakable", (&UNBREAKABLE).into()),
+        ("max_stack_size", (&MAX_STACK_SIZE).into()),
+        ("rarity", (&RARITY).into()),
+        (
+            "enchantment_glint_override",
+            (&ENCHANTMENT_GLINT_OVERRIDE).into(),
+        ),
+        ("repair_cost", (&REPAIR_COST).into()),
+    ];
+    for (path, ty) in types {
+        let key = RegistryKey::new(registry.key().value().clone(), id(path));
+        registry.register(key, ty).map_err(|(key, _)| key)?;
+    }
+    Ok(())
+}
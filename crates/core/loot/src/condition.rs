@@ -0,0 +1,345 @@
+use std::{fmt::Debug, fmt::Display, str::FromStr};
+
+use rand::Rng;
+use rimecraft_item::{Item, ItemStack, RawItem};
+use rimecraft_registry::{entry::RefEntry, tag::TagKey, Registry};
+use serde::Deserialize;
+
+use crate::{LootContext, LootCx, NumberProvider};
+
+/// A condition deciding whether an entry, a pool or a function applies.
+///
+/// # Serialization
+///
+/// Conditions are dispatched by their `condition` field, which is one of
+/// `minecraft:random_chance`, `minecraft:match_tool` and
+/// `minecraft:survives_explosion`. The namespace may be omitted.
+///
+/// # MCJE Reference
+///
+/// This represents `LootCondition` (yarn).
+#[non_exhaustive]
+pub enum LootCondition<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Passes with the given chance.
+    RandomChance {
+        /// The chance of passing.
+        chance: NumberProvider,
+    },
+    /// Passes if the tool matches the predicate.
+    MatchTool {
+        /// The predicate of the tool.
+        predicate: ItemPredicate<'r, Cx>,
+    },
+    /// Passes with the chance of `1 / radius` if there is an explosion,
+    /// otherwise always passes.
+    SurvivesExplosion,
+}
+
+impl<'r, Cx> LootCondition<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Tests this condition against the given context.
+    pub fn test<R>(&self, context: &mut LootContext<'_, 'r, Cx, R>) -> bool
+    where
+        R: Rng,
+    {
+        match self {
+            Self::RandomChance { chance } => {
+                let chance = chance.next_f32(context.rng());
+                context.rng().gen::<f32>() < chance
+            }
+            Self::MatchTool { predicate } => {
+                context.tool().is_some_and(|tool| predicate.test(tool))
+            }
+            Self::SurvivesExplosion => context
+                .explosion_radius()
+                .is_none_or(|radius| context.rng().gen::<f32>() <= radius.recip()),
+        }
+    }
+
+    /// Tests all the given conditions against the given context, in order.
+    pub fn test_all<R>(conditions: &[Self], context: &mut LootContext<'_, 'r, Cx, R>) -> bool
+    where
+        R: Rng,
+    {
+        conditions.iter().all(|condition| condition.test(context))
+    }
+}
+
+impl<'r, Cx> Debug for LootCondition<'r, Cx>
+where
+    Cx: LootCx<'r, Id: Debug>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RandomChance { chance } => f
+                .debug_struct("RandomChance")
+                .field("chance", chance)
+                .finish(),
+            Self::MatchTool { predicate } => f
+                .debug_struct("MatchTool")
+                .field("predicate", predicate)
+                .finish(),
+            Self::SurvivesExplosion => f.write_str("SurvivesExplosion"),
+        }
+    }
+}
+
+/// Data pack representation of conditions, whose items are not resolved yet.
+#[derive(Deserialize)]
+#[serde(tag = "condition")]
+pub(crate) enum RawLootCondition {
+    #[serde(rename = "minecraft:random_chance", alias = "random_chance")]
+    RandomChance { chance: NumberProvider },
+    #[serde(rename = "minecraft:match_tool", alias = "match_tool")]
+    MatchTool { predicate: RawItemPredicate },
+    #[serde(rename = "minecraft:survives_explosion", alias = "survives_explosion")]
+    SurvivesExplosion,
+}
+
+impl RawLootCondition {
+    /// Resolves items of this condition through the given registry.
+    pub(crate) fn resolve<'r, Cx>(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<LootCondition<'r, Cx>, String>
+    where
+        Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+    {
+        Ok(match self {
+            Self::RandomChance { chance } => LootCondition::RandomChance { chance },
+            Self::MatchTool { predicate } => LootCondition::MatchTool {
+                predicate: predicate.resolve(registry)?,
+            },
+            Self::SurvivesExplosion => LootCondition::SurvivesExplosion,
+        })
+    }
+
+    /// Resolves all the given conditions through the given registry.
+    pub(crate) fn resolve_all<'r, Cx>(
+        conditions: Vec<Self>,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<Vec<LootCondition<'r, Cx>>, String>
+    where
+        Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+    {
+        conditions
+            .into_iter()
+            .map(|condition| condition.resolve(registry))
+            .collect()
+    }
+}
+
+/// A range of stack counts, where absent bounds are unbounded.
+///
+/// # Serialization
+///
+/// Ranges are deserialized from either an exact number or an object with
+/// optional `min` and `max` fields.
+///
+/// # MCJE Reference
+///
+/// This represents `NumberRange.IntRange` (yarn).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "RawCountRange")]
+pub struct CountRange {
+    /// The inclusive lower bound.
+    pub min: Option<u32>,
+    /// The inclusive upper bound.
+    pub max: Option<u32>,
+}
+
+impl CountRange {
+    /// A range containing any count.
+    pub const ANY: Self = Self {
+        min: None,
+        max: None,
+    };
+
+    /// Creates a range only containing the given count.
+    #[inline]
+    pub const fn exactly(count: u32) -> Self {
+        Self {
+            min: Some(count),
+            max: Some(count),
+        }
+    }
+
+    /// Whether the given count is in this range.
+    #[inline]
+    pub fn contains(&self, count: u32) -> bool {
+        self.min.is_none_or(|min| count >= min) && self.max.is_none_or(|max| count <= max)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawCountRange {
+    Exact(u32),
+    Bounds {
+        #[serde(default)]
+        min: Option<u32>,
+        #[serde(default)]
+        max: Option<u32>,
+    },
+}
+
+impl From<RawCountRange> for CountRange {
+    #[inline]
+    fn from(raw: RawCountRange) -> Self {
+        match raw {
+            RawCountRange::Exact(count) => Self::exactly(count),
+            RawCountRange::Bounds { min, max } => Self { min, max },
+        }
+    }
+}
+
+/// A list of items, either given directly or through a tag.
+#[non_exhaustive]
+pub enum ItemList<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// The given items.
+    Items(Vec<Item<'r, Cx>>),
+    /// Items of the given tag.
+    Tag(TagKey<Cx::Id, RawItem<'r, Cx>>),
+}
+
+impl<'r, Cx> ItemList<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Whether the given item is in this list.
+    pub fn contains(&self, item: Item<'r, Cx>) -> bool {
+        match self {
+            Self::Items(items) => items.contains(&item),
+            Self::Tag(tag) => {
+                let entry: &RefEntry<_, _> = item.as_ref();
+                entry.tags().contains(tag)
+            }
+        }
+    }
+}
+
+impl<'r, Cx> Debug for ItemList<'r, Cx>
+where
+    Cx: LootCx<'r, Id: Debug>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Items(items) => f.debug_tuple("Items").field(items).finish(),
+            Self::Tag(tag) => f.debug_tuple("Tag").field(tag).finish(),
+        }
+    }
+}
+
+/// A predicate of item stacks, matching their items and counts.
+///
+/// # Serialization
+///
+/// Predicates are deserialized from objects with optional `items` and
+/// `count` fields, where `items` is an item identifier, a tag identifier
+/// prefixed with `#`, or a list of item identifiers.
+///
+/// # MCJE Reference
+///
+/// This represents `ItemPredicate` (yarn).
+pub struct ItemPredicate<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    items: Option<ItemList<'r, Cx>>,
+    count: CountRange,
+}
+
+impl<'r, Cx> ItemPredicate<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Creates a predicate matching stacks of items in the given list, or of
+    /// any item if `None`, whose count is in the given range.
+    #[inline]
+    pub fn new(items: Option<ItemList<'r, Cx>>, count: CountRange) -> Self {
+        Self { items, count }
+    }
+
+    /// Returns the list of items matched by this predicate.
+    #[inline]
+    pub fn items(&self) -> Option<&ItemList<'r, Cx>> {
+        self.items.as_ref()
+    }
+
+    /// Returns the range of counts matched by this predicate.
+    #[inline]
+    pub fn count(&self) -> CountRange {
+        self.count
+    }
+
+    /// Whether the given stack matches this predicate.
+    pub fn test(&self, stack: &ItemStack<'r, Cx>) -> bool {
+        self.items
+            .as_ref()
+            .is_none_or(|items| items.contains(stack.item()))
+            && self.count.contains(stack.count())
+    }
+}
+
+impl<'r, Cx> Debug for ItemPredicate<'r, Cx>
+where
+    Cx: LootCx<'r, Id: Debug>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemPredicate")
+            .field("items", &self.items)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawItemList {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RawItemPredicate {
+    #[serde(default)]
+    items: Option<RawItemList>,
+    #[serde(default)]
+    count: CountRange,
+}
+
+impl RawItemPredicate {
+    /// Resolves items of this predicate through the given registry.
+    fn resolve<'r, Cx>(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<ItemPredicate<'r, Cx>, String>
+    where
+        Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+    {
+        let items = match self.items {
+            None => None,
+            Some(RawItemList::One(id)) => Some(match id.strip_prefix('#') {
+                Some(tag) => ItemList::Tag(crate::item_tag(registry, tag)?),
+                None => ItemList::Items(vec![crate::resolve_item(registry, &id)?]),
+            }),
+            Some(RawItemList::Many(ids)) => Some(ItemList::Items(
+                ids.iter()
+                    .map(|id| crate::resolve_item(registry, id))
+                    .collect::<Result<_, _>>()?,
+            )),
+        };
+        Ok(ItemPredicate {
+            items,
+            count: self.count,
+        })
+    }
+}
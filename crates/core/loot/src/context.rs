@@ -0,0 +1,121 @@
+use std::fmt::Debug;
+
+use rimecraft_item::{ItemStack, RawItem};
+use rimecraft_registry::Registry;
+
+use crate::LootCx;
+
+/// Context of generating loot, providing the random source and parameters
+/// that conditions and functions could rely on.
+///
+/// Loot generated with random sources seeded with the same seed is
+/// deterministic.
+///
+/// # MCJE Reference
+///
+/// This represents `LootContext` (yarn).
+pub struct LootContext<'a, 'r, Cx, R>
+where
+    Cx: LootCx<'r>,
+{
+    items: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    rng: R,
+    luck: f32,
+    tool: Option<&'a ItemStack<'r, Cx>>,
+    explosion_radius: Option<f32>,
+}
+
+impl<'a, 'r, Cx, R> LootContext<'a, 'r, Cx, R>
+where
+    Cx: LootCx<'r>,
+{
+    /// Creates a new loot context with given registry of items, random source
+    /// and no parameters.
+    ///
+    /// Items of tags are looked up in the given registry.
+    #[inline]
+    pub fn new(items: &'r Registry<Cx::Id, RawItem<'r, Cx>>, rng: R) -> Self {
+        Self {
+            items,
+            rng,
+            luck: 0.0,
+            tool: None,
+            explosion_radius: None,
+        }
+    }
+
+    /// Sets the luck of this context.
+    #[inline]
+    pub fn with_luck(self, luck: f32) -> Self {
+        Self { luck, ..self }
+    }
+
+    /// Sets the tool used to break the block or kill the entity.
+    #[inline]
+    pub fn with_tool(self, tool: &'a ItemStack<'r, Cx>) -> Self {
+        Self {
+            tool: Some(tool),
+            ..self
+        }
+    }
+
+    /// Sets the radius of the explosion destroying the block.
+    #[inline]
+    pub fn with_explosion_radius(self, radius: f32) -> Self {
+        Self {
+            explosion_radius: Some(radius),
+            ..self
+        }
+    }
+
+    /// Returns the registry of items of this context.
+    #[inline]
+    pub fn items(&self) -> &'r Registry<Cx::Id, RawItem<'r, Cx>> {
+        self.items
+    }
+
+    /// Returns the random source of this context.
+    #[inline]
+    pub fn rng(&mut self) -> &mut R {
+        &mut self.rng
+    }
+
+    /// Returns the luck of this context.
+    #[inline]
+    pub fn luck(&self) -> f32 {
+        self.luck
+    }
+
+    /// Returns the tool used to break the block or kill the entity.
+    #[inline]
+    pub fn tool(&self) -> Option<&'a ItemStack<'r, Cx>> {
+        self.tool
+    }
+
+    /// Returns the radius of the explosion destroying the block.
+    #[inline]
+    pub fn explosion_radius(&self) -> Option<f32> {
+        self.explosion_radius
+    }
+
+    /// Consumes this context and returns its random source.
+    #[inline]
+    pub fn into_rng(self) -> R {
+        self.rng
+    }
+}
+
+impl<'r, Cx, R> Debug for LootContext<'_, 'r, Cx, R>
+where
+    Cx: LootCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+    R: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LootContext")
+            .field("rng", &self.rng)
+            .field("luck", &self.luck)
+            .field("tool", &self.tool)
+            .field("explosion_radius", &self.explosion_radius)
+            .finish_non_exhaustive()
+    }
+}
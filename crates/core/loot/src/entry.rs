@@ -0,0 +1,402 @@
+use std::{fmt::Debug, fmt::Display, str::FromStr};
+
+use rand::Rng;
+use rimecraft_item::{component::changes::ComponentChanges, Item, ItemStack, RawItem};
+use rimecraft_registry::{tag::TagKey, Registry};
+use serde::Deserialize;
+
+use crate::{
+    condition::RawLootCondition, function::RawLootFunction, LootCondition, LootContext, LootCx,
+    LootFunction,
+};
+
+/// Properties shared by entries generating loot by themselves.
+///
+/// # MCJE Reference
+///
+/// This represents the properties of `LeafEntry` (yarn).
+pub struct LeafProperties<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// The weight of this entry being chosen.
+    pub weight: u32,
+    /// The weight added to this entry per point of luck.
+    pub quality: i32,
+    /// Functions applied to the generated stacks.
+    pub functions: Vec<LootFunction<'r, Cx>>,
+    /// Conditions of this entry.
+    pub conditions: Vec<LootCondition<'r, Cx>>,
+}
+
+#[inline]
+const fn default_weight() -> u32 {
+    1
+}
+
+impl<'r, Cx> LeafProperties<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Returns the weight of this entry with given luck, which is never
+    /// negative.
+    pub fn weight(&self, luck: f32) -> u32 {
+        (self.weight as f32 + self.quality as f32 * luck)
+            .floor()
+            .max(0.0) as u32
+    }
+
+    fn generate<R>(
+        &self,
+        item: Item<'r, Cx>,
+        context: &mut LootContext<'_, 'r, Cx, R>,
+        output: &mut Vec<ItemStack<'r, Cx>>,
+    ) where
+        R: Rng,
+    {
+        let mut stack = ItemStack::new(item, 1);
+        for function in &self.functions {
+            function.apply(&mut stack, context);
+        }
+        output.push(stack);
+    }
+}
+
+impl<'r, Cx> Default for LeafProperties<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self {
+            weight: default_weight(),
+            quality: 0,
+            functions: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+}
+
+impl<'r, Cx> Debug for LeafProperties<'r, Cx>
+where
+    Cx: LootCx<'r, Id: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeafProperties")
+            .field("weight", &self.weight)
+            .field("quality", &self.quality)
+            .field("functions", &self.functions)
+            .field("conditions", &self.conditions)
+            .finish()
+    }
+}
+
+/// An entry of a loot pool.
+///
+/// # Serialization
+///
+/// Entries are dispatched by their `type` field, which is one of
+/// `minecraft:item`, `minecraft:tag`, `minecraft:empty` and
+/// `minecraft:alternatives`. The namespace may be omitted.
+///
+/// # MCJE Reference
+///
+/// This represents `LootPoolEntry` (yarn).
+#[non_exhaustive]
+pub enum LootEntry<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Generates a stack of the given item.
+    Item {
+        /// The item to generate.
+        item: Item<'r, Cx>,
+        /// Properties of this entry.
+        properties: LeafProperties<'r, Cx>,
+    },
+    /// Generates stacks of items of the given tag.
+    Tag {
+        /// The tag of items to generate.
+        tag: TagKey<Cx::Id, RawItem<'r, Cx>>,
+        /// Whether to choose a single item of the tag, as if each item of
+        /// the tag is a separate entry, instead of generating all of them.
+        expand: bool,
+        /// Properties of this entry.
+        properties: LeafProperties<'r, Cx>,
+    },
+    /// Generates nothing.
+    Empty {
+        /// Properties of this entry.
+        properties: LeafProperties<'r, Cx>,
+    },
+    /// Chooses from the first child whose conditions pass.
+    Alternatives {
+        /// The children of this entry.
+        children: Vec<LootEntry<'r, Cx>>,
+        /// Conditions of this entry.
+        conditions: Vec<LootCondition<'r, Cx>>,
+    },
+}
+
+/// A weighted choice expanded from an entry.
+pub(crate) struct Choice<'e, 'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    kind: ChoiceKind<'e, 'r, Cx>,
+    properties: &'e LeafProperties<'r, Cx>,
+}
+
+enum ChoiceKind<'e, 'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    Item(Item<'r, Cx>),
+    Tag(&'e TagKey<Cx::Id, RawItem<'r, Cx>>),
+    Empty,
+}
+
+impl<'r, Cx> Choice<'_, 'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Returns the weight of this choice with given luck.
+    #[inline]
+    pub(crate) fn weight(&self, luck: f32) -> u32 {
+        self.properties.weight(luck)
+    }
+
+    /// Generates stacks of this choice into the given output.
+    pub(crate) fn generate<R>(
+        &self,
+        context: &mut LootContext<'_, 'r, Cx, R>,
+        output: &mut Vec<ItemStack<'r, Cx>>,
+    ) where
+        R: Rng,
+    {
+        match self.kind {
+            ChoiceKind::Item(item) => self.properties.generate(item, context, output),
+            ChoiceKind::Tag(tag) => {
+                for item in context.items().of_tag(tag) {
+                    self.properties.generate(item, context, output);
+                }
+            }
+            ChoiceKind::Empty => {}
+        }
+    }
+}
+
+impl<'r, Cx> LootEntry<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Expands this entry into weighted choices if its conditions pass,
+    /// returning whether they passed.
+    pub(crate) fn expand<'e, R>(
+        &'e self,
+        context: &mut LootContext<'_, 'r, Cx, R>,
+        choices: &mut Vec<Choice<'e, 'r, Cx>>,
+    ) -> bool
+    where
+        R: Rng,
+    {
+        let (kind, properties) = match self {
+            Self::Item { item, properties } => (ChoiceKind::Item(*item), properties),
+            Self::Tag {
+                tag,
+                expand,
+                properties,
+            } => {
+                if !LootCondition::test_all(&properties.conditions, context) {
+                    return false;
+                }
+                if !*expand {
+                    choices.push(Choice {
+                        kind: ChoiceKind::Tag(tag),
+                        properties,
+                    });
+                    return true;
+                }
+                // An expanded empty tag offers no choice, so alternatives fall
+                // through to the next child.
+                let start = choices.len();
+                choices.extend(context.items().of_tag(tag).map(|item| Choice {
+                    kind: ChoiceKind::Item(item),
+                    properties,
+                }));
+                return choices.len() > start;
+            }
+            Self::Empty { properties } => (ChoiceKind::Empty, properties),
+            Self::Alternatives {
+                children,
+                conditions,
+            } => {
+                return LootCondition::test_all(conditions, context)
+                    && children.iter().any(|child| child.expand(context, choices));
+            }
+        };
+        if LootCondition::test_all(&properties.conditions, context) {
+            choices.push(Choice { kind, properties });
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<'r, Cx> Debug for LootEntry<'r, Cx>
+where
+    Cx: LootCx<'r, Id: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Item { item, properties } => f
+                .debug_struct("Item")
+                .field("item", item)
+                .field("properties", properties)
+                .finish(),
+            Self::Tag {
+                tag,
+                expand,
+                properties,
+            } => f
+                .debug_struct("Tag")
+                .field("tag", tag)
+                .field("expand", expand)
+                .field("properties", properties)
+                .finish(),
+            Self::Empty { properties } => f
+                .debug_struct("Empty")
+                .field("properties", properties)
+                .finish(),
+            Self::Alternatives {
+                children,
+                conditions,
+            } => f
+                .debug_struct("Alternatives")
+                .field("children", children)
+                .field("conditions", conditions)
+                .finish(),
+        }
+    }
+}
+
+/// Data pack representation of [`LeafProperties`], whose items are not
+/// resolved yet.
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "ComponentChanges<'r, 'r, Cx>: Deserialize<'de>"))]
+pub(crate) struct RawLeafProperties<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    #[serde(default = "default_weight")]
+    weight: u32,
+    #[serde(default)]
+    quality: i32,
+    #[serde(default)]
+    functions: Vec<RawLootFunction<'r, Cx>>,
+    #[serde(default)]
+    conditions: Vec<RawLootCondition>,
+}
+
+impl<'r, Cx> RawLeafProperties<'r, Cx>
+where
+    Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+{
+    fn resolve(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<LeafProperties<'r, Cx>, String> {
+        Ok(LeafProperties {
+            weight: self.weight,
+            quality: self.quality,
+            functions: RawLootFunction::resolve_all(self.functions, registry)?,
+            conditions: RawLootCondition::resolve_all(self.conditions, registry)?,
+        })
+    }
+}
+
+/// Data pack representation of entries, whose items are not resolved yet.
+#[derive(Deserialize)]
+#[serde(
+    tag = "type",
+    bound(deserialize = "ComponentChanges<'r, 'r, Cx>: Deserialize<'de>")
+)]
+pub(crate) enum RawLootEntry<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    #[serde(rename = "minecraft:item", alias = "item")]
+    Item {
+        name: String,
+        #[serde(flatten)]
+        properties: RawLeafProperties<'r, Cx>,
+    },
+    #[serde(rename = "minecraft:tag", alias = "tag")]
+    Tag {
+        name: String,
+        #[serde(default)]
+        expand: bool,
+        #[serde(flatten)]
+        properties: RawLeafProperties<'r, Cx>,
+    },
+    #[serde(rename = "minecraft:empty", alias = "empty")]
+    Empty {
+        #[serde(flatten)]
+        properties: RawLeafProperties<'r, Cx>,
+    },
+    #[serde(rename = "minecraft:alternatives", alias = "alternatives")]
+    Alternatives {
+        children: Vec<RawLootEntry<'r, Cx>>,
+        #[serde(default)]
+        conditions: Vec<RawLootCondition>,
+    },
+}
+
+impl<'r, Cx> RawLootEntry<'r, Cx>
+where
+    Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+{
+    /// Resolves items of this entry through the given registry.
+    pub(crate) fn resolve(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<LootEntry<'r, Cx>, String> {
+        Ok(match self {
+            Self::Item { name, properties } => LootEntry::Item {
+                item: crate::resolve_item(registry, &name)?,
+                properties: properties.resolve(registry)?,
+            },
+            Self::Tag {
+                name,
+                expand,
+                properties,
+            } => LootEntry::Tag {
+                tag: crate::item_tag(registry, &name)?,
+                expand,
+                properties: properties.resolve(registry)?,
+            },
+            Self::Empty { properties } => LootEntry::Empty {
+                properties: properties.resolve(registry)?,
+            },
+            Self::Alternatives {
+                children,
+                conditions,
+            } => LootEntry::Alternatives {
+                children: Self::resolve_all(children, registry)?,
+                conditions: RawLootCondition::resolve_all(conditions, registry)?,
+            },
+        })
+    }
+
+    /// Resolves all the given entries through the given registry.
+    pub(crate) fn resolve_all(
+        entries: Vec<Self>,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<Vec<LootEntry<'r, Cx>>, String> {
+        entries
+            .into_iter()
+            .map(|entry| entry.resolve(registry))
+            .collect()
+    }
+}
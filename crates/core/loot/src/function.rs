@@ -0,0 +1,225 @@
+use std::{fmt::Debug, fmt::Display, str::FromStr};
+
+use rand::Rng;
+use rimecraft_item::{component::changes::ComponentChanges, ItemStack, RawItem};
+use rimecraft_registry::Registry;
+use serde::Deserialize;
+
+use crate::{condition::RawLootCondition, LootCondition, LootContext, LootCx, NumberProvider};
+
+/// A function modifying generated stacks, applied only if all of its
+/// conditions pass.
+///
+/// # Serialization
+///
+/// Functions are dispatched by their `function` field, which is one of
+/// `minecraft:set_count`, `minecraft:set_damage` and
+/// `minecraft:set_components`. The namespace may be omitted.
+///
+/// # MCJE Reference
+///
+/// This represents `LootFunction` (yarn).
+#[non_exhaustive]
+pub enum LootFunction<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Sets the count of the stack.
+    ///
+    /// The count is not clamped to the max count of the stack, and stacks
+    /// exceeding it are split when dropped from a [`LootTable`](crate::LootTable).
+    SetCount {
+        /// The count to set.
+        count: NumberProvider,
+        /// Whether to add the count to the current count instead.
+        add: bool,
+        /// Conditions of this function.
+        conditions: Vec<LootCondition<'r, Cx>>,
+    },
+    /// Sets the durability of damageable stacks, as a fraction of the max
+    /// damage between `0` and `1`.
+    SetDamage {
+        /// The durability fraction to set.
+        damage: NumberProvider,
+        /// Whether to add the fraction to the current durability instead.
+        add: bool,
+        /// Conditions of this function.
+        conditions: Vec<LootCondition<'r, Cx>>,
+    },
+    /// Applies component changes to the stack.
+    SetComponents {
+        /// The changes to apply.
+        components: ComponentChanges<'r, 'r, Cx>,
+        /// Conditions of this function.
+        conditions: Vec<LootCondition<'r, Cx>>,
+    },
+}
+
+impl<'r, Cx> LootFunction<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Returns the conditions of this function.
+    pub fn conditions(&self) -> &[LootCondition<'r, Cx>] {
+        match self {
+            Self::SetCount { conditions, .. }
+            | Self::SetDamage { conditions, .. }
+            | Self::SetComponents { conditions, .. } => conditions,
+        }
+    }
+
+    /// Applies this function to the given stack if all of its conditions
+    /// pass.
+    pub fn apply<R>(&self, stack: &mut ItemStack<'r, Cx>, context: &mut LootContext<'_, 'r, Cx, R>)
+    where
+        R: Rng,
+    {
+        if !LootCondition::test_all(self.conditions(), context) {
+            return;
+        }
+        match self {
+            Self::SetCount { count, add, .. } => {
+                let base = if *add { stack.count() as i64 } else { 0 };
+                let count = base + count.next_i32(context.rng()) as i64;
+                stack.set_count(count.clamp(0, u32::MAX as i64) as u32);
+            }
+            Self::SetDamage { damage, add, .. } => {
+                if !stack.is_damageable() {
+                    return;
+                }
+                let max = stack.max_damage() as f32;
+                let base = if *add {
+                    1.0 - stack.current_damage() as f32 / max
+                } else {
+                    0.0
+                };
+                let durability = (damage.next_f32(context.rng()) + base).clamp(0.0, 1.0);
+                stack.set_damage(((1.0 - durability) * max).floor() as u32);
+            }
+            Self::SetComponents { components, .. } => stack.apply_changes(components),
+        }
+    }
+}
+
+impl<'r, Cx> Debug for LootFunction<'r, Cx>
+where
+    Cx: LootCx<'r, Id: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SetCount {
+                count,
+                add,
+                conditions,
+            } => f
+                .debug_struct("SetCount")
+                .field("count", count)
+                .field("add", add)
+                .field("conditions", conditions)
+                .finish(),
+            Self::SetDamage {
+                damage,
+                add,
+                conditions,
+            } => f
+                .debug_struct("SetDamage")
+                .field("damage", damage)
+                .field("add", add)
+                .field("conditions", conditions)
+                .finish(),
+            Self::SetComponents {
+                components,
+                conditions,
+            } => f
+                .debug_struct("SetComponents")
+                .field("components", components)
+                .field("conditions", conditions)
+                .finish(),
+        }
+    }
+}
+
+/// Data pack representation of functions, whose items are not resolved yet.
+#[allow(clippy::enum_variant_names)]
+#[derive(Deserialize)]
+#[serde(
+    tag = "function",
+    bound(deserialize = "ComponentChanges<'r, 'r, Cx>: Deserialize<'de>")
+)]
+pub(crate) enum RawLootFunction<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    #[serde(rename = "minecraft:set_count", alias = "set_count")]
+    SetCount {
+        count: NumberProvider,
+        #[serde(default)]
+        add: bool,
+        #[serde(default)]
+        conditions: Vec<RawLootCondition>,
+    },
+    #[serde(rename = "minecraft:set_damage", alias = "set_damage")]
+    SetDamage {
+        damage: NumberProvider,
+        #[serde(default)]
+        add: bool,
+        #[serde(default)]
+        conditions: Vec<RawLootCondition>,
+    },
+    #[serde(rename = "minecraft:set_components", alias = "set_components")]
+    SetComponents {
+        components: ComponentChanges<'r, 'r, Cx>,
+        #[serde(default)]
+        conditions: Vec<RawLootCondition>,
+    },
+}
+
+impl<'r, Cx> RawLootFunction<'r, Cx>
+where
+    Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+{
+    /// Resolves items of this function through the given registry.
+    pub(crate) fn resolve(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<LootFunction<'r, Cx>, String> {
+        Ok(match self {
+            Self::SetCount {
+                count,
+                add,
+                conditions,
+            } => LootFunction::SetCount {
+                count,
+                add,
+                conditions: RawLootCondition::resolve_all(conditions, registry)?,
+            },
+            Self::SetDamage {
+                damage,
+                add,
+                conditions,
+            } => LootFunction::SetDamage {
+                damage,
+                add,
+                conditions: RawLootCondition::resolve_all(conditions, registry)?,
+            },
+            Self::SetComponents {
+                components,
+                conditions,
+            } => LootFunction::SetComponents {
+                components,
+                conditions: RawLootCondition::resolve_all(conditions, registry)?,
+            },
+        })
+    }
+
+    /// Resolves all the given functions through the given registry.
+    pub(crate) fn resolve_all(
+        functions: Vec<Self>,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<Vec<LootFunction<'r, Cx>>, String> {
+        functions
+            .into_iter()
+            .map(|function| function.resolve(registry))
+            .collect()
+    }
+}
@@ -0,0 +1,71 @@
+//! Minecraft loot table primitives.
+
+use std::{fmt::Display, hash::Hash, str::FromStr};
+
+use rimecraft_item::{
+    damage::ProvideDamageComponentTypes, stack::ItemStackCx, Item, ProvideItemComponentTypes,
+    RawItem,
+};
+use rimecraft_registry::{tag::TagKey, Registry};
+
+mod condition;
+mod context;
+mod entry;
+mod function;
+mod number;
+mod table;
+
+pub use condition::{CountRange, ItemList, ItemPredicate, LootCondition};
+pub use context::LootContext;
+pub use entry::{LeafProperties, LootEntry};
+pub use function::LootFunction;
+pub use number::NumberProvider;
+pub use table::{LootPool, LootTable, LootTableSeed};
+
+/// Global context used for loot tables.
+pub trait LootCx<'r>:
+    ItemStackCx<Id: Hash + Eq> + ProvideItemComponentTypes<'r> + ProvideDamageComponentTypes<'r>
+{
+}
+
+impl<'r, T> LootCx<'r> for T where
+    T: ItemStackCx<Id: Hash + Eq> + ProvideItemComponentTypes<'r> + ProvideDamageComponentTypes<'r>
+{
+}
+
+fn parse_id<Cx>(id: &str) -> Result<Cx::Id, String>
+where
+    Cx: ItemStackCx<Id: FromStr<Err: Display> + Clone>,
+{
+    id.parse()
+        .map_err(|err| format!("invalid identifier {id}: {err}"))
+}
+
+fn resolve_item<'r, Cx>(
+    registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    id: &str,
+) -> Result<Item<'r, Cx>, String>
+where
+    Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+{
+    let parsed = parse_id::<Cx>(id)?;
+    registry
+        .get(&parsed)
+        .ok_or_else(|| format!("unknown item {id}"))
+}
+
+fn item_tag<'r, Cx>(
+    registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    id: &str,
+) -> Result<TagKey<Cx::Id, RawItem<'r, Cx>>, String>
+where
+    Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+{
+    Ok(TagKey {
+        registry: registry.key().clone(),
+        id: parse_id::<Cx>(id)?,
+    })
+}
+
+#[cfg(test)]
+mod tests;
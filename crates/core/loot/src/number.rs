@@ -0,0 +1,113 @@
+use rand::Rng;
+use serde::Deserialize;
+
+/// Provider of random numbers used by loot tables.
+///
+/// # Serialization
+///
+/// Constants are deserialized from plain numbers. Other providers are
+/// objects dispatched by their `type` field, where objects without the field
+/// but with `min` and `max` are uniform providers.
+///
+/// # MCJE Reference
+///
+/// This represents `LootNumberProvider` (yarn).
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(from = "RawNumberProvider")]
+#[non_exhaustive]
+pub enum NumberProvider {
+    /// A constant number.
+    Constant(f32),
+    /// A uniformly distributed number between `min` and `max`, both inclusive.
+    Uniform {
+        /// The minimum value.
+        min: f32,
+        /// The maximum value.
+        max: f32,
+    },
+    /// A binomially distributed number of `n` trials with success probability
+    /// of `p`.
+    Binomial {
+        /// The count of trials.
+        n: u32,
+        /// The probability of success of each trial.
+        p: f32,
+    },
+}
+
+impl NumberProvider {
+    /// Returns a random integer of this provider.
+    pub fn next_i32<R>(&self, rng: &mut R) -> i32
+    where
+        R: Rng + ?Sized,
+    {
+        match *self {
+            Self::Constant(value) => value.round() as i32,
+            Self::Uniform { min, max } => {
+                let (min, max) = (min.round() as i32, max.round() as i32);
+                if min >= max {
+                    min
+                } else {
+                    rng.gen_range(min..=max)
+                }
+            }
+            Self::Binomial { n, p } => (0..n).filter(|_| rng.gen::<f32>() < p).count() as i32,
+        }
+    }
+
+    /// Returns a random float of this provider.
+    pub fn next_f32<R>(&self, rng: &mut R) -> f32
+    where
+        R: Rng + ?Sized,
+    {
+        match *self {
+            Self::Constant(value) => value,
+            Self::Uniform { min, max } => min + rng.gen::<f32>() * (max - min),
+            Self::Binomial { .. } => self.next_i32(rng) as f32,
+        }
+    }
+}
+
+impl Default for NumberProvider {
+    #[inline]
+    fn default() -> Self {
+        Self::Constant(0.0)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawNumberProvider {
+    Constant(f32),
+    Typed(TypedNumberProvider),
+    Uniform { min: f32, max: f32 },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum TypedNumberProvider {
+    #[serde(rename = "minecraft:constant", alias = "constant")]
+    Constant { value: f32 },
+    #[serde(rename = "minecraft:uniform", alias = "uniform")]
+    Uniform { min: f32, max: f32 },
+    #[serde(rename = "minecraft:binomial", alias = "binomial")]
+    Binomial { n: u32, p: f32 },
+}
+
+impl From<RawNumberProvider> for NumberProvider {
+    fn from(raw: RawNumberProvider) -> Self {
+        match raw {
+            RawNumberProvider::Constant(value)
+            | RawNumberProvider::Typed(TypedNumberProvider::Constant { value }) => {
+                Self::Constant(value)
+            }
+            RawNumberProvider::Uniform { min, max }
+            | RawNumberProvider::Typed(TypedNumberProvider::Uniform { min, max }) => {
+                Self::Uniform { min, max }
+            }
+            RawNumberProvider::Typed(TypedNumberProvider::Binomial { n, p }) => {
+                Self::Binomial { n, p }
+            }
+        }
+    }
+}
@@ -0,0 +1,382 @@
+use std::{fmt::Debug, fmt::Display, str::FromStr};
+
+use rand::Rng;
+use rimecraft_item::{component::changes::ComponentChanges, ItemStack, RawItem};
+use rimecraft_registry::Registry;
+use serde::{de::DeserializeSeed, Deserialize};
+
+use crate::{
+    condition::RawLootCondition, entry::RawLootEntry, function::RawLootFunction, LootCondition,
+    LootContext, LootCx, LootEntry, LootFunction, NumberProvider,
+};
+
+/// A pool of a loot table, choosing from its entries for each roll.
+///
+/// # MCJE Reference
+///
+/// This represents `LootPool` (yarn).
+pub struct LootPool<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    entries: Vec<LootEntry<'r, Cx>>,
+    conditions: Vec<LootCondition<'r, Cx>>,
+    functions: Vec<LootFunction<'r, Cx>>,
+    rolls: NumberProvider,
+    bonus_rolls: NumberProvider,
+}
+
+impl<'r, Cx> LootPool<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Creates a new loot pool with given rolls and entries.
+    #[inline]
+    pub fn new(rolls: NumberProvider, entries: Vec<LootEntry<'r, Cx>>) -> Self {
+        Self {
+            entries,
+            conditions: Vec::new(),
+            functions: Vec::new(),
+            rolls,
+            bonus_rolls: NumberProvider::Constant(0.0),
+        }
+    }
+
+    /// Sets the rolls added per point of luck.
+    #[inline]
+    pub fn with_bonus_rolls(self, bonus_rolls: NumberProvider) -> Self {
+        Self {
+            bonus_rolls,
+            ..self
+        }
+    }
+
+    /// Adds a condition to this pool.
+    #[inline]
+    pub fn with_condition(mut self, condition: LootCondition<'r, Cx>) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Adds a function applied to stacks generated by this pool.
+    #[inline]
+    pub fn with_function(mut self, function: LootFunction<'r, Cx>) -> Self {
+        self.functions.push(function);
+        self
+    }
+
+    /// Returns the entries of this pool.
+    #[inline]
+    pub fn entries(&self) -> &[LootEntry<'r, Cx>] {
+        &self.entries
+    }
+
+    /// Returns the rolls of this pool.
+    #[inline]
+    pub fn rolls(&self) -> NumberProvider {
+        self.rolls
+    }
+
+    /// Returns the rolls added per point of luck.
+    #[inline]
+    pub fn bonus_rolls(&self) -> NumberProvider {
+        self.bonus_rolls
+    }
+
+    /// Chooses a single entry from this pool by weight, and generates its
+    /// stacks into the given output.
+    fn supply_once<R>(
+        &self,
+        context: &mut LootContext<'_, 'r, Cx, R>,
+        output: &mut Vec<ItemStack<'r, Cx>>,
+    ) where
+        R: Rng,
+    {
+        let mut choices = Vec::new();
+        for entry in &self.entries {
+            entry.expand(context, &mut choices);
+        }
+        let luck = context.luck();
+        choices.retain(|choice| choice.weight(luck) > 0);
+        let total: u32 = choices.iter().map(|choice| choice.weight(luck)).sum();
+
+        match choices.as_slice() {
+            [] => {}
+            [choice] => choice.generate(context, output),
+            choices => {
+                let mut remaining = context.rng().gen_range(0..total);
+                for choice in choices {
+                    let weight = choice.weight(luck);
+                    if remaining < weight {
+                        choice.generate(context, output);
+                        return;
+                    }
+                    remaining -= weight;
+                }
+            }
+        }
+    }
+
+    /// Generates loot of this pool into the given output if its conditions
+    /// pass.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `LootPool.addGeneratedLoot` (yarn).
+    pub fn generate_loot<R>(
+        &self,
+        context: &mut LootContext<'_, 'r, Cx, R>,
+        output: &mut Vec<ItemStack<'r, Cx>>,
+    ) where
+        R: Rng,
+    {
+        if !LootCondition::test_all(&self.conditions, context) {
+            return;
+        }
+        let rolls = self.rolls.next_i32(context.rng())
+            + (self.bonus_rolls.next_f32(context.rng()) * context.luck()).floor() as i32;
+        let start = output.len();
+        for _ in 0..rolls {
+            self.supply_once(context, output);
+        }
+        for stack in &mut output[start..] {
+            for function in &self.functions {
+                function.apply(stack, context);
+            }
+        }
+    }
+}
+
+impl<'r, Cx> Debug for LootPool<'r, Cx>
+where
+    Cx: LootCx<'r, Id: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LootPool")
+            .field("entries", &self.entries)
+            .field("conditions", &self.conditions)
+            .field("functions", &self.functions)
+            .field("rolls", &self.rolls)
+            .field("bonus_rolls", &self.bonus_rolls)
+            .finish()
+    }
+}
+
+/// A loot table, generating item stacks from its pools.
+///
+/// # Serialization
+///
+/// This type is deserialized through [`LootTableSeed`] from the data pack
+/// format, with optional `pools` and `functions` fields.
+///
+/// # MCJE Reference
+///
+/// This represents `LootTable` (yarn).
+pub struct LootTable<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    pools: Vec<LootPool<'r, Cx>>,
+    functions: Vec<LootFunction<'r, Cx>>,
+}
+
+impl<'r, Cx> LootTable<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// An empty loot table, generating nothing.
+    pub const EMPTY: Self = Self {
+        pools: Vec::new(),
+        functions: Vec::new(),
+    };
+
+    /// Creates a new loot table with given pools.
+    #[inline]
+    pub fn new(pools: Vec<LootPool<'r, Cx>>) -> Self {
+        Self {
+            pools,
+            functions: Vec::new(),
+        }
+    }
+
+    /// Adds a function applied to all stacks generated by this table.
+    #[inline]
+    pub fn with_function(mut self, function: LootFunction<'r, Cx>) -> Self {
+        self.functions.push(function);
+        self
+    }
+
+    /// Returns the pools of this table.
+    #[inline]
+    pub fn pools(&self) -> &[LootPool<'r, Cx>] {
+        &self.pools
+    }
+
+    /// Generates loot of this table.
+    ///
+    /// Empty stacks are dropped, and stacks exceeding their max count are
+    /// split into multiple stacks.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `LootTable.generateLoot` (yarn).
+    pub fn generate_loot<R>(
+        &self,
+        context: &mut LootContext<'_, 'r, Cx, R>,
+    ) -> Vec<ItemStack<'r, Cx>>
+    where
+        R: Rng,
+    {
+        let mut generated = Vec::new();
+        for pool in &self.pools {
+            pool.generate_loot(context, &mut generated);
+        }
+
+        let mut output = Vec::with_capacity(generated.len());
+        for mut stack in generated {
+            for function in &self.functions {
+                function.apply(&mut stack, context);
+            }
+            let max = stack.max_count().max(1);
+            while !stack.is_empty() {
+                output.push(stack.split(max));
+            }
+        }
+        output
+    }
+}
+
+impl<'r, Cx> Default for LootTable<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<'r, Cx> Debug for LootTable<'r, Cx>
+where
+    Cx: LootCx<'r, Id: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LootTable")
+            .field("pools", &self.pools)
+            .field("functions", &self.functions)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "ComponentChanges<'r, 'r, Cx>: Deserialize<'de>"))]
+struct RawLootPool<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    entries: Vec<RawLootEntry<'r, Cx>>,
+    #[serde(default)]
+    conditions: Vec<RawLootCondition>,
+    #[serde(default)]
+    functions: Vec<RawLootFunction<'r, Cx>>,
+    rolls: NumberProvider,
+    #[serde(default)]
+    bonus_rolls: NumberProvider,
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "ComponentChanges<'r, 'r, Cx>: Deserialize<'de>"))]
+struct RawLootTable<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    #[serde(default)]
+    pools: Vec<RawLootPool<'r, Cx>>,
+    #[serde(default)]
+    functions: Vec<RawLootFunction<'r, Cx>>,
+}
+
+impl<'r, Cx> RawLootTable<'r, Cx>
+where
+    Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+{
+    fn resolve(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<LootTable<'r, Cx>, String> {
+        Ok(LootTable {
+            pools: self
+                .pools
+                .into_iter()
+                .map(|pool| {
+                    Ok(LootPool {
+                        entries: RawLootEntry::resolve_all(pool.entries, registry)?,
+                        conditions: RawLootCondition::resolve_all(pool.conditions, registry)?,
+                        functions: RawLootFunction::resolve_all(pool.functions, registry)?,
+                        rolls: pool.rolls,
+                        bonus_rolls: pool.bonus_rolls,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            functions: RawLootFunction::resolve_all(self.functions, registry)?,
+        })
+    }
+}
+
+/// [`DeserializeSeed`] of [`LootTable`]s, resolving items of entries and
+/// conditions through the given registry.
+pub struct LootTableSeed<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+}
+
+impl<'r, Cx> LootTableSeed<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    /// Creates a seed resolving items through the given registry.
+    #[inline]
+    pub const fn new(registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'r, Cx> Clone for LootTableSeed<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'r, Cx> Copy for LootTableSeed<'r, Cx> where Cx: LootCx<'r> {}
+
+impl<'r, Cx> Debug for LootTableSeed<'r, Cx>
+where
+    Cx: LootCx<'r>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LootTableSeed").finish_non_exhaustive()
+    }
+}
+
+impl<'r, 'de, Cx> DeserializeSeed<'de> for LootTableSeed<'r, Cx>
+where
+    Cx: LootCx<'r, Id: FromStr<Err: Display> + Clone>,
+    ComponentChanges<'r, 'r, Cx>: Deserialize<'de>,
+{
+    type Value = LootTable<'r, Cx>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        RawLootTable::deserialize(deserializer)?
+            .resolve(self.registry)
+            .map_err(serde::de::Error::custom)
+    }
+}
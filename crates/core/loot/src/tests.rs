@@ -0,0 +1,339 @@
+use rand::{rngs::StdRng, SeedableRng};
+use rimecraft_item::{
+    component::map::ComponentMap,
+    test_global_integration::{component_type, id, init_registries, item, key, registry, Settings},
+    Damage, ItemStack, MaxDamage, MaxStackSize, RawItem,
+};
+use rimecraft_registry::{entry::RefEntry, tag::TagKey, Reg};
+use serde::de::DeserializeSeed;
+use test_global::TestContext;
+
+use crate::{LootContext, LootTable, LootTableSeed};
+
+fn init() {
+    init_registries(|items| {
+        let settings = |max: u32| {
+            let mut builder = ComponentMap::builder();
+            builder.insert(component_type("max_stack_size"), MaxStackSize(max));
+            Settings(builder.build())
+        };
+        items
+            .register_default(key("air"), RawItem::new(settings(64)))
+            .unwrap();
+        for (path, max) in [
+            ("cobblestone", 64),
+            ("diamond", 64),
+            ("emerald", 64),
+            ("oak_sapling", 64),
+            ("birch_sapling", 64),
+            ("ender_pearl", 16),
+        ] {
+            items
+                .register(key(path), RawItem::new(settings(max)))
+                .unwrap();
+        }
+        for path in ["shears", "iron_sword"] {
+            let mut builder = ComponentMap::builder();
+            builder.insert(component_type("damage"), Damage(0));
+            builder.insert(component_type("max_damage"), MaxDamage(200));
+            items
+                .register(key(path), RawItem::new(Settings(builder.build())))
+                .unwrap();
+        }
+    });
+    let saplings = TagKey {
+        registry: registry().key().clone(),
+        id: id("saplings"),
+    };
+    let entry = |path: &str| -> &RefEntry<_, _> { item(path).into() };
+    registry()
+        .populate_tags([(saplings, vec![entry("oak_sapling"), entry("birch_sapling")])])
+        .unwrap();
+}
+
+fn table(json: &str) -> LootTable<'static, TestContext> {
+    LootTableSeed::new(registry())
+        .deserialize(&mut serde_json::Deserializer::from_str(json))
+        .unwrap()
+}
+
+fn generate(
+    table: &LootTable<'static, TestContext>,
+    seed: u64,
+    tool: Option<&ItemStack<'static, TestContext>>,
+) -> Vec<ItemStack<'static, TestContext>> {
+    let mut context = LootContext::new(registry(), StdRng::seed_from_u64(seed));
+    if let Some(tool) = tool {
+        context = context.with_tool(tool);
+    }
+    table.generate_loot(&mut context)
+}
+
+/// Summarizes generated stacks as pairs of item identifiers and counts.
+fn summary(stacks: &[ItemStack<'static, TestContext>]) -> Vec<(String, u32)> {
+    stacks
+        .iter()
+        .map(|stack| (Reg::id(stack.item()).to_string(), stack.count()))
+        .collect()
+}
+
+const CHEST: &str = r#"{
+    "type": "minecraft:chest",
+    "pools": [
+        {
+            "rolls": { "type": "minecraft:uniform", "min": 2, "max": 4 },
+            "entries": [
+                {
+                    "type": "minecraft:item",
+                    "name": "minecraft:diamond",
+                    "weight": 5,
+                    "functions": [
+                        {
+                            "function": "minecraft:set_count",
+                            "count": { "min": 1, "max": 3 }
+                        }
+                    ]
+                },
+                { "type": "minecraft:item", "name": "minecraft:emerald", "weight": 10 },
+                { "type": "minecraft:empty", "weight": 5 }
+            ]
+        },
+        {
+            "rolls": 1,
+            "entries": [
+                {
+                    "type": "minecraft:tag",
+                    "name": "minecraft:saplings",
+                    "expand": true
+                }
+            ]
+        }
+    ]
+}"#;
+
+#[test]
+fn deterministic() {
+    init();
+    let table = table(CHEST);
+    // Pinned, as drops of a seed must not change across versions.
+    for (seed, expected) in [
+        (
+            0,
+            &[
+                ("minecraft:emerald", 1),
+                ("minecraft:emerald", 1),
+                ("minecraft:oak_sapling", 1),
+            ][..],
+        ),
+        (
+            2,
+            &[("minecraft:diamond", 2), ("minecraft:birch_sapling", 1)],
+        ),
+        (
+            21,
+            &[
+                ("minecraft:diamond", 2),
+                ("minecraft:emerald", 1),
+                ("minecraft:diamond", 3),
+                ("minecraft:emerald", 1),
+                ("minecraft:oak_sapling", 1),
+            ],
+        ),
+    ] {
+        let drops = summary(&generate(&table, seed, None));
+        assert_eq!(
+            drops
+                .iter()
+                .map(|(id, count)| (id.as_str(), *count))
+                .collect::<Vec<_>>(),
+            expected,
+            "drops of seed {seed}"
+        );
+        assert_eq!(drops, summary(&generate(&table, seed, None)));
+    }
+}
+
+#[test]
+fn tag_entry() {
+    init();
+    let all = table(
+        r#"{ "pools": [ { "rolls": 1, "entries": [ { "type": "tag", "name": "minecraft:saplings" } ] } ] }"#,
+    );
+    let mut ids: Vec<_> = summary(&generate(&all, 0, None))
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, ["minecraft:birch_sapling", "minecraft:oak_sapling"]);
+}
+
+#[test]
+fn empty_tag_alternatives() {
+    init();
+    let table = table(
+        r#"{
+            "pools": [
+                {
+                    "rolls": 1,
+                    "entries": [
+                        {
+                            "type": "alternatives",
+                            "children": [
+                                { "type": "tag", "name": "minecraft:flowers", "expand": true },
+                                { "type": "item", "name": "minecraft:diamond" }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }"#,
+    );
+    for seed in 0..8 {
+        assert_eq!(
+            summary(&generate(&table, seed, None)),
+            [("minecraft:diamond".to_owned(), 1)]
+        );
+    }
+}
+
+#[test]
+fn conditions() {
+    init();
+    let ore = table(
+        r#"{
+            "pools": [
+                {
+                    "rolls": 1,
+                    "entries": [
+                        {
+                            "type": "minecraft:alternatives",
+                            "children": [
+                                {
+                                    "type": "minecraft:item",
+                                    "name": "minecraft:emerald",
+                                    "conditions": [
+                                        {
+                                            "condition": "minecraft:match_tool",
+                                            "predicate": { "items": "minecraft:shears" }
+                                        }
+                                    ]
+                                },
+                                {
+                                    "type": "minecraft:item",
+                                    "name": "minecraft:cobblestone",
+                                    "conditions": [ { "condition": "minecraft:survives_explosion" } ]
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }"#,
+    );
+    let shears = ItemStack::new(item("shears"), 1);
+    let sword = ItemStack::new(item("iron_sword"), 1);
+    assert_eq!(
+        summary(&generate(&ore, 0, Some(&shears))),
+        [("minecraft:emerald".to_owned(), 1)]
+    );
+    assert_eq!(
+        summary(&generate(&ore, 0, Some(&sword))),
+        [("minecraft:cobblestone".to_owned(), 1)]
+    );
+    assert_eq!(
+        summary(&generate(&ore, 0, None)),
+        [("minecraft:cobblestone".to_owned(), 1)]
+    );
+
+    let mut survived = 0;
+    for seed in 0..64 {
+        let mut context =
+            LootContext::new(registry(), StdRng::seed_from_u64(seed)).with_explosion_radius(4.0);
+        survived += ore.generate_loot(&mut context).len();
+    }
+    assert!((1..64).contains(&survived));
+
+    let never = table(
+        r#"{
+            "pools": [
+                {
+                    "rolls": 8,
+                    "conditions": [ { "condition": "random_chance", "chance": 0.0 } ],
+                    "entries": [ { "type": "item", "name": "minecraft:diamond" } ]
+                }
+            ]
+        }"#,
+    );
+    assert!(generate(&never, 0, None).is_empty());
+}
+
+#[test]
+fn functions() {
+    init();
+    let loot = table(
+        r#"{
+            "pools": [
+                {
+                    "rolls": 1,
+                    "entries": [
+                        {
+                            "type": "minecraft:item",
+                            "name": "minecraft:iron_sword",
+                            "functions": [
+                                { "function": "minecraft:set_damage", "damage": 0.25 },
+                                {
+                                    "function": "minecraft:set_components",
+                                    "components": { "minecraft:max_stack_size": 4 }
+                                }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "rolls": 1,
+                    "entries": [ { "type": "minecraft:item", "name": "minecraft:ender_pearl" } ],
+                    "functions": [ { "function": "minecraft:set_count", "count": 40 } ]
+                }
+            ]
+        }"#,
+    );
+    let stacks = generate(&loot, 0, None);
+    // Counts are not clamped, so stacks exceeding their max count are split.
+    assert_eq!(
+        summary(&stacks),
+        [
+            ("minecraft:iron_sword".to_owned(), 1),
+            ("minecraft:ender_pearl".to_owned(), 16),
+            ("minecraft:ender_pearl".to_owned(), 16),
+            ("minecraft:ender_pearl".to_owned(), 8),
+        ]
+    );
+    assert_eq!(stacks[0].current_damage(), 150);
+    assert_eq!(stacks[0].max_count(), 4);
+
+    let added = table(
+        r#"{
+            "pools": [ { "rolls": 1, "entries": [ { "type": "item", "name": "minecraft:diamond" } ] } ],
+            "functions": [
+                { "function": "minecraft:set_count", "count": 50 },
+                { "function": "minecraft:set_count", "count": 30, "add": true },
+                { "function": "minecraft:set_count", "count": -10, "add": true }
+            ]
+        }"#,
+    );
+    assert_eq!(
+        summary(&generate(&added, 0, None)),
+        [
+            ("minecraft:diamond".to_owned(), 64),
+            ("minecraft:diamond".to_owned(), 6),
+        ]
+    );
+
+    let removed = table(
+        r#"{
+            "pools": [ { "rolls": 1, "entries": [ { "type": "item", "name": "minecraft:diamond" } ] } ],
+            "functions": [ { "function": "minecraft:set_count", "count": -1, "add": true } ]
+        }"#,
+    );
+    assert!(generate(&removed, 0, None).is_empty());
+}
@@ -0,0 +1,180 @@
+use std::{fmt::Debug, str::FromStr};
+
+use rimecraft_item::{ItemStack, RawItem};
+use rimecraft_registry::Registry;
+use serde::Deserialize;
+
+use crate::{ingredient::RawIngredient, ErasedRecipe, Ingredient, Recipe, RecipeCx, RecipeType};
+
+/// A cooking recipe, turning a single stack into the result in a furnace-like
+/// block or a campfire.
+///
+/// # MCJE Reference
+///
+/// This represents `AbstractCookingRecipe` (yarn).
+pub struct CookingRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    ty: RecipeType,
+    group: String,
+    ingredient: Ingredient<'r, Cx>,
+    result: ItemStack<'r, Cx>,
+    experience: f32,
+    cooking_time: u32,
+}
+
+impl<'r, Cx> CookingRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Creates a new cooking recipe of given type, using the default cooking
+    /// time of the type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given type is not a cooking type.
+    pub fn new(ty: RecipeType, ingredient: Ingredient<'r, Cx>, result: ItemStack<'r, Cx>) -> Self {
+        assert!(ty.is_cooking(), "{ty:?} is not a cooking recipe type");
+        Self {
+            ty,
+            group: String::new(),
+            ingredient,
+            result,
+            experience: 0.0,
+            cooking_time: ty.default_cooking_time(),
+        }
+    }
+
+    /// Sets the group of this recipe.
+    #[inline]
+    pub fn with_group(self, group: String) -> Self {
+        Self { group, ..self }
+    }
+
+    /// Sets the experience rewarded by this recipe.
+    #[inline]
+    pub fn with_experience(self, experience: f32) -> Self {
+        Self { experience, ..self }
+    }
+
+    /// Sets the cooking time of this recipe, in ticks.
+    #[inline]
+    pub fn with_cooking_time(self, cooking_time: u32) -> Self {
+        Self {
+            cooking_time,
+            ..self
+        }
+    }
+
+    /// Returns the ingredient of this recipe.
+    #[inline]
+    pub fn ingredient(&self) -> &Ingredient<'r, Cx> {
+        &self.ingredient
+    }
+
+    /// Returns the experience rewarded by this recipe.
+    #[inline]
+    pub fn experience(&self) -> f32 {
+        self.experience
+    }
+
+    /// Returns the cooking time of this recipe, in ticks.
+    #[inline]
+    pub fn cooking_time(&self) -> u32 {
+        self.cooking_time
+    }
+}
+
+impl<'r, Cx> Recipe<'r, Cx, ItemStack<'r, Cx>> for CookingRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[inline]
+    fn matches(&self, input: &ItemStack<'r, Cx>) -> bool {
+        self.ingredient.test(input)
+    }
+
+    #[inline]
+    fn result(&self) -> &ItemStack<'r, Cx> {
+        &self.result
+    }
+
+    #[inline]
+    fn recipe_type(&self) -> RecipeType {
+        self.ty
+    }
+
+    #[inline]
+    fn group(&self) -> &str {
+        &self.group
+    }
+}
+
+impl<'r, Cx> ErasedRecipe<'r, Cx> for CookingRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[inline]
+    fn as_cooking(&self) -> Option<&CookingRecipe<'r, Cx>> {
+        Some(self)
+    }
+}
+
+impl<'r, Cx> Debug for CookingRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CookingRecipe")
+            .field("type", &self.ty)
+            .field("group", &self.group)
+            .field("ingredient", &self.ingredient)
+            .field("result", &self.result)
+            .field("experience", &self.experience)
+            .field("cooking_time", &self.cooking_time)
+            .finish()
+    }
+}
+
+/// Data pack representation of cooking recipes, whose type is given by the
+/// outer tag.
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "ItemStack<'r, Cx>: Deserialize<'de>"))]
+pub(crate) struct RawCookingRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[serde(default)]
+    group: String,
+    ingredient: RawIngredient,
+    result: ItemStack<'r, Cx>,
+    #[serde(default)]
+    experience: f32,
+    #[serde(default, rename = "cookingtime")]
+    cooking_time: Option<u32>,
+}
+
+impl<'r, Cx> RawCookingRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: FromStr<Err: std::fmt::Display> + Clone>,
+{
+    /// Resolves the ingredient through the given item registry into a recipe
+    /// of given type.
+    pub(crate) fn resolve(
+        self,
+        ty: RecipeType,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<CookingRecipe<'r, Cx>, String> {
+        Ok(CookingRecipe {
+            ty,
+            group: self.group,
+            ingredient: self.ingredient.resolve(registry)?,
+            result: self.result,
+            experience: self.experience,
+            cooking_time: self
+                .cooking_time
+                .unwrap_or_else(|| ty.default_cooking_time()),
+        })
+    }
+}
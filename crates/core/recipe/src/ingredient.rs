@@ -0,0 +1,266 @@
+use std::{fmt::Debug, str::FromStr};
+
+use rimecraft_item::{Item, ItemStack, RawItem};
+use rimecraft_registry::{entry::RefEntry, tag::TagKey, Registry};
+use serde::{de::DeserializeSeed, Deserialize};
+
+use crate::RecipeCx;
+
+/// An entry of an [`Ingredient`], matching either an item or items of a tag.
+#[non_exhaustive]
+pub enum IngredientEntry<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Matches the given item.
+    Item(Item<'r, Cx>),
+    /// Matches items of the given tag.
+    Tag(TagKey<Cx::Id, RawItem<'r, Cx>>),
+}
+
+impl<'r, Cx> IngredientEntry<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Whether the given item matches this entry.
+    pub fn test(&self, item: Item<'r, Cx>) -> bool {
+        match self {
+            Self::Item(i) => *i == item,
+            Self::Tag(tag) => {
+                let entry: &RefEntry<_, _> = item.as_ref();
+                entry.tags().contains(tag)
+            }
+        }
+    }
+}
+
+impl<'r, Cx> Clone for IngredientEntry<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Clone>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            Self::Item(item) => Self::Item(*item),
+            Self::Tag(tag) => Self::Tag(tag.clone()),
+        }
+    }
+}
+
+impl<'r, Cx> Debug for IngredientEntry<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Debug>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Item(item) => f.debug_tuple("Item").field(item).finish(),
+            Self::Tag(tag) => f.debug_tuple("Tag").field(tag).finish(),
+        }
+    }
+}
+
+/// An ingredient of a recipe, matching non-empty item stacks whose item
+/// matches any of its entries.
+///
+/// # Serialization
+///
+/// Ingredients are deserialized through [`IngredientSeed`] from the data pack
+/// format: an item id, a tag id prefixed with `#`, an object with either an
+/// `item` or a `tag` field, or a list of them.
+///
+/// # MCJE Reference
+///
+/// This represents `Ingredient` (yarn).
+pub struct Ingredient<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    entries: Vec<IngredientEntry<'r, Cx>>,
+}
+
+impl<'r, Cx> Ingredient<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Creates an ingredient from given entries.
+    #[inline]
+    pub fn new(entries: Vec<IngredientEntry<'r, Cx>>) -> Self {
+        Self { entries }
+    }
+
+    /// Creates an ingredient matching any of given items.
+    pub fn of_items<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Item<'r, Cx>>,
+    {
+        Self::new(items.into_iter().map(IngredientEntry::Item).collect())
+    }
+
+    /// Creates an ingredient matching items of given tag.
+    #[inline]
+    pub fn of_tag(tag: TagKey<Cx::Id, RawItem<'r, Cx>>) -> Self {
+        Self::new(vec![IngredientEntry::Tag(tag)])
+    }
+
+    /// Returns the entries of this ingredient.
+    #[inline]
+    pub fn entries(&self) -> &[IngredientEntry<'r, Cx>] {
+        &self.entries
+    }
+
+    /// Whether the given stack matches this ingredient.
+    ///
+    /// Empty stacks never match.
+    pub fn test(&self, stack: &ItemStack<'r, Cx>) -> bool {
+        !stack.is_empty() && self.entries.iter().any(|entry| entry.test(stack.item()))
+    }
+}
+
+impl<'r, Cx> Clone for Ingredient<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Clone>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<'r, Cx> Debug for Ingredient<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Debug>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&self.entries).finish()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum RawEntry {
+    Id(String),
+    Item { item: String },
+    Tag { tag: String },
+}
+
+/// Data pack representation of ingredients, whose items are not resolved yet.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum RawIngredient {
+    One(RawEntry),
+    Many(Vec<RawEntry>),
+}
+
+impl<'r, Cx> IngredientEntry<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: FromStr<Err: std::fmt::Display> + Clone>,
+{
+    fn resolve(
+        raw: RawEntry,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<Self, String> {
+        let (id, is_tag) = match raw {
+            RawEntry::Id(id) => match id.strip_prefix('#') {
+                Some(tag) => (tag.to_owned(), true),
+                None => (id, false),
+            },
+            RawEntry::Item { item } => (item, false),
+            RawEntry::Tag { tag } => (tag, true),
+        };
+        let parsed: Cx::Id = id
+            .parse()
+            .map_err(|err| format!("invalid identifier {id}: {err}"))?;
+        if is_tag {
+            Ok(Self::Tag(TagKey {
+                registry: registry.key().clone(),
+                id: parsed,
+            }))
+        } else {
+            registry
+                .get(&parsed)
+                .map(Self::Item)
+                .ok_or_else(|| format!("unknown item {id}"))
+        }
+    }
+}
+
+impl RawIngredient {
+    /// Resolves items of this ingredient through the given registry.
+    pub(crate) fn resolve<'r, Cx>(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<Ingredient<'r, Cx>, String>
+    where
+        Cx: RecipeCx<'r, Id: FromStr<Err: std::fmt::Display> + Clone>,
+    {
+        let raw = match self {
+            Self::One(entry) => vec![entry],
+            Self::Many(entries) => entries,
+        };
+        if raw.is_empty() {
+            return Err("empty ingredient".to_owned());
+        }
+        raw.into_iter()
+            .map(|entry| IngredientEntry::resolve(entry, registry))
+            .collect::<Result<_, _>>()
+            .map(Ingredient::new)
+    }
+}
+
+/// [`DeserializeSeed`] of ingredients, resolving items through the given registry.
+pub struct IngredientSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+}
+
+impl<'r, Cx> IngredientSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Creates a seed resolving items through the given registry.
+    #[inline]
+    pub const fn new(registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'r, Cx> Clone for IngredientSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'r, Cx> Copy for IngredientSeed<'r, Cx> where Cx: RecipeCx<'r> {}
+
+impl<'r, Cx> Debug for IngredientSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IngredientSeed").finish_non_exhaustive()
+    }
+}
+
+impl<'r, 'de, Cx> DeserializeSeed<'de> for IngredientSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: FromStr<Err: std::fmt::Display> + Clone>,
+{
+    type Value = Ingredient<'r, Cx>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        RawIngredient::deserialize(deserializer)?
+            .resolve(self.registry)
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::Debug;
+
+use rimecraft_item::ItemStack;
+
+use crate::RecipeCx;
+
+/// Input of crafting recipes, which is a grid of item stacks trimmed to the
+/// bounding box of its non-empty stacks.
+///
+/// # MCJE Reference
+///
+/// This represents `CraftingRecipeInput` (yarn).
+pub struct CraftingInput<'a, 'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    width: usize,
+    height: usize,
+    stacks: Vec<&'a ItemStack<'r, Cx>>,
+    non_empty: usize,
+}
+
+impl<'a, 'r, Cx> CraftingInput<'a, 'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Creates a crafting input from a row-major grid of stacks with given
+    /// width and height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count of stacks does not equal to `width * height`.
+    pub fn new(width: usize, height: usize, stacks: &'a [ItemStack<'r, Cx>]) -> Self {
+        assert_eq!(
+            stacks.len(),
+            width * height,
+            "the count of stacks should match the size of the grid"
+        );
+
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (width, height, 0, 0);
+        let mut non_empty = 0;
+        for y in 0..height {
+            for x in 0..width {
+                if !stacks[y * width + x].is_empty() {
+                    non_empty += 1;
+                    min_x = min_x.min(x);
+                    min_y = min_y.min(y);
+                    max_x = max_x.max(x + 1);
+                    max_y = max_y.max(y + 1);
+                }
+            }
+        }
+
+        if non_empty == 0 {
+            return Self {
+                width: 0,
+                height: 0,
+                stacks: Vec::new(),
+                non_empty,
+            };
+        }
+        Self {
+            width: max_x - min_x,
+            height: max_y - min_y,
+            stacks: (min_y..max_y)
+                .flat_map(|y| &stacks[y * width + min_x..y * width + max_x])
+                .collect(),
+            non_empty,
+        }
+    }
+
+    /// Returns the width of the trimmed grid.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the trimmed grid.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the stack at given position of the trimmed grid.
+    #[inline]
+    pub fn stack(&self, x: usize, y: usize) -> Option<&'a ItemStack<'r, Cx>> {
+        (x < self.width && y < self.height).then(|| self.stacks[y * self.width + x])
+    }
+
+    /// Returns the stacks of the trimmed grid in row-major order.
+    #[inline]
+    pub fn stacks(&self) -> &[&'a ItemStack<'r, Cx>] {
+        &self.stacks
+    }
+
+    /// Returns the count of non-empty stacks.
+    #[inline]
+    pub fn non_empty_count(&self) -> usize {
+        self.non_empty
+    }
+
+    /// Whether all stacks of this input are empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.non_empty == 0
+    }
+}
+
+impl<'r, Cx> Debug for CraftingInput<'_, 'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CraftingInput")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("stacks", &self.stacks)
+            .finish()
+    }
+}
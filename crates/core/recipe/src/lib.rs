@@ -0,0 +1,132 @@
+//! Minecraft recipe primitives.
+
+use rimecraft_item::{stack::ItemStackCx, ItemStack};
+
+mod cooking;
+mod ingredient;
+mod input;
+mod manager;
+mod shaped;
+mod shapeless;
+
+pub use cooking::CookingRecipe;
+pub use ingredient::{Ingredient, IngredientEntry, IngredientSeed};
+pub use input::CraftingInput;
+pub use manager::{RecipeManager, RecipeSeed, VanillaRecipe};
+pub use shaped::ShapedRecipe;
+pub use shapeless::ShapelessRecipe;
+
+/// Global context used for recipes.
+pub trait RecipeCx<'r>: ItemStackCx<Id: std::hash::Hash + Eq> {}
+
+impl<T> RecipeCx<'_> for T where T: ItemStackCx<Id: std::hash::Hash + Eq> {}
+
+/// A recipe crafting an item stack from an input of type `I`.
+///
+/// # MCJE Reference
+///
+/// This represents `Recipe` (yarn).
+pub trait Recipe<'r, Cx, I: ?Sized>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Whether the given input matches this recipe.
+    fn matches(&self, input: &I) -> bool;
+
+    /// Crafts the output stack from the given input, which should match this recipe.
+    #[inline]
+    fn craft(&self, input: &I) -> ItemStack<'r, Cx> {
+        let _ = input;
+        self.result().clone()
+    }
+
+    /// Returns the result of this recipe, including its count and components.
+    fn result(&self) -> &ItemStack<'r, Cx>;
+
+    /// Returns the type of this recipe.
+    fn recipe_type(&self) -> RecipeType;
+
+    /// Returns the group of this recipe, which groups similar recipes in the recipe book.
+    #[inline]
+    fn group(&self) -> &str {
+        ""
+    }
+}
+
+/// A recipe of any input, as stored in a [`RecipeManager`].
+///
+/// Custom recipes implement this trait to be inserted into a manager along
+/// with the vanilla ones. A recipe which is neither a crafting nor a cooking
+/// recipe never matches.
+pub trait ErasedRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Returns this recipe as a crafting recipe, or `None` if this is not
+    /// a crafting recipe.
+    #[inline]
+    fn as_crafting(&self) -> Option<&DynCraftingRecipe<'r, Cx>> {
+        None
+    }
+
+    /// Returns this recipe as a cooking recipe, or `None` if this is not
+    /// a cooking recipe.
+    ///
+    /// Cooking recipes are plain data, so custom cooking recipes are
+    /// expressed as [`CookingRecipe`]s.
+    #[inline]
+    fn as_cooking(&self) -> Option<&CookingRecipe<'r, Cx>> {
+        None
+    }
+}
+
+/// A crafting recipe as a trait object.
+///
+/// # MCJE Reference
+///
+/// This represents `CraftingRecipe` (yarn).
+pub type DynCraftingRecipe<'r, Cx> = dyn for<'a> Recipe<'r, Cx, CraftingInput<'a, 'r, Cx>> + 'r;
+
+/// A boxed [`ErasedRecipe`].
+pub type DynRecipe<'r, Cx> = Box<dyn ErasedRecipe<'r, Cx> + Send + Sync + 'r>;
+
+/// Type of a recipe.
+///
+/// # MCJE Reference
+///
+/// This represents `RecipeType` (yarn).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RecipeType {
+    /// Recipes of crafting tables and the inventory crafting grid.
+    Crafting,
+    /// Recipes of furnaces.
+    Smelting,
+    /// Recipes of blast furnaces.
+    Blasting,
+    /// Recipes of smokers.
+    Smoking,
+    /// Recipes of campfires.
+    CampfireCooking,
+}
+
+impl RecipeType {
+    /// Whether this is a type of cooking recipes.
+    #[inline]
+    pub const fn is_cooking(self) -> bool {
+        !matches!(self, Self::Crafting)
+    }
+
+    /// Returns the default cooking time of this type in ticks, or `0` if
+    /// this is not a type of cooking recipes.
+    pub const fn default_cooking_time(self) -> u32 {
+        match self {
+            Self::Crafting => 0,
+            Self::Smelting => 200,
+            Self::Blasting | Self::Smoking | Self::CampfireCooking => 100,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
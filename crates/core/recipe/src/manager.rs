@@ -0,0 +1,326 @@
+use std::{collections::HashMap, fmt::Debug, str::FromStr};
+
+use rimecraft_item::{ItemStack, RawItem};
+use rimecraft_registry::Registry;
+use serde::{de::DeserializeSeed, Deserialize};
+
+use crate::{
+    cooking::RawCookingRecipe, shaped::RawShapedRecipe, shapeless::RawShapelessRecipe,
+    CookingRecipe, CraftingInput, DynCraftingRecipe, DynRecipe, ErasedRecipe, Recipe, RecipeCx,
+    RecipeType, ShapedRecipe, ShapelessRecipe,
+};
+
+/// A recipe of the vanilla recipe serializers.
+///
+/// # Serialization
+///
+/// This type is deserialized through [`RecipeSeed`] from the data pack
+/// format, dispatched by the
+/// `type` field, which is one of `minecraft:crafting_shaped`,
+/// `minecraft:crafting_shapeless`, `minecraft:smelting`, `minecraft:blasting`,
+/// `minecraft:smoking` and `minecraft:campfire_cooking`. The namespace may be
+/// omitted.
+#[non_exhaustive]
+pub enum VanillaRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// A shaped crafting recipe.
+    Shaped(ShapedRecipe<'r, Cx>),
+    /// A shapeless crafting recipe.
+    Shapeless(ShapelessRecipe<'r, Cx>),
+    /// A cooking recipe.
+    Cooking(CookingRecipe<'r, Cx>),
+}
+
+impl<'r, Cx> ErasedRecipe<'r, Cx> for VanillaRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    fn as_crafting(&self) -> Option<&DynCraftingRecipe<'r, Cx>> {
+        match self {
+            Self::Shaped(recipe) => recipe.as_crafting(),
+            Self::Shapeless(recipe) => recipe.as_crafting(),
+            Self::Cooking(recipe) => recipe.as_crafting(),
+        }
+    }
+
+    fn as_cooking(&self) -> Option<&CookingRecipe<'r, Cx>> {
+        match self {
+            Self::Shaped(recipe) => recipe.as_cooking(),
+            Self::Shapeless(recipe) => recipe.as_cooking(),
+            Self::Cooking(recipe) => recipe.as_cooking(),
+        }
+    }
+}
+
+impl<'r, Cx> Debug for VanillaRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shaped(recipe) => f.debug_tuple("Shaped").field(recipe).finish(),
+            Self::Shapeless(recipe) => f.debug_tuple("Shapeless").field(recipe).finish(),
+            Self::Cooking(recipe) => f.debug_tuple("Cooking").field(recipe).finish(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(
+    tag = "type",
+    bound(deserialize = "ItemStack<'r, Cx>: Deserialize<'de>")
+)]
+enum RawVanillaRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[serde(rename = "minecraft:crafting_shaped", alias = "crafting_shaped")]
+    Shaped(RawShapedRecipe<'r, Cx>),
+    #[serde(rename = "minecraft:crafting_shapeless", alias = "crafting_shapeless")]
+    Shapeless(RawShapelessRecipe<'r, Cx>),
+    #[serde(rename = "minecraft:smelting", alias = "smelting")]
+    Smelting(RawCookingRecipe<'r, Cx>),
+    #[serde(rename = "minecraft:blasting", alias = "blasting")]
+    Blasting(RawCookingRecipe<'r, Cx>),
+    #[serde(rename = "minecraft:smoking", alias = "smoking")]
+    Smoking(RawCookingRecipe<'r, Cx>),
+    #[serde(rename = "minecraft:campfire_cooking", alias = "campfire_cooking")]
+    CampfireCooking(RawCookingRecipe<'r, Cx>),
+}
+
+/// [`DeserializeSeed`] of [`VanillaRecipe`]s, resolving items of ingredients
+/// through the given registry.
+pub struct RecipeSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+}
+
+impl<'r, Cx> RecipeSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Creates a seed resolving items through the given registry.
+    #[inline]
+    pub const fn new(registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'r, Cx> Clone for RecipeSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'r, Cx> Copy for RecipeSeed<'r, Cx> where Cx: RecipeCx<'r> {}
+
+impl<'r, Cx> Debug for RecipeSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecipeSeed").finish_non_exhaustive()
+    }
+}
+
+impl<'r, 'de, Cx> DeserializeSeed<'de> for RecipeSeed<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: FromStr<Err: std::fmt::Display> + Clone>,
+    ItemStack<'r, Cx>: Deserialize<'de>,
+{
+    type Value = VanillaRecipe<'r, Cx>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let registry = self.registry;
+        match RawVanillaRecipe::deserialize(deserializer)? {
+            RawVanillaRecipe::Shaped(raw) => raw.resolve(registry).map(VanillaRecipe::Shaped),
+            RawVanillaRecipe::Shapeless(raw) => raw.resolve(registry).map(VanillaRecipe::Shapeless),
+            RawVanillaRecipe::Smelting(raw) => raw
+                .resolve(RecipeType::Smelting, registry)
+                .map(VanillaRecipe::Cooking),
+            RawVanillaRecipe::Blasting(raw) => raw
+                .resolve(RecipeType::Blasting, registry)
+                .map(VanillaRecipe::Cooking),
+            RawVanillaRecipe::Smoking(raw) => raw
+                .resolve(RecipeType::Smoking, registry)
+                .map(VanillaRecipe::Cooking),
+            RawVanillaRecipe::CampfireCooking(raw) => raw
+                .resolve(RecipeType::CampfireCooking, registry)
+                .map(VanillaRecipe::Cooking),
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Manager of loaded recipes, keyed by their identifiers.
+///
+/// Recipes are matched in the order they were first inserted. Besides the
+/// [`VanillaRecipe`]s loaded from data packs, any [`ErasedRecipe`] could be
+/// inserted.
+///
+/// # MCJE Reference
+///
+/// This represents `RecipeManager` (yarn).
+pub struct RecipeManager<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    recipes: Vec<(Cx::Id, DynRecipe<'r, Cx>)>,
+    indices: HashMap<Cx::Id, usize>,
+}
+
+impl<'r, Cx> RecipeManager<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Creates an empty recipe manager.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            recipes: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Inserts a recipe with given identifier, returning the recipe previously
+    /// registered with the identifier.
+    ///
+    /// A replaced recipe keeps its matching priority.
+    pub fn insert<R>(&mut self, id: Cx::Id, recipe: R) -> Option<DynRecipe<'r, Cx>>
+    where
+        R: ErasedRecipe<'r, Cx> + Send + Sync + 'r,
+        Cx::Id: Clone,
+    {
+        let recipe: DynRecipe<'r, Cx> = Box::new(recipe);
+        if let Some(&index) = self.indices.get(&id) {
+            Some(std::mem::replace(&mut self.recipes[index].1, recipe))
+        } else {
+            self.indices.insert(id.clone(), self.recipes.len());
+            self.recipes.push((id, recipe));
+            None
+        }
+    }
+
+    /// Deserializes a [`VanillaRecipe`] from the given deserializer, resolving
+    /// items through the given registry, and inserts it with given identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the recipe failed to deserialize.
+    pub fn load<'de, D>(
+        &mut self,
+        id: Cx::Id,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+        deserializer: D,
+    ) -> Result<(), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        RecipeSeed<'r, Cx>: DeserializeSeed<'de, Value = VanillaRecipe<'r, Cx>>,
+        VanillaRecipe<'r, Cx>: Send + Sync + 'r,
+        Cx::Id: Clone,
+    {
+        let recipe = RecipeSeed::new(registry).deserialize(deserializer)?;
+        self.insert(id, recipe);
+        Ok(())
+    }
+
+    /// Returns the recipe with given identifier.
+    #[inline]
+    pub fn get(&self, id: &Cx::Id) -> Option<&dyn ErasedRecipe<'r, Cx>> {
+        self.indices
+            .get(id)
+            .map(|&index| &*self.recipes[index].1 as _)
+    }
+
+    /// Returns the count of recipes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.recipes.len()
+    }
+
+    /// Whether there are no recipes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.recipes.is_empty()
+    }
+
+    /// Returns an iterator over identifiers and recipes, in matching order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&Cx::Id, &dyn ErasedRecipe<'r, Cx>)> {
+        self.recipes
+            .iter()
+            .map(|(id, recipe)| (id, &**recipe as &dyn ErasedRecipe<'r, Cx>))
+    }
+
+    /// Returns the first crafting recipe matching the given input along with
+    /// its identifier.
+    pub fn first_crafting_match(
+        &self,
+        input: &CraftingInput<'_, 'r, Cx>,
+    ) -> Option<(&Cx::Id, &DynCraftingRecipe<'r, Cx>)> {
+        if input.is_empty() {
+            return None;
+        }
+        self.recipes.iter().find_map(|(id, recipe)| {
+            recipe
+                .as_crafting()
+                .filter(|recipe| recipe.matches(input))
+                .map(|recipe| (id, recipe))
+        })
+    }
+
+    /// Crafts the output stack from the given input with the first matching
+    /// crafting recipe.
+    #[inline]
+    pub fn craft(&self, input: &CraftingInput<'_, 'r, Cx>) -> Option<ItemStack<'r, Cx>> {
+        self.first_crafting_match(input)
+            .map(|(_, recipe)| recipe.craft(input))
+    }
+
+    /// Returns the first cooking recipe of given type matching the given stack
+    /// along with its identifier.
+    pub fn first_cooking_match(
+        &self,
+        ty: RecipeType,
+        input: &ItemStack<'r, Cx>,
+    ) -> Option<(&Cx::Id, &CookingRecipe<'r, Cx>)> {
+        self.recipes.iter().find_map(|(id, recipe)| {
+            recipe
+                .as_cooking()
+                .filter(|recipe| recipe.recipe_type() == ty && recipe.matches(input))
+                .map(|recipe| (id, recipe))
+        })
+    }
+}
+
+impl<'r, Cx> Default for RecipeManager<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'r, Cx> Debug for RecipeManager<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Debug>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.recipes.iter().map(|(id, _)| id))
+            .finish()
+    }
+}
@@ -0,0 +1,307 @@
+use std::{collections::HashMap, fmt::Debug, str::FromStr};
+
+use rimecraft_item::{ItemStack, RawItem};
+use rimecraft_registry::Registry;
+use serde::Deserialize;
+
+use crate::{
+    ingredient::RawIngredient, CraftingInput, DynCraftingRecipe, ErasedRecipe, Ingredient, Recipe,
+    RecipeCx, RecipeType,
+};
+
+/// The max width and height of patterns of shaped recipes.
+const MAX_SIZE: usize = 3;
+
+/// A crafting recipe requiring its ingredients to be placed in a pattern.
+///
+/// Empty slots of the pattern require empty stacks. The pattern also matches
+/// its horizontally mirrored variant.
+///
+/// # Serialization
+///
+/// This type is deserialized as a [`VanillaRecipe`](crate::VanillaRecipe)
+/// through [`RecipeSeed`](crate::RecipeSeed) from the data pack format, with
+/// a `pattern` of rows and a `key` mapping symbols to ingredients, where spaces
+/// stand for empty slots.
+///
+/// # MCJE Reference
+///
+/// This represents `ShapedRecipe` (yarn).
+pub struct ShapedRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    group: String,
+    width: usize,
+    height: usize,
+    ingredients: Vec<Option<Ingredient<'r, Cx>>>,
+    result: ItemStack<'r, Cx>,
+    show_notification: bool,
+}
+
+impl<'r, Cx> ShapedRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Creates a new shaped recipe with a row-major pattern of ingredients
+    /// with given width and height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count of ingredients does not equal to `width * height`.
+    pub fn new(
+        width: usize,
+        height: usize,
+        ingredients: Vec<Option<Ingredient<'r, Cx>>>,
+        result: ItemStack<'r, Cx>,
+    ) -> Self {
+        assert_eq!(
+            ingredients.len(),
+            width * height,
+            "the count of ingredients should match the size of the pattern"
+        );
+        Self {
+            group: String::new(),
+            width,
+            height,
+            ingredients,
+            result,
+            show_notification: true,
+        }
+    }
+
+    /// Sets the group of this recipe.
+    #[inline]
+    pub fn with_group(self, group: String) -> Self {
+        Self { group, ..self }
+    }
+
+    /// Returns the width of the pattern.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the pattern.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the row-major pattern of ingredients, where `None` stands for
+    /// empty slots.
+    #[inline]
+    pub fn ingredients(&self) -> &[Option<Ingredient<'r, Cx>>] {
+        &self.ingredients
+    }
+
+    /// Whether unlocking this recipe shows a notification.
+    #[inline]
+    pub fn show_notification(&self) -> bool {
+        self.show_notification
+    }
+
+    fn matches_oriented(&self, input: &CraftingInput<'_, 'r, Cx>, mirrored: bool) -> bool {
+        (0..self.height).all(|y| {
+            (0..self.width).all(|x| {
+                let px = if mirrored { self.width - 1 - x } else { x };
+                let Some(stack) = input.stack(x, y) else {
+                    return false;
+                };
+                match &self.ingredients[y * self.width + px] {
+                    Some(ingredient) => ingredient.test(stack),
+                    None => stack.is_empty(),
+                }
+            })
+        })
+    }
+}
+
+impl<'r, Cx> Recipe<'r, Cx, CraftingInput<'_, 'r, Cx>> for ShapedRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    fn matches(&self, input: &CraftingInput<'_, 'r, Cx>) -> bool {
+        input.width() == self.width
+            && input.height() == self.height
+            && (self.matches_oriented(input, false) || self.matches_oriented(input, true))
+    }
+
+    #[inline]
+    fn result(&self) -> &ItemStack<'r, Cx> {
+        &self.result
+    }
+
+    #[inline]
+    fn recipe_type(&self) -> RecipeType {
+        RecipeType::Crafting
+    }
+
+    #[inline]
+    fn group(&self) -> &str {
+        &self.group
+    }
+}
+
+impl<'r, Cx> ErasedRecipe<'r, Cx> for ShapedRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[inline]
+    fn as_crafting(&self) -> Option<&DynCraftingRecipe<'r, Cx>> {
+        Some(self)
+    }
+}
+
+impl<'r, Cx> Debug for ShapedRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShapedRecipe")
+            .field("group", &self.group)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("ingredients", &self.ingredients)
+            .field("result", &self.result)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "ItemStack<'r, Cx>: Deserialize<'de>"))]
+pub(crate) struct RawShapedRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[serde(default)]
+    group: String,
+    pattern: Vec<String>,
+    key: HashMap<String, RawIngredient>,
+    result: ItemStack<'r, Cx>,
+    #[serde(default = "default_show_notification")]
+    show_notification: bool,
+}
+
+#[inline]
+const fn default_show_notification() -> bool {
+    true
+}
+
+/// Trims rows and columns of spaces around the given pattern.
+///
+/// Columns are counted in characters, as symbols of the key are not
+/// necessarily ASCII.
+fn remove_padding(pattern: &[String]) -> Vec<&str> {
+    let is_blank = |row: &&String| row.chars().all(|c| c == ' ');
+    let Some(first) = pattern.iter().position(|row| !is_blank(&row)) else {
+        return Vec::new();
+    };
+    let last = pattern
+        .iter()
+        .rposition(|row| !is_blank(&row))
+        .unwrap_or(first);
+    let rows = &pattern[first..=last];
+    let start = rows
+        .iter()
+        .filter_map(|row| row.chars().position(|c| c != ' '))
+        .min()
+        .unwrap_or(0);
+    let end = rows
+        .iter()
+        .filter_map(|row| {
+            row.chars()
+                .rev()
+                .position(|c| c != ' ')
+                .map(|i| row.chars().count() - i)
+        })
+        .max()
+        .unwrap_or(start);
+    let offset = |row: &str, column: usize| {
+        row.char_indices()
+            .nth(column)
+            .map_or(row.len(), |(offset, _)| offset)
+    };
+    rows.iter()
+        .map(|row| &row[offset(row, start)..offset(row, end)])
+        .collect()
+}
+
+impl<'r, Cx> RawShapedRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: FromStr<Err: std::fmt::Display> + Clone>,
+{
+    /// Validates the pattern and resolves the ingredients through the given
+    /// item registry.
+    pub(crate) fn resolve(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<ShapedRecipe<'r, Cx>, String> {
+        if self.pattern.is_empty() || self.pattern.len() > MAX_SIZE {
+            return Err(format!(
+                "invalid pattern: expected 1 to {MAX_SIZE} rows, got {}",
+                self.pattern.len()
+            ));
+        }
+        let width = self.pattern[0].chars().count();
+        if width == 0 || width > MAX_SIZE {
+            return Err(format!(
+                "invalid pattern: expected 1 to {MAX_SIZE} columns, got {width}"
+            ));
+        }
+        if self.pattern.iter().any(|row| row.chars().count() != width) {
+            return Err("invalid pattern: each row must be the same width".to_owned());
+        }
+
+        let mut key = HashMap::with_capacity(self.key.len());
+        for (symbol, ingredient) in self.key {
+            let mut chars = symbol.chars();
+            match (chars.next(), chars.next()) {
+                (Some(' '), None) => {
+                    return Err("invalid key entry: ' ' is a reserved symbol".to_owned())
+                }
+                (Some(c), None) => {
+                    key.insert(c, (ingredient.resolve(registry)?, false));
+                }
+                _ => {
+                    return Err(format!(
+                    "invalid key entry: '{symbol}' is an invalid symbol (must be 1 character only)"
+                ))
+                }
+            }
+        }
+
+        let pattern = remove_padding(&self.pattern);
+        if pattern.is_empty() {
+            return Err("invalid pattern: empty pattern not allowed".to_owned());
+        }
+        let height = pattern.len();
+        let width = pattern[0].chars().count();
+        let mut ingredients = Vec::with_capacity(width * height);
+        for c in pattern.iter().flat_map(|row| row.chars()) {
+            if c == ' ' {
+                ingredients.push(None);
+                continue;
+            }
+            let (ingredient, used) = key.get_mut(&c).ok_or_else(|| {
+                format!("pattern references symbol '{c}' but it's not defined in the key")
+            })?;
+            *used = true;
+            ingredients.push(Some(ingredient.clone()));
+        }
+        if let Some(c) = key.iter().find_map(|(c, (_, used))| (!used).then_some(c)) {
+            return Err(format!(
+                "key defines symbol '{c}' but it's not used in pattern"
+            ));
+        }
+
+        Ok(ShapedRecipe {
+            group: self.group,
+            width,
+            height,
+            ingredients,
+            result: self.result,
+            show_notification: self.show_notification,
+        })
+    }
+}
@@ -0,0 +1,189 @@
+use std::{fmt::Debug, str::FromStr};
+
+use rimecraft_item::{ItemStack, RawItem};
+use rimecraft_registry::Registry;
+use serde::Deserialize;
+
+use crate::{
+    ingredient::RawIngredient, CraftingInput, DynCraftingRecipe, ErasedRecipe, Ingredient, Recipe,
+    RecipeCx, RecipeType,
+};
+
+/// The max count of ingredients of shapeless recipes.
+const MAX_INGREDIENTS: usize = 9;
+
+/// A crafting recipe requiring its ingredients to be placed in any order.
+///
+/// # MCJE Reference
+///
+/// This represents `ShapelessRecipe` (yarn).
+pub struct ShapelessRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    group: String,
+    ingredients: Vec<Ingredient<'r, Cx>>,
+    result: ItemStack<'r, Cx>,
+}
+
+impl<'r, Cx> ShapelessRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    /// Creates a new shapeless recipe.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no ingredients or more than 9 ingredients.
+    pub fn new(ingredients: Vec<Ingredient<'r, Cx>>, result: ItemStack<'r, Cx>) -> Self {
+        assert!(
+            (1..=MAX_INGREDIENTS).contains(&ingredients.len()),
+            "shapeless recipes should have 1 to {MAX_INGREDIENTS} ingredients"
+        );
+        Self {
+            group: String::new(),
+            ingredients,
+            result,
+        }
+    }
+
+    /// Sets the group of this recipe.
+    #[inline]
+    pub fn with_group(self, group: String) -> Self {
+        Self { group, ..self }
+    }
+
+    /// Returns the ingredients of this recipe.
+    #[inline]
+    pub fn ingredients(&self) -> &[Ingredient<'r, Cx>] {
+        &self.ingredients
+    }
+}
+
+/// Tries to assign each stack to a distinct ingredient, backtracking on
+/// conflicts.
+fn assign<'r, Cx>(
+    ingredients: &[Ingredient<'r, Cx>],
+    stacks: &[&ItemStack<'r, Cx>],
+    used: &mut [bool],
+) -> bool
+where
+    Cx: RecipeCx<'r>,
+{
+    let Some((stack, rest)) = stacks.split_first() else {
+        return true;
+    };
+    for (i, ingredient) in ingredients.iter().enumerate() {
+        if !used[i] && ingredient.test(stack) {
+            used[i] = true;
+            if assign(ingredients, rest, used) {
+                return true;
+            }
+            used[i] = false;
+        }
+    }
+    false
+}
+
+impl<'r, Cx> Recipe<'r, Cx, CraftingInput<'_, 'r, Cx>> for ShapelessRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    fn matches(&self, input: &CraftingInput<'_, 'r, Cx>) -> bool {
+        if input.non_empty_count() != self.ingredients.len() {
+            return false;
+        }
+        let stacks: Vec<_> = input
+            .stacks()
+            .iter()
+            .copied()
+            .filter(|stack| !stack.is_empty())
+            .collect();
+        assign(
+            &self.ingredients,
+            &stacks,
+            &mut vec![false; self.ingredients.len()],
+        )
+    }
+
+    #[inline]
+    fn result(&self) -> &ItemStack<'r, Cx> {
+        &self.result
+    }
+
+    #[inline]
+    fn recipe_type(&self) -> RecipeType {
+        RecipeType::Crafting
+    }
+
+    #[inline]
+    fn group(&self) -> &str {
+        &self.group
+    }
+}
+
+impl<'r, Cx> ErasedRecipe<'r, Cx> for ShapelessRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[inline]
+    fn as_crafting(&self) -> Option<&DynCraftingRecipe<'r, Cx>> {
+        Some(self)
+    }
+}
+
+impl<'r, Cx> Debug for ShapelessRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: Debug, Settings<'r>: Debug> + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShapelessRecipe")
+            .field("group", &self.group)
+            .field("ingredients", &self.ingredients)
+            .field("result", &self.result)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "ItemStack<'r, Cx>: Deserialize<'de>"))]
+pub(crate) struct RawShapelessRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r>,
+{
+    #[serde(default)]
+    group: String,
+    ingredients: Vec<RawIngredient>,
+    result: ItemStack<'r, Cx>,
+}
+
+impl<'r, Cx> RawShapelessRecipe<'r, Cx>
+where
+    Cx: RecipeCx<'r, Id: FromStr<Err: std::fmt::Display> + Clone>,
+{
+    /// Validates the ingredients and resolves them through the given item
+    /// registry.
+    pub(crate) fn resolve(
+        self,
+        registry: &'r Registry<Cx::Id, RawItem<'r, Cx>>,
+    ) -> Result<ShapelessRecipe<'r, Cx>, String> {
+        match self.ingredients.len() {
+            0 => return Err("no ingredients for shapeless recipe".to_owned()),
+            len if len > MAX_INGREDIENTS => {
+                return Err(format!(
+                    "too many ingredients for shapeless recipe: the maximum is {MAX_INGREDIENTS}"
+                ))
+            }
+            _ => {}
+        }
+        Ok(ShapelessRecipe {
+            group: self.group,
+            ingredients: self
+                .ingredients
+                .into_iter()
+                .map(|ingredient| ingredient.resolve(registry))
+                .collect::<Result<_, _>>()?,
+            result: self.result,
+        })
+    }
+}
@@ -0,0 +1,425 @@
+use rimecraft_item::{
+    component::map::ComponentMap,
+    test_global_integration::{component_type, id, init_registries, item, key, registry, Settings},
+    ItemStack, MaxStackSize, RawItem,
+};
+use rimecraft_registry::{entry::RefEntry, tag::TagKey};
+use serde::de::DeserializeSeed;
+use test_global::TestContext;
+
+use crate::{
+    CookingRecipe, CraftingInput, DynCraftingRecipe, ErasedRecipe, Ingredient, IngredientSeed,
+    Recipe, RecipeManager, RecipeSeed, RecipeType, ShapedRecipe, VanillaRecipe,
+};
+
+fn init() {
+    init_registries(|items| {
+        let settings = || {
+            let mut builder = ComponentMap::builder();
+            builder.insert(component_type("max_stack_size"), MaxStackSize(64));
+            Settings(builder.build())
+        };
+        items
+            .register_default(key("air"), RawItem::new(settings()))
+            .unwrap();
+        for path in [
+            "oak_planks",
+            "birch_planks",
+            "stick",
+            "iron_ingot",
+            "raw_iron",
+            "wooden_pickaxe",
+        ] {
+            items.register(key(path), RawItem::new(settings())).unwrap();
+        }
+    });
+    let planks = TagKey {
+        registry: registry().key().clone(),
+        id: id("planks"),
+    };
+    let entry = |path: &str| -> &RefEntry<_, _> { item(path).into() };
+    registry()
+        .populate_tags([(planks, vec![entry("oak_planks"), entry("birch_planks")])])
+        .unwrap();
+}
+
+fn stack(path: &str) -> ItemStack<'static, TestContext> {
+    ItemStack::new(item(path), 1)
+}
+
+fn empty() -> ItemStack<'static, TestContext> {
+    ItemStack::empty()
+}
+
+fn parse(json: &str) -> serde_json::Result<VanillaRecipe<'static, TestContext>> {
+    RecipeSeed::new(registry()).deserialize(&mut serde_json::Deserializer::from_str(json))
+}
+
+fn recipe(json: &str) -> VanillaRecipe<'static, TestContext> {
+    parse(json).unwrap()
+}
+
+const PICKAXE: &str = r##"{
+    "type": "minecraft:crafting_shaped",
+    "pattern": ["XXX", " # ", " # "],
+    "key": { "X": "#minecraft:planks", "#": { "item": "minecraft:stick" } },
+    "result": { "id": "minecraft:wooden_pickaxe" }
+}"##;
+
+#[test]
+fn ingredient() {
+    init();
+    let parse = |json: &str| {
+        IngredientSeed::new(registry()).deserialize(&mut serde_json::Deserializer::from_str(json))
+    };
+
+    let planks = parse(r##"[{ "tag": "minecraft:planks" }, "minecraft:stick"]"##).unwrap();
+    assert_eq!(planks.entries().len(), 2);
+    assert!(planks.test(&stack("birch_planks")));
+    assert!(planks.test(&stack("stick")));
+    assert!(!planks.test(&stack("iron_ingot")));
+    assert!(!planks.test(&empty()));
+
+    assert!(parse(r#""minecraft:diamond""#).is_err());
+    assert!(parse("[]").is_err());
+}
+
+#[test]
+fn shaped() {
+    init();
+    let VanillaRecipe::Shaped(pickaxe) = recipe(PICKAXE) else {
+        panic!("expected a shaped recipe");
+    };
+    assert_eq!((pickaxe.width(), pickaxe.height()), (3, 3));
+    assert_eq!(pickaxe.result().item(), item("wooden_pickaxe"));
+    assert_eq!(pickaxe.result().count(), 1);
+
+    let grid = [
+        stack("oak_planks"),
+        stack("birch_planks"),
+        stack("oak_planks"),
+        empty(),
+        stack("stick"),
+        empty(),
+        empty(),
+        stack("stick"),
+        empty(),
+    ];
+    assert!(pickaxe.matches(&CraftingInput::new(3, 3, &grid)));
+
+    let mut wrong = grid.clone();
+    wrong[4] = stack("iron_ingot");
+    assert!(!pickaxe.matches(&CraftingInput::new(3, 3, &wrong)));
+    let mut extra = grid;
+    extra[3] = stack("stick");
+    assert!(!pickaxe.matches(&CraftingInput::new(3, 3, &extra)));
+}
+
+#[test]
+fn shaped_offset_and_mirrored() {
+    init();
+    let recipe = ShapedRecipe::new(
+        2,
+        2,
+        vec![
+            Some(Ingredient::of_items([item("iron_ingot")])),
+            None,
+            Some(Ingredient::of_items([item("iron_ingot")])),
+            Some(Ingredient::of_items([item("stick")])),
+        ],
+        stack("raw_iron"),
+    );
+
+    let offset = [
+        empty(),
+        empty(),
+        empty(),
+        empty(),
+        stack("iron_ingot"),
+        empty(),
+        empty(),
+        stack("iron_ingot"),
+        stack("stick"),
+    ];
+    let input = CraftingInput::new(3, 3, &offset);
+    assert_eq!((input.width(), input.height()), (2, 2));
+    assert!(recipe.matches(&input));
+
+    let mirrored = [
+        empty(),
+        stack("iron_ingot"),
+        stack("stick"),
+        stack("iron_ingot"),
+    ];
+    assert!(recipe.matches(&CraftingInput::new(2, 2, &mirrored)));
+
+    let flipped = [
+        stack("iron_ingot"),
+        stack("stick"),
+        stack("iron_ingot"),
+        empty(),
+    ];
+    assert!(!recipe.matches(&CraftingInput::new(2, 2, &flipped)));
+}
+
+#[test]
+fn shaped_invalid() {
+    init();
+    let result = r#""result": { "id": "minecraft:stick" }"#;
+
+    assert!(parse(&format!(
+        r#"{{ "type": "crafting_shaped", "pattern": ["X"], "key": {{}}, {result} }}"#
+    ))
+    .is_err());
+    assert!(parse(&format!(
+        r#"{{ "type": "crafting_shaped", "pattern": ["X"], "key": {{ "X": "minecraft:stick", "Y": "minecraft:stick" }}, {result} }}"#
+    ))
+    .is_err());
+    assert!(parse(&format!(
+        r#"{{ "type": "crafting_shaped", "pattern": ["XX", "X"], "key": {{ "X": "minecraft:stick" }}, {result} }}"#
+    ))
+    .is_err());
+    assert!(parse(&format!(
+        r#"{{ "type": "crafting_shaped", "pattern": ["XXXX"], "key": {{ "X": "minecraft:stick" }}, {result} }}"#
+    ))
+    .is_err());
+    assert!(parse(&format!(
+        r#"{{ "type": "crafting_shaped", "pattern": ["X"], "key": {{ "X": "minecraft:unknown" }}, {result} }}"#
+    ))
+    .is_err());
+
+    let VanillaRecipe::Shaped(padded) = parse(&format!(
+        r#"{{ "type": "crafting_shaped", "pattern": ["   ", " X ", "   "], "key": {{ "X": "minecraft:stick" }}, {result} }}"#
+    ))
+    .unwrap() else {
+        panic!("expected a shaped recipe");
+    };
+    assert_eq!((padded.width(), padded.height()), (1, 1));
+
+    // Symbols may take more than one byte.
+    for (pattern, size) in [
+        (r#"["é"]"#, (1, 1)),
+        (r#"["  ", "é "]"#, (1, 1)),
+        (r#"["é ", " é"]"#, (2, 2)),
+    ] {
+        let VanillaRecipe::Shaped(recipe) = parse(&format!(
+            r#"{{ "type": "crafting_shaped", "pattern": {pattern}, "key": {{ "é": "minecraft:stick" }}, {result} }}"#
+        ))
+        .unwrap() else {
+            panic!("expected a shaped recipe");
+        };
+        assert_eq!((recipe.width(), recipe.height()), size);
+    }
+}
+
+#[test]
+fn shapeless() {
+    init();
+    let VanillaRecipe::Shapeless(sticks) = recipe(
+        r##"{
+            "type": "minecraft:crafting_shapeless",
+            "group": "sticks",
+            "ingredients": ["#minecraft:planks", "minecraft:iron_ingot"],
+            "result": {
+                "id": "minecraft:stick",
+                "count": 4,
+                "components": { "minecraft:max_stack_size": 16 }
+            }
+        }"##,
+    ) else {
+        panic!("expected a shapeless recipe");
+    };
+    assert_eq!(sticks.group(), "sticks");
+
+    let grid = [stack("iron_ingot"), empty(), empty(), stack("birch_planks")];
+    let input = CraftingInput::new(2, 2, &grid);
+    assert!(sticks.matches(&input));
+    let output = sticks.craft(&input);
+    assert_eq!(output.item(), item("stick"));
+    assert_eq!(output.count(), 4);
+    assert_eq!(output.max_count(), 16);
+
+    let twice = [
+        stack("iron_ingot"),
+        stack("oak_planks"),
+        empty(),
+        stack("birch_planks"),
+    ];
+    assert!(!sticks.matches(&CraftingInput::new(2, 2, &twice)));
+    let missing = [stack("oak_planks"), stack("birch_planks")];
+    assert!(!sticks.matches(&CraftingInput::new(2, 1, &missing)));
+}
+
+#[test]
+fn cooking() {
+    init();
+    let VanillaRecipe::Cooking(smelting) = recipe(
+        r#"{
+            "type": "minecraft:smelting",
+            "ingredient": { "item": "minecraft:raw_iron" },
+            "result": { "id": "minecraft:iron_ingot" },
+            "experience": 0.7
+        }"#,
+    ) else {
+        panic!("expected a cooking recipe");
+    };
+    assert_eq!(smelting.recipe_type(), RecipeType::Smelting);
+    assert_eq!(smelting.cooking_time(), 200);
+    assert_eq!(smelting.experience(), 0.7);
+    assert!(smelting.matches(&stack("raw_iron")));
+    assert!(!smelting.matches(&stack("iron_ingot")));
+
+    let VanillaRecipe::Cooking(blasting) = recipe(
+        r#"{
+            "type": "blasting",
+            "ingredient": "minecraft:raw_iron",
+            "result": { "id": "minecraft:iron_ingot" },
+            "cookingtime": 50
+        }"#,
+    ) else {
+        panic!("expected a cooking recipe");
+    };
+    assert_eq!(blasting.recipe_type(), RecipeType::Blasting);
+    assert_eq!(blasting.cooking_time(), 50);
+
+    let smoking = CookingRecipe::new(
+        RecipeType::Smoking,
+        Ingredient::of_items([item("raw_iron")]),
+        stack("iron_ingot"),
+    );
+    assert_eq!(smoking.cooking_time(), 100);
+}
+
+#[test]
+fn manager() {
+    init();
+    let mut manager = RecipeManager::new();
+    manager
+        .load(
+            id("wooden_pickaxe"),
+            registry(),
+            &mut serde_json::Deserializer::from_str(PICKAXE),
+        )
+        .unwrap();
+    manager
+        .load(
+            id("iron_ingot_from_smelting_raw_iron"),
+            registry(),
+            &mut serde_json::Deserializer::from_str(
+                r#"{
+                    "type": "minecraft:smelting",
+                    "ingredient": "minecraft:raw_iron",
+                    "result": { "id": "minecraft:iron_ingot" }
+                }"#,
+            ),
+        )
+        .unwrap();
+    assert_eq!(manager.len(), 2);
+    assert!(manager.get(&id("wooden_pickaxe")).is_some());
+
+    let grid = [
+        stack("oak_planks"),
+        stack("oak_planks"),
+        stack("oak_planks"),
+        empty(),
+        stack("stick"),
+        empty(),
+        empty(),
+        stack("stick"),
+        empty(),
+    ];
+    let input = CraftingInput::new(3, 3, &grid);
+    let (matched, _) = manager.first_crafting_match(&input).unwrap();
+    assert_eq!(matched, &id("wooden_pickaxe"));
+    assert_eq!(
+        manager.craft(&input).unwrap().item(),
+        item("wooden_pickaxe")
+    );
+    assert!(manager
+        .craft(&CraftingInput::new(
+            3,
+            3,
+            &std::array::from_fn::<_, 9, _>(|_| empty())
+        ))
+        .is_none());
+
+    let (matched, _) = manager
+        .first_cooking_match(RecipeType::Smelting, &stack("raw_iron"))
+        .unwrap();
+    assert_eq!(matched, &id("iron_ingot_from_smelting_raw_iron"));
+    assert!(manager
+        .first_cooking_match(RecipeType::Blasting, &stack("raw_iron"))
+        .is_none());
+}
+
+/// Crafts raw iron from any two iron ingots.
+struct Unsmelting(ItemStack<'static, TestContext>);
+
+impl Recipe<'static, TestContext, CraftingInput<'_, 'static, TestContext>> for Unsmelting {
+    fn matches(&self, input: &CraftingInput<'_, 'static, TestContext>) -> bool {
+        let mut ingots = 0;
+        for y in 0..input.height() {
+            for x in 0..input.width() {
+                let stack = input.stack(x, y).unwrap();
+                if stack.item() == item("iron_ingot") {
+                    ingots += 1;
+                } else if !stack.is_empty() {
+                    return false;
+                }
+            }
+        }
+        ingots == 2
+    }
+
+    fn result(&self) -> &ItemStack<'static, TestContext> {
+        &self.0
+    }
+
+    fn recipe_type(&self) -> RecipeType {
+        RecipeType::Crafting
+    }
+}
+
+impl ErasedRecipe<'static, TestContext> for Unsmelting {
+    fn as_crafting(&self) -> Option<&DynCraftingRecipe<'static, TestContext>> {
+        Some(self)
+    }
+}
+
+#[test]
+fn manager_custom() {
+    init();
+    let mut manager = RecipeManager::new();
+    manager.insert(id("unsmelting"), Unsmelting(stack("raw_iron")));
+    manager
+        .load(
+            id("wooden_pickaxe"),
+            registry(),
+            &mut serde_json::Deserializer::from_str(PICKAXE),
+        )
+        .unwrap();
+    let unsmelting = manager.get(&id("unsmelting")).unwrap();
+    assert!(unsmelting.as_cooking().is_none());
+    assert_eq!(
+        unsmelting.as_crafting().unwrap().result().item(),
+        item("raw_iron")
+    );
+
+    let grid = [stack("iron_ingot"), empty(), empty(), stack("iron_ingot")];
+    let input = CraftingInput::new(2, 2, &grid);
+    let (matched, _) = manager.first_crafting_match(&input).unwrap();
+    assert_eq!(matched, &id("unsmelting"));
+    assert_eq!(manager.craft(&input).unwrap().item(), item("raw_iron"));
+    assert!(manager
+        .first_cooking_match(RecipeType::Smelting, &stack("iron_ingot"))
+        .is_none());
+
+    // Replacing a recipe keeps its priority.
+    let replaced = manager.insert(id("unsmelting"), Unsmelting(stack("iron_ingot")));
+    assert!(replaced.is_some());
+    assert_eq!(
+        manager.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+        [&id("unsmelting"), &id("wooden_pickaxe")]
+    );
+    assert_eq!(manager.craft(&input).unwrap().item(), item("iron_ingot"));
+}
@@ -9,13 +9,17 @@ use std::{
     fmt::Display,
     hash::Hash,
     ops::{Deref, Index},
-    sync::OnceLock,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
 };
 
 use entry::RefEntry;
 use key::Key;
 use parking_lot::RwLock;
-use tag::Tags;
+use rimecraft_event::ListenerId;
+use tag::{ReloadEvent, ReloadListener, Tags};
 
 mod dyn_manager;
 pub mod entry;
@@ -31,13 +35,20 @@ pub use tag::TagKey;
 pub use dyn_manager::*;
 
 /// Immutable registry of various in-game components.
-#[derive(Debug)]
+///
+/// # Tag Reloading
+///
+/// Tags of a registry could be swapped in place through [`Self::populate_tags`] and
+/// [`Self::clear_tags`]. Each reload bumps the [tag generation](Self::tag_generation)
+/// and fires the reload event, so caches keyed on tag membership could be invalidated.
 pub struct Registry<K, T> {
     key: Key<K, Self>,
 
     entries: Vec<RefEntry<K, T>>,
     kv: HashMap<K, usize>,
     tv: RwLock<HashMap<TagKey<K, T>, Vec<usize>>>,
+    tag_generation: AtomicU64,
+    tag_reload_event: RwLock<ReloadEvent>,
 
     /// The default registration raw id.
     default: Option<usize>,
@@ -91,15 +102,14 @@ where
     }
 
     /// Gets entries of given tag.
+    ///
+    /// The returned iterator is a snapshot of the tag, so it won't observe any
+    /// reload happened after its creation. See [`OfTag::is_stale`].
     pub fn of_tag<'a>(&'a self, tag: &TagKey<K, T>) -> OfTag<'a, K, T> {
+        let tv = self.tv.read();
         OfTag {
-            inner: self
-                .tv
-                .read()
-                .get(tag)
-                .cloned()
-                .unwrap_or_default()
-                .into_iter(),
+            inner: tv.get(tag).cloned().unwrap_or_default().into_iter(),
+            generation: self.tag_generation(),
             registry: self,
         }
     }
@@ -167,6 +177,79 @@ impl<K, T> Registry<K, T> {
     pub fn default_entry(&self) -> Option<Reg<'_, K, T>> {
         self.default.and_then(|raw| self.of_raw(raw))
     }
+
+    /// Gets the tag generation of this registry.
+    ///
+    /// The generation starts from `0` and increases by one each time the tags
+    /// of this registry are reloaded.
+    #[inline]
+    pub fn tag_generation(&self) -> u64 {
+        self.tag_generation.load(Ordering::Acquire)
+    }
+
+    /// Registers a listener with given phase, which will be called with the new
+    /// tag generation after each tag reload of this registry.
+    ///
+    /// # Deadlocks
+    ///
+    /// Registering or unregistering listeners inside a listener will deadlock.
+    pub fn register_tag_reload_listener(
+        &self,
+        listener: Arc<ReloadListener>,
+        phase: i8,
+    ) -> ListenerId {
+        self.tag_reload_event.write().register(listener, phase)
+    }
+
+    /// Unregisters a tag reload listener from this registry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given identifier is invalid for this registry.
+    #[inline]
+    pub fn unregister_tag_reload_listener(&self, id: ListenerId) {
+        self.tag_reload_event.write().unregister(id)
+    }
+
+    /// Replaces all tag bindings at once, bumps the tag generation and
+    /// fires the reload event.
+    ///
+    /// The tag map lock is held during the whole swap, so readers of [`Tags`] and
+    /// [`OfTag`] observe either the old state or the new state. Tags of each
+    /// entry are replaced one entry at a time, so [`RefEntry::tags`] of
+    /// different entries may mix old and new bindings during the swap.
+    fn commit_tags(
+        &self,
+        tv: HashMap<TagKey<K, T>, Vec<usize>>,
+        entry_tags: Vec<HashSet<TagKey<K, T>>>,
+    ) {
+        let generation = {
+            let mut guard = self.tv.write();
+            for (entry, tags) in self.entries.iter().zip(entry_tags) {
+                *entry.tags.write() = tags;
+            }
+            *guard = tv;
+            self.tag_generation.fetch_add(1, Ordering::AcqRel) + 1
+        };
+        self.tag_reload_event.read().invoker()(generation)
+    }
+}
+
+impl<K, T> std::fmt::Debug for Registry<K, T>
+where
+    K: std::fmt::Debug,
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Registry")
+            .field("key", &self.key)
+            .field("entries", &self.entries)
+            .field("kv", &self.kv)
+            .field("tv", &self.tv)
+            .field("tag_generation", &self.tag_generation)
+            .field("default", &self.default)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<K, T, Q> Index<Q> for Registry<K, T>
@@ -374,6 +457,22 @@ impl<'a, K, T> Iterator for Entries<'a, K, T> {
 pub struct OfTag<'a, K, T> {
     registry: &'a Registry<K, T>,
     inner: std::vec::IntoIter<usize>,
+    generation: u64,
+}
+
+impl<K, T> OfTag<'_, K, T> {
+    /// Gets the tag generation this iterator was created at.
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Whether the tags of the registry have been reloaded since
+    /// this iterator was created.
+    #[inline]
+    pub fn is_stale(&self) -> bool {
+        self.generation != self.registry.tag_generation()
+    }
 }
 
 impl<'a, K, T> Iterator for OfTag<'a, K, T> {
@@ -519,6 +618,8 @@ where
                 .map(|(raw, entry)| (entry.key.value().clone(), raw))
                 .collect(),
             tv: RwLock::new(HashMap::new()),
+            tag_generation: AtomicU64::new(0),
+            tag_reload_event: RwLock::new(tag::reload_event()),
             entries,
             default: value.default,
         }
@@ -537,33 +638,52 @@ where
     K: Hash + Eq + Clone,
 {
     /// Binds given tags to entries, and removes old tag bindings.
+    ///
+    /// The new bindings are built aside and swapped in while the tag map is
+    /// locked, so readers of [`Registry::tags`] and [`Registry::of_tag`] never
+    /// observe a half-populated state. Tags of a single entry are replaced as
+    /// a whole, but tags read from different entries through
+    /// [`RefEntry::tags`] may mix old and new bindings until this returns. The reload event is fired afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns back the tag binding an entry that is not from this registry, in
+    /// which case the old bindings are kept and no reload happens.
     #[doc(alias = "bind_tags")]
-    pub fn populate_tags<'a, I>(&'a self, entries: I)
+    pub fn populate_tags<'a, I>(&'a self, entries: I) -> Result<(), TagKey<K, T>>
     where
         I: IntoIterator<Item = (TagKey<K, T>, Vec<&'a RefEntry<K, T>>)>,
     {
-        self.clear_tags();
-
-        let iter = entries.into_iter();
-        let mut tv = self.tv.write();
-        for (tag, entries) in iter {
+        let mut tv: HashMap<TagKey<K, T>, Vec<usize>> = HashMap::new();
+        let mut entry_tags: Vec<HashSet<TagKey<K, T>>> =
+            self.entries.iter().map(|_| HashSet::new()).collect();
+        for (tag, entries) in entries {
             for entry in entries.iter() {
-                entry.tags.write().insert(tag.clone());
-            }
-            if let Some(vec) = tv.get_mut(&tag) {
-                vec.extend(entries.into_iter().map(|e| e.raw));
-            } else {
-                tv.insert(tag, entries.into_iter().map(|e| e.raw).collect());
+                if !self
+                    .entries
+                    .get(entry.raw)
+                    .is_some_and(|e| std::ptr::eq(e, *entry))
+                {
+                    return Err(tag);
+                }
+                entry_tags[entry.raw].insert(tag.clone());
             }
+            tv.entry(tag)
+                .or_default()
+                .extend(entries.into_iter().map(|e| e.raw));
         }
+        self.commit_tags(tv, entry_tags);
+        Ok(())
     }
 
     /// Clears all tags.
+    ///
+    /// This is considered as a reload, so the reload event is fired afterwards.
     pub fn clear_tags(&self) {
-        for entry in self.entries.iter() {
-            entry.tags.write().clear();
-        }
-        self.tv.write().clear();
+        self.commit_tags(
+            HashMap::new(),
+            self.entries.iter().map(|_| HashSet::new()).collect(),
+        );
     }
 }
 
@@ -1,9 +1,30 @@
 //! Tag related types.
 
-use std::{collections::HashMap, hash::Hash};
+use std::{collections::HashMap, hash::Hash, sync::Arc};
+
+use rimecraft_event::DefaultSyncEvent;
 
 use crate::{key::Key, Registry};
 
+/// Listener of tag reloads, called with the new tag generation of the registry.
+///
+/// The listener does not receive the registry itself, as the registry is not
+/// required to be `'static`. Capture whatever needed instead.
+pub type ReloadListener = dyn Fn(u64) + Send + Sync;
+
+/// Event fired after the tags of a registry being reloaded.
+pub type ReloadEvent = DefaultSyncEvent<ReloadListener>;
+
+pub(crate) fn reload_event() -> ReloadEvent {
+    ReloadEvent::new(|listeners| {
+        Arc::new(move |generation| {
+            for listener in &listeners {
+                listener(generation)
+            }
+        })
+    })
+}
+
 /// Key of a tag.
 pub struct TagKey<K, T> {
     /// The registry reference.
@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{entry::RefEntry, *};
 
 #[test]
 fn register() {
@@ -38,3 +38,100 @@ fn freeze() {
     assert_eq!(registry.get(&"two").unwrap(), 2);
     assert!(registry.get(&"three").is_none());
 }
+
+#[test]
+fn tag_reload() {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    for (id, value) in [("one", 1), ("two", 2), ("three", 3)] {
+        registry
+            .register(Key::new(registry.key().value(), id), value)
+            .unwrap();
+    }
+    let registry: Registry<_, _> = registry.into();
+    let odd = TagKey {
+        registry: *registry.key(),
+        id: "odd",
+    };
+
+    let notified = Arc::new(AtomicU64::new(0));
+    let notified_c = notified.clone();
+    registry.register_tag_reload_listener(
+        Arc::new(move |generation| notified_c.store(generation, Ordering::Relaxed)),
+        0,
+    );
+    assert_eq!(registry.tag_generation(), 0);
+
+    let entry = |id| -> &RefEntry<_, _> { registry.get(&id).unwrap().into() };
+    registry
+        .populate_tags([(odd, vec![entry("one"), entry("three")])])
+        .unwrap();
+    assert_eq!(registry.tag_generation(), 1);
+    assert_eq!(notified.load(Ordering::Relaxed), 1);
+
+    // Entries from other registries are rejected without reloading.
+    let mut other: RegistryMut<&'static str, i32> = RegistryMut::new(Key::new("root", "other"));
+    other
+        .register(Key::new(other.key().value(), "two"), 2)
+        .unwrap();
+    let other: Registry<_, _> = other.into();
+    let foreign: &RefEntry<_, _> = other.get(&"two").unwrap().into();
+    assert_eq!(
+        registry.populate_tags([(odd, vec![entry("one"), foreign])]),
+        Err(odd)
+    );
+    assert_eq!(registry.tag_generation(), 1);
+    assert!(entry("three").tags().contains(&odd));
+
+    let of_tag = registry.of_tag(&odd);
+    assert!(!of_tag.is_stale());
+    assert!(entry("one").tags().contains(&odd));
+    assert!(!entry("two").tags().contains(&odd));
+
+    registry.clear_tags();
+    assert_eq!(registry.tag_generation(), 2);
+    assert_eq!(notified.load(Ordering::Relaxed), 2);
+    assert!(of_tag.is_stale());
+    assert_eq!(of_tag.map(|r| *r).collect::<Vec<_>>(), [1, 3]);
+    assert!(entry("one").tags().is_empty());
+    assert_eq!(registry.of_tag(&odd).count(), 0);
+}
+
+#[test]
+fn tag_reload_atomicity() {
+    let mut registry: RegistryMut<&'static str, i32> =
+        RegistryMut::new(Key::new("root", "integer"));
+    let ids: Vec<&'static str> = (0..64)
+        .map(|i| &*Box::leak(i.to_string().into_boxed_str()))
+        .collect();
+    for (i, id) in ids.iter().enumerate() {
+        registry
+            .register(Key::new(registry.key().value(), *id), i as i32)
+            .unwrap();
+    }
+    let registry: Registry<_, _> = registry.into();
+    let all = TagKey {
+        registry: *registry.key(),
+        id: "all",
+    };
+    let entries: Vec<&RefEntry<_, _>> = registry.entries().map(From::from).collect();
+
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            for _ in 0..256 {
+                registry.populate_tags([(all, entries.clone())]).unwrap();
+                registry.clear_tags();
+            }
+        });
+        for _ in 0..1024 {
+            let len = registry.of_tag(&all).count();
+            assert!(len == 0 || len == ids.len(), "half-populated tag: {len}");
+        }
+    });
+    assert_eq!(registry.tag_generation(), 512);
+}
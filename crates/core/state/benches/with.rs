@@ -0,0 +1,65 @@
+//! Benchmarks of state transitions.
+//!
+//! Transitions are looked up in tables precomputed when freezing the states,
+//! so the time of [`State::with`] should not grow with the number of states.
+
+// `criterion_group!` generates an undocumented public function.
+#![allow(missing_docs)]
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rimecraft_state::{
+    property::{data::IntData, IntProperty},
+    States, StatesMut,
+};
+
+fn properties(count: usize) -> Vec<IntProperty<'static>> {
+    (0..count)
+        .map(|i| IntProperty::with_owned_name(format!("prop_{i}"), IntData(0..=15)))
+        .collect()
+}
+
+fn states<'a>(props: &'a [IntProperty<'static>]) -> States<'a, ()> {
+    let mut states = StatesMut::new(());
+    for prop in props {
+        states.add(prop).unwrap();
+    }
+    states.freeze()
+}
+
+fn transitions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("with");
+    for count in 1..=3 {
+        let props = properties(count);
+        let states = states(&props);
+        let state = states
+            .find(|s| props.iter().all(|p| s.get(p) == Some(8)))
+            .unwrap();
+
+        group.bench_function(BenchmarkId::from_parameter(states.len()), |b| {
+            let mut value = 0;
+            b.iter(|| {
+                value = (value + 1) & 15;
+                black_box(state.with(&props[0], black_box(value)).unwrap())
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("cycle");
+    for count in 1..=3 {
+        let props = properties(count);
+        let states = states(&props);
+
+        group.bench_function(BenchmarkId::from_parameter(states.len()), |b| {
+            let mut state = states.default_state();
+            b.iter(|| {
+                state = state.cycle(&props[0]).unwrap();
+                black_box(state)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, transitions);
+criterion_main!(benches);
@@ -3,6 +3,7 @@
 //! This corresponds to `net.minecraft.state` in `yarn`.
 
 use std::{
+    borrow::Cow,
     collections::BTreeMap,
     fmt::{Debug, Display},
     ptr::NonNull,
@@ -10,7 +11,7 @@ use std::{
 };
 
 use ahash::AHashMap;
-use property::{BiIndex, ErasedProperty, Property, UnObjSafeErasedWrap as _, Wrap};
+use property::{BiIndex, ErasedProperty, Property, PropertySet, UnObjSafeErasedWrap as _, Wrap};
 
 #[cfg(feature = "regex")]
 use regex::Regex;
@@ -21,17 +22,47 @@ use crate::property::ErasedWrap;
 
 pub mod property;
 
-// <property> -> <<value> -> <state>>
-type Table<'a, T> = AHashMap<ErasedProperty<'a>, AHashMap<isize, NonNull<T>>>;
+// <property> -> <transitions>
+type Table<'a, T> = AHashMap<ErasedProperty<'a>, Transitions<T>>;
+
+/// Precomputed transitions of a state through one property.
+struct Transitions<T> {
+    /// The smallest value index of the property.
+    offset: isize,
+    /// Target states indexed by value index minus `offset`.
+    states: Box<[Option<NonNull<T>>]>,
+    /// The target state with the property cycled.
+    cycled: NonNull<T>,
+}
+
+impl<T> Debug for Transitions<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transitions")
+            .field("offset", &self.offset)
+            .field("states", &self.states)
+            .field("cycled", &self.cycled)
+            .finish()
+    }
+}
+
+impl<T> Transitions<T> {
+    #[inline]
+    fn get(&self, index: isize) -> Option<NonNull<T>> {
+        usize::try_from(index - self.offset)
+            .ok()
+            .and_then(|i| self.states.get(i).copied().flatten())
+    }
+}
 
 /// State of an object.
 pub struct State<'a, T> {
     pub(crate) entries: AHashMap<ErasedProperty<'a>, isize>,
     table: OnceLock<Table<'a, Self>>,
+    raw_id: usize,
     data: T,
 }
 
-impl<T> State<'_, T> {
+impl<'a, T> State<'a, T> {
     /// Gets the current value of given property in this state.
     #[inline]
     pub fn get<V, W>(&self, prop: &Property<'_, W>) -> Option<V>
@@ -43,6 +74,33 @@ impl<T> State<'_, T> {
             .and_then(|&index| prop.wrap.index(index))
     }
 
+    /// Gets the current values of given set of properties in this state.
+    ///
+    /// Returns `None` if any of the properties is not present in this state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rimecraft_state::{StatesMut, property::{BoolProperty, IntProperty, data::*}};
+    /// static AGE: IntProperty<'static> = IntProperty::new("age", IntData(0..=3));
+    /// static LIT: BoolProperty<'static> = BoolProperty::new("lit", BoolData);
+    ///
+    /// let mut states = StatesMut::new(());
+    /// states.add(&AGE).unwrap();
+    /// states.add(&LIT).unwrap();
+    /// let states = states.freeze();
+    ///
+    /// let state = states.default_state().with(&LIT, true).unwrap();
+    /// assert_eq!(state.get_many((&AGE, &LIT)), Some((0, true)));
+    /// ```
+    #[inline]
+    pub fn get_many<P, V>(&self, props: P) -> Option<V>
+    where
+        P: PropertySet<V>,
+    {
+        props.get_from(self)
+    }
+
     /// Gets the state of this state with given property `prop` cycled.
     ///
     /// # Errors
@@ -58,6 +116,12 @@ impl<T> State<'_, T> {
         W: BiIndex<V>,
         for<'w> &'w W: IntoIterator<Item = V>,
     {
+        let (erased, transitions) = self.transitions(prop.name())?;
+        if std::ptr::addr_eq(erased.wrap, &prop.wrap) {
+            // The property is the one these states were built from,
+            // so the precomputed target is valid.
+            return Ok(unsafe { transitions.cycled.as_ref() });
+        }
         let index = *self
             .entries
             .get(prop.name())
@@ -70,21 +134,17 @@ impl<T> State<'_, T> {
         ) else {
             return Ok(self);
         };
-        if next == index {
-            Ok(self)
-        } else {
-            self.table
-                .get()
-                .expect("state not initialized")
-                .get(prop.name())
-                .ok_or_else(|| Error::PropertyNotFound(prop.name().to_owned()))
-                .and_then(|map| map.get(&next).ok_or(Error::ValueNotFound(index)))
-                .map(|ptr| unsafe { ptr.as_ref() })
-        }
+        transitions
+            .get(next)
+            .ok_or(Error::ValueNotFound(next))
+            .map(|ptr| unsafe { ptr.as_ref() })
     }
 
     /// Gets the state of this state with given property `prop` set to `value`.
     ///
+    /// This is a constant-time lookup into the transition table precomputed
+    /// when freezing the states.
+    ///
     /// # Errors
     ///
     /// - Errors if the property `prop` is not present in this state.
@@ -98,22 +158,62 @@ impl<T> State<'_, T> {
     where
         W: BiIndex<V>,
     {
-        let index = *self
-            .entries
-            .get(prop.name())
-            .ok_or_else(|| Error::PropertyNotFound(prop.name().to_owned()))?;
+        let (_, transitions) = self.transitions(prop.name())?;
         let value = prop.wrap.index_of(&value).ok_or(Error::InvalidValue)?;
-        if value == index {
-            Ok(self)
-        } else {
-            self.table
-                .get()
-                .expect("state not initialized")
-                .get(prop.name())
-                .ok_or_else(|| Error::PropertyNotFound(prop.name().to_owned()))
-                .and_then(|map| map.get(&value).ok_or(Error::ValueNotFound(index)))
-                .map(|ptr| unsafe { ptr.as_ref() })
-        }
+        transitions
+            .get(value)
+            .ok_or(Error::ValueNotFound(value))
+            .map(|ptr| unsafe { ptr.as_ref() })
+    }
+
+    /// Gets the name of the current value of the property with given name.
+    ///
+    /// Returns `None` if the property is not present in this state.
+    pub fn get_name(&self, prop: &str) -> Option<Cow<'_, str>> {
+        self.entries
+            .get_key_value(prop)
+            .and_then(|(p, &index)| p.wrap.erased_to_name(index))
+    }
+
+    /// Gets the state of this state with the property of given name set to
+    /// the value of given name.
+    ///
+    /// The value is resolved through [`Wrap::parse_name`].
+    ///
+    /// # Errors
+    ///
+    /// - Errors if the property `prop` is not present in this state.
+    /// - Errors if the value name `value` is not valid in the property `prop`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the target state was dropped.
+    /// - Panics if this state is not fully initialized.
+    pub fn with_name(&self, prop: &str, value: &str) -> Result<&Self, Error> {
+        let (p, transitions) = self.transitions(prop)?;
+        let value = p
+            .wrap
+            .erased_parse_name(value)
+            .ok_or_else(|| Error::InvalidValueName {
+                property: prop.to_owned(),
+                value: value.to_owned(),
+            })?;
+        transitions
+            .get(value)
+            .ok_or(Error::ValueNotFound(value))
+            .map(|ptr| unsafe { ptr.as_ref() })
+    }
+
+    /// Gets names of all properties and their current values in this state,
+    /// sorted by property names.
+    pub fn names(&self) -> Vec<(&str, Cow<'_, str>)> {
+        let mut names: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|(p, &index)| p.wrap.erased_to_name(index).map(|v| (p.name, v)))
+            .collect();
+        names.sort_unstable_by_key(|(name, _)| *name);
+        names
     }
 
     /// Whether this state contains given property.
@@ -127,6 +227,21 @@ impl<T> State<'_, T> {
     pub fn data(&self) -> &T {
         &self.data
     }
+
+    /// Gets the raw id of this state, which is its index in its [`States`].
+    #[inline]
+    pub fn raw_id(&self) -> usize {
+        self.raw_id
+    }
+
+    #[inline]
+    fn transitions(&self, prop: &str) -> Result<(&ErasedProperty<'a>, &Transitions<Self>), Error> {
+        self.table
+            .get()
+            .expect("state not initialized")
+            .get_key_value(prop)
+            .ok_or_else(|| Error::PropertyNotFound(prop.to_owned()))
+    }
 }
 
 fn obtain_next(value: isize, mut iter: impl Iterator<Item = isize>) -> Option<isize> {
@@ -171,29 +286,34 @@ where
         I: IntoIterator<Item = ErasedProperty<'a>>,
     {
         let props: BTreeMap<_, _> = props.into_iter().map(|prop| (prop.name, prop)).collect();
-        let mut iter: Vec<Vec<(ErasedProperty<'a>, isize)>> = vec![Vec::new()];
-        for prop in props.values() {
-            iter = iter
-                .into_iter()
-                .flat_map(|lx| {
-                    prop.wrap
-                        .erased_iter()
-                        .map(|val| {
-                            let mut lx = lx.clone();
-                            lx.push((prop.clone(), val));
-                            lx
-                        })
-                        .collect::<Vec<_>>()
-                })
-                .collect();
+        let values: Vec<(&ErasedProperty<'a>, Vec<isize>)> = props
+            .values()
+            .map(|prop| (prop, prop.wrap.erased_iter().collect()))
+            .collect();
+
+        // States are laid out in mixed radix, with the last property varying fastest,
+        // so the neighbor of a state through a property is at a fixed stride from it.
+        let mut strides = vec![1usize; values.len()];
+        for i in (1..values.len()).rev() {
+            strides[i - 1] = strides[i] * values[i].1.len();
         }
-        let list = iter
-            .into_iter()
-            .map(|vec| vec.into_iter().collect::<AHashMap<_, _>>())
-            .map(|entries| {
+        let len = values.iter().map(|(_, vals)| vals.len()).product::<usize>();
+        let position = |i: usize, prop: usize| (i / strides[prop]) % values[prop].1.len();
+
+        let list = (0..len)
+            .map(|i| {
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(p, (prop, vals))| ((*prop).clone(), vals[position(i, p)]))
+                    .collect::<AHashMap<_, _>>()
+            })
+            .enumerate()
+            .map(|(raw_id, entries)| {
                 NonNull::new(Box::into_raw(Box::new(State {
                     entries,
                     table: OnceLock::new(),
+                    raw_id,
                     data: data.clone(),
                 })))
                 .expect("failed to allocate state")
@@ -201,29 +321,32 @@ where
             .collect::<Vec<_>>();
 
         // Initialize tables
-        for state in list.iter() {
-            let state = unsafe { state.as_ref() };
-            let mut table: Table<'a, State<'a, T>> = Table::new();
-            for (prop, s_val) in state.entries.iter() {
-                let mut row = AHashMap::new();
-                for val in prop.wrap.erased_iter().filter(|v| v != s_val) {
-                    let Some(s) = list.iter().find(|s| {
-                        let s = unsafe { s.as_ref() };
-                        s.entries.iter().all(|(p, v)| {
-                            if p == prop {
-                                *v == val
-                            } else {
-                                v == state.entries.get(p).unwrap()
-                            }
-                        })
-                    }) else {
-                        continue;
+        for (i, state) in list.iter().enumerate() {
+            let table: Table<'a, State<'a, T>> = values
+                .iter()
+                .enumerate()
+                .map(|(p, (prop, vals))| {
+                    let stride = strides[p];
+                    let pos = position(i, p);
+                    let base = i - pos * stride;
+                    let offset = vals.iter().copied().min().unwrap_or_default();
+                    let end = vals.iter().copied().max().unwrap_or_default();
+                    let mut states = vec![None; (end - offset + 1) as usize].into_boxed_slice();
+                    for (j, val) in vals.iter().enumerate() {
+                        states[(val - offset) as usize] = Some(list[base + j * stride]);
+                    }
+                    let transitions = Transitions {
+                        offset,
+                        states,
+                        cycled: list[base + (pos + 1) % vals.len() * stride],
                     };
-                    row.insert(val, *s);
-                }
-                table.insert(prop.clone(), row);
-            }
-            state.table.set(table).expect("state already initialized");
+                    ((*prop).clone(), transitions)
+                })
+                .collect();
+            unsafe { state.as_ref() }
+                .table
+                .set(table)
+                .expect("state already initialized");
         }
 
         Self {
@@ -240,6 +363,43 @@ impl<'a, T> States<'a, T> {
         &self.states
     }
 
+    /// Returns an iterator over all states.
+    ///
+    /// The first state is the [default state](Self::default_state).
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, 'a, T> {
+        Iter {
+            inner: self.states.iter(),
+        }
+    }
+
+    /// Finds the first state matching given predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rimecraft_state::{StatesMut, property::{BoolProperty, IntProperty, data::*}};
+    /// static AGE: IntProperty<'static> = IntProperty::new("age", IntData(0..=3));
+    /// static LIT: BoolProperty<'static> = BoolProperty::new("lit", BoolData);
+    ///
+    /// let mut states = StatesMut::new(());
+    /// states.add(&AGE).unwrap();
+    /// states.add(&LIT).unwrap();
+    /// let states = states.freeze();
+    ///
+    /// let state = states
+    ///     .find(|state| state.get_many((&AGE, &LIT)) == Some((2, true)))
+    ///     .unwrap();
+    /// assert_eq!(state.get(&AGE), Some(2));
+    /// ```
+    #[inline]
+    pub fn find<P>(&self, mut predicate: P) -> Option<&State<'a, T>>
+    where
+        P: FnMut(&State<'a, T>) -> bool,
+    {
+        self.iter().find(|state| predicate(state))
+    }
+
     /// Gets the default state.
     ///
     /// # Panics
@@ -263,6 +423,52 @@ impl<'a, T> States<'a, T> {
     }
 }
 
+impl<'s, 'a, T> IntoIterator for &'s States<'a, T> {
+    type Item = &'s State<'a, T>;
+    type IntoIter = Iter<'s, 'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over states of a [`States`].
+///
+/// See [`States::iter`].
+#[derive(Debug, Clone)]
+pub struct Iter<'s, 'a, T> {
+    inner: std::slice::Iter<'s, NonNull<State<'a, T>>>,
+}
+
+impl<'s, 'a, T> Iterator for Iter<'s, 'a, T> {
+    type Item = &'s State<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|ptr| unsafe { ptr.as_ref() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, '_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|ptr| unsafe { ptr.as_ref() })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, '_, T> {}
+
+impl<T> std::iter::FusedIterator for Iter<'_, '_, T> {}
+
+unsafe impl<T: Sync> Send for Iter<'_, '_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, '_, T> {}
+
 impl<T> Drop for States<'_, T> {
     fn drop(&mut self) {
         for state in self.states.iter() {
@@ -459,7 +665,7 @@ mod _serde {
                             .ok_or_else(|| {
                                 serde::de::Error::custom(format!("property {prop} not found"))
                             })?
-                            .get(&val)
+                            .get(val)
                             .ok_or_else(|| {
                                 serde::de::Error::custom(format!(
                                     "value {val} not found in property {prop}"
@@ -9,6 +9,8 @@ use std::{
     ops::RangeInclusive,
 };
 
+use crate::State;
+
 #[derive(Clone)]
 pub(crate) struct ErasedProperty<'a> {
     pub name: &'a str,
@@ -108,7 +110,6 @@ pub trait Wrap<T> {
 }
 
 pub(crate) trait ErasedWrap {
-    #[allow(dead_code)]
     fn erased_parse_name(&self, name: &str) -> Option<isize>;
     fn erased_to_name(&self, index: isize) -> Option<Cow<'_, str>>;
     fn erased_iter(&self) -> Box<dyn Iterator<Item = isize> + '_>;
@@ -205,12 +206,18 @@ where
 }
 
 mod bool;
+mod r#enum;
 mod int;
 
+#[cfg(feature = "voxel-math")]
+pub mod direction;
+
+pub use r#enum::StrEnum;
+
 pub mod data {
     //! Property data types.
 
-    pub use super::{bool::Data as BoolData, int::Data as IntData};
+    pub use super::{bool::Data as BoolData, int::Data as IntData, r#enum::Data as EnumData};
 }
 
 /// Property that has integer values.
@@ -219,6 +226,57 @@ pub type IntProperty<'a, T = RangeInclusive<i32>> = Property<'a, int::Data<T>>;
 /// Property that has boolean values.
 #[doc(alias = "BooleanProperty")]
 pub type BoolProperty<'a> = Property<'a, bool::Data>;
+/// Property that has enum values.
+///
+/// See [`StrEnum`] for the requirements of the enum type.
+pub type EnumProperty<'a, T> = Property<'a, r#enum::Data<'a, T>>;
+
+/// Set of properties whose values could be obtained from a state at once,
+/// as a tuple of values.
+///
+/// This is implemented for references to properties and tuples of them.
+/// See [`State::get_many`].
+pub trait PropertySet<V> {
+    /// Gets the values of the properties from given state.
+    ///
+    /// Returns `None` if any of the properties is not present in the state.
+    fn get_from<T>(&self, state: &State<'_, T>) -> Option<V>;
+}
+
+impl<V, W> PropertySet<V> for &Property<'_, W>
+where
+    W: BiIndex<V>,
+{
+    #[inline]
+    fn get_from<T>(&self, state: &State<'_, T>) -> Option<V> {
+        state.get(self)
+    }
+}
+
+macro_rules! property_set_tuple {
+    ($($p:ident: $v:ident),+) => {
+        impl<$($p, $v),+> PropertySet<($($v,)+)> for ($($p,)+)
+        where
+            $($p: PropertySet<$v>),+
+        {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn get_from<T>(&self, state: &State<'_, T>) -> Option<($($v,)+)> {
+                let ($($p,)+) = self;
+                Some(($($p.get_from(state)?,)+))
+            }
+        }
+    };
+}
+
+property_set_tuple!(P0: V0);
+property_set_tuple!(P0: V0, P1: V1);
+property_set_tuple!(P0: V0, P1: V1, P2: V2);
+property_set_tuple!(P0: V0, P1: V1, P2: V2, P3: V3);
+property_set_tuple!(P0: V0, P1: V1, P2: V2, P3: V3, P4: V4);
+property_set_tuple!(P0: V0, P1: V1, P2: V2, P3: V3, P4: V4, P5: V5);
+property_set_tuple!(P0: V0, P1: V1, P2: V2, P3: V3, P4: V4, P5: V5, P6: V6);
+property_set_tuple!(P0: V0, P1: V1, P2: V2, P3: V3, P4: V4, P5: V5, P6: V6, P7: V7);
 
 /// A property provider.
 pub trait ProvideProperty<'p> {
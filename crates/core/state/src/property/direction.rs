@@ -0,0 +1,53 @@
+//! Ready-made properties backed by [`rimecraft_voxel_math::direction`].
+
+use rimecraft_voxel_math::direction::{Axis, Direction};
+
+use super::{data::EnumData, EnumProperty, Property, StrEnum};
+
+impl StrEnum for Direction {
+    const VARIANTS: &'static [Self] = &Direction::ALL;
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        Direction::name(*self)
+    }
+
+    #[inline]
+    fn ordinal(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl StrEnum for Axis {
+    const VARIANTS: &'static [Self] = &Axis::ALL;
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        Axis::name(*self)
+    }
+
+    #[inline]
+    fn ordinal(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Property that has [`Direction`] values.
+pub type DirectionProperty<'a> = EnumProperty<'a, Direction>;
+
+/// Property that has [`Axis`] values.
+pub type AxisProperty<'a> = EnumProperty<'a, Axis>;
+
+/// The `facing` property containing all six directions.
+pub static FACING: DirectionProperty<'static> = Property::new("facing", EnumData::all());
+
+/// The `facing` property containing the four horizontal directions.
+pub static HORIZONTAL_FACING: DirectionProperty<'static> =
+    Property::new("facing", EnumData(&Direction::HORIZONTAL));
+
+/// The `axis` property containing all three axes.
+pub static AXIS: AxisProperty<'static> = Property::new("axis", EnumData::all());
+
+/// The `axis` property containing the two horizontal axes.
+pub static HORIZONTAL_AXIS: AxisProperty<'static> =
+    Property::new("axis", EnumData(&[Axis::X, Axis::Z]));
@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+
+use super::{BiIndex, Wrap};
+
+/// Enum types whose variants could be represented by names.
+///
+/// See [`str_enum!`](crate::str_enum) for defining one.
+///
+/// # MCJE Reference
+///
+/// This trait represents `net.minecraft.util.StringIdentifiable` (yarn).
+#[doc(alias = "StringIdentifiable")]
+pub trait StrEnum: Copy + Eq + 'static {
+    /// All variants of this enum, in order.
+    const VARIANTS: &'static [Self];
+
+    /// Gets the name of this variant.
+    fn name(&self) -> &'static str;
+
+    /// Gets the ordinal of this variant in [`Self::VARIANTS`].
+    #[inline]
+    fn ordinal(&self) -> usize {
+        Self::VARIANTS
+            .iter()
+            .position(|v| v == self)
+            .expect("variant not present in VARIANTS")
+    }
+}
+
+/// Property data that wraps a set of enum variants.
+///
+/// The index of a value is its [ordinal](StrEnum::ordinal), so values
+/// are indexed identically across properties of the same enum type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Data<'a, T>(pub &'a [T]);
+
+impl<T> Data<'static, T>
+where
+    T: StrEnum,
+{
+    /// Creates a property data containing all variants of the enum.
+    #[inline]
+    pub const fn all() -> Self {
+        Self(T::VARIANTS)
+    }
+}
+
+impl<T> BiIndex<T> for Data<'_, T>
+where
+    T: StrEnum,
+{
+    #[inline]
+    fn index(&self, index: isize) -> Option<T> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| T::VARIANTS.get(i))
+            .filter(|v| self.0.contains(v))
+            .copied()
+    }
+
+    #[inline]
+    fn index_of(&self, value: &T) -> Option<isize> {
+        self.0.contains(value).then(|| value.ordinal() as isize)
+    }
+}
+
+impl<T> Wrap<T> for Data<'_, T>
+where
+    T: StrEnum,
+{
+    #[inline]
+    fn parse_name(&self, name: &str) -> Option<T> {
+        self.0.iter().find(|v| v.name() == name).copied()
+    }
+
+    #[inline]
+    fn to_name<'a>(&'a self, value: &T) -> Option<Cow<'a, str>> {
+        self.0.contains(value).then(|| Cow::Borrowed(value.name()))
+    }
+
+    #[inline]
+    fn variants(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<'s, T> IntoIterator for &Data<'s, T>
+where
+    T: Copy,
+{
+    type Item = T;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'s, T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().copied()
+    }
+}
+
+/// Defines an enum implementing [`StrEnum`].
+///
+/// # Examples
+///
+/// ```
+/// # use rimecraft_state::{str_enum, property::StrEnum};
+/// str_enum! {
+///     /// Type of a slab.
+///     pub enum SlabType {
+///         /// The top half.
+///         Top => "top",
+///         /// The bottom half.
+///         Bottom => "bottom",
+///         /// Both halves.
+///         Double => "double",
+///     }
+/// }
+///
+/// assert_eq!(SlabType::Double.name(), "double");
+/// assert_eq!(SlabType::VARIANTS.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! str_enum {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident {
+        $($(#[$vmeta:meta])* $variant:ident => $vname:literal),* $(,)?
+    }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $($(#[$vmeta])* $variant,)*
+        }
+
+        impl $crate::property::StrEnum for $name {
+            const VARIANTS: &'static [Self] = &[$(Self::$variant),*];
+
+            #[inline]
+            fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $vname),*
+                }
+            }
+
+            #[inline]
+            fn ordinal(&self) -> usize {
+                *self as usize
+            }
+        }
+    };
+}
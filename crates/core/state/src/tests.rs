@@ -1,9 +1,9 @@
 use crate::{
     property::{
-        data::{BoolData, IntData},
-        BoolProperty, IntProperty,
+        data::{BoolData, EnumData, IntData},
+        BoolProperty, EnumProperty, IntProperty, Wrap as _,
     },
-    StatesMut,
+    str_enum, StatesMut,
 };
 
 static INT_PROPERTY: IntProperty<'static> = IntProperty::new("int_property", IntData(1..=3));
@@ -37,3 +37,125 @@ fn with_cycle() {
     let state = state.cycle(&BOOL_PROPERTY).unwrap();
     assert_eq!(state.get(&BOOL_PROPERTY), Some(false));
 }
+
+str_enum! {
+    enum Half {
+        Top => "top",
+        Bottom => "bottom",
+    }
+}
+
+static HALF_PROPERTY: EnumProperty<'static, Half> = EnumProperty::new("half", EnumData::all());
+
+#[test]
+fn enum_property() {
+    let mut states = StatesMut::new(());
+    states.add(&HALF_PROPERTY).unwrap();
+    states.add(&BOOL_PROPERTY).unwrap();
+    let states = states.freeze();
+    assert_eq!(states.len(), 4);
+
+    let state = states.default_state();
+    assert_eq!(state.get(&HALF_PROPERTY), Some(Half::Top));
+    let state = state.cycle(&HALF_PROPERTY).unwrap();
+    assert_eq!(state.get(&HALF_PROPERTY), Some(Half::Bottom));
+    let state = state.with(&HALF_PROPERTY, Half::Top).unwrap();
+    assert_eq!(state.get(&HALF_PROPERTY), Some(Half::Top));
+
+    assert_eq!(HALF_PROPERTY.wrap.parse_name("bottom"), Some(Half::Bottom));
+    assert_eq!(HALF_PROPERTY.wrap.parse_name("middle"), None);
+    assert_eq!(
+        HALF_PROPERTY.wrap.to_name(&Half::Top).as_deref(),
+        Some("top")
+    );
+}
+
+#[cfg(feature = "voxel-math")]
+#[test]
+fn direction_property() {
+    use rimecraft_voxel_math::direction::Direction;
+
+    use crate::property::direction::{FACING, HORIZONTAL_FACING};
+
+    let mut states = StatesMut::new(());
+    states.add(&HORIZONTAL_FACING).unwrap();
+    let states = states.freeze();
+    assert_eq!(states.len(), 4);
+
+    let state = states.default_state();
+    assert_eq!(state.get(&HORIZONTAL_FACING), Some(Direction::North));
+    assert!(state.with(&HORIZONTAL_FACING, Direction::Up).is_err());
+    let state = state.cycle(&HORIZONTAL_FACING).unwrap();
+    assert_eq!(state.get(&HORIZONTAL_FACING), Some(Direction::East));
+    // Properties of the same enum type share indices.
+    assert_eq!(state.get(&FACING), Some(Direction::East));
+    // Cycling through another property follows its own order.
+    let state = states.default_state().cycle(&FACING).unwrap();
+    assert_eq!(state.get(&HORIZONTAL_FACING), Some(Direction::South));
+
+    assert_eq!(HORIZONTAL_FACING.wrap.parse_name("down"), None);
+    assert_eq!(FACING.wrap.parse_name("down"), Some(Direction::Down));
+}
+
+#[test]
+fn with_name() {
+    let mut states = StatesMut::new(());
+    states.add(&INT_PROPERTY).unwrap();
+    states.add(&BOOL_PROPERTY).unwrap();
+    let states = states.freeze();
+
+    let state = states.default_state();
+    let state = state.with_name("int_property", "3").unwrap();
+    assert_eq!(state.get(&INT_PROPERTY), Some(3));
+    assert_eq!(state.get_name("int_property").as_deref(), Some("3"));
+    assert!(state.with_name("int_property", "4").is_err());
+    assert!(state.with_name("missing", "true").is_err());
+
+    let names = state.names();
+    assert_eq!(
+        names
+            .iter()
+            .map(|(n, v)| (*n, v.as_ref()))
+            .collect::<Vec<_>>(),
+        [("bool_property", "false"), ("int_property", "3")]
+    );
+}
+
+#[test]
+fn transitions() {
+    let mut states = StatesMut::new(());
+    states.add(&INT_PROPERTY).unwrap();
+    states.add(&BOOL_PROPERTY).unwrap();
+    states.add(&HALF_PROPERTY).unwrap();
+    let states = states.freeze();
+    assert_eq!(states.iter().len(), 12);
+
+    for (raw_id, state) in states.iter().enumerate() {
+        assert_eq!(state.raw_id(), raw_id);
+        let (int, bool, half) = state
+            .get_many((&INT_PROPERTY, &BOOL_PROPERTY, &HALF_PROPERTY))
+            .unwrap();
+        for value in 1..=3 {
+            let target = state.with(&INT_PROPERTY, value).unwrap();
+            assert_eq!(
+                target.get_many((&INT_PROPERTY, &BOOL_PROPERTY, &HALF_PROPERTY)),
+                Some((value, bool, half))
+            );
+        }
+        assert_eq!(
+            state.cycle(&INT_PROPERTY).unwrap().get(&INT_PROPERTY),
+            Some(int % 3 + 1)
+        );
+        assert!(std::ptr::eq(
+            state.with(&BOOL_PROPERTY, bool).unwrap(),
+            state
+        ));
+        assert!(state.with(&INT_PROPERTY, 4).is_err());
+    }
+
+    let found = states
+        .find(|s| s.get_many((&INT_PROPERTY, &HALF_PROPERTY)) == Some((3, Half::Bottom)))
+        .unwrap();
+    assert_eq!(found.get(&BOOL_PROPERTY), Some(false));
+    assert!(states.find(|s| s.get(&INT_PROPERTY) == Some(0)).is_none());
+}
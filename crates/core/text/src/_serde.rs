@@ -18,6 +18,7 @@ enum Component<'a, T, StyleExt> {
         content: T,
         #[serde(flatten)]
         style: Style<StyleExt>,
+        #[serde(default)]
         extra: Vec<RawText<T, StyleExt>>,
     },
 }
@@ -38,13 +39,14 @@ where
             content: &'a T,
             #[serde(flatten)]
             style: &'a Style<StyleExt>,
-            sibs: &'a [RawText<T, StyleExt>],
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            extra: &'a [RawText<T, StyleExt>],
         }
 
         Component {
             content: &self.content,
             style: &self.style,
-            sibs: &self.sibs,
+            extra: &self.sibs,
         }
         .serialize(serializer)
     }
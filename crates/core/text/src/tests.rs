@@ -1,5 +1,7 @@
 use crate::*;
 
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Content {
     text: String,
 }
@@ -35,3 +37,21 @@ fn display() {
         "Hello, world! Genshin Impact, a game by miHoYo, boot! opssw"
     );
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_extra() {
+    let mut text: RawText<_, ()> = Content::from("Hello, ").into();
+    text.push(Content::from("world!").into());
+
+    let json = serde_json::to_string(&text).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+        serde_json::json!({ "text": "Hello, ", "extra": [{ "text": "world!" }] })
+    );
+    assert_eq!(serde_json::from_str::<RawText<_, ()>>(&json).unwrap(), text);
+
+    let leaf: RawText<Content, ()> = serde_json::from_str(r#"{ "text": "leaf" }"#).unwrap();
+    assert!(leaf.sibs().is_empty());
+    assert_eq!(serde_json::to_string(&leaf).unwrap(), r#"{"text":"leaf"}"#);
+}
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use rimecraft_block::{Block, BlockState, ProvideStateIds, RawBlock};
 use rimecraft_chunk_palette::{
@@ -7,8 +7,10 @@ use rimecraft_chunk_palette::{
 };
 use rimecraft_fluid::{BlockStateExt as _, BsToFs};
 use rimecraft_registry::{ProvideRegistry, Registry};
+use rimecraft_voxel_math::{direction::Direction, IVec3};
+use rimecraft_voxel_shape::{BooleanOp, VoxelShapeSlice};
 
-use super::{internal_types::*, ChunkCx};
+use super::{internal_types::*, ChunkCx, BORDER_LEN};
 
 /// Section on a `Chunk`.
 pub struct ChunkSection<'w, Cx>
@@ -151,6 +153,65 @@ where
     {
         self.block_state(x, y, z).map(Cx::block_to_fluid_state)
     }
+
+    /// Whether the side towards given direction of the block at given position
+    /// is covered by its opaque neighbor in this section.
+    ///
+    /// `shape` gets the culling shape of a block state. Sides facing outside
+    /// this section are never covered.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `Block.shouldDrawSide` (yarn) with the result inverted.
+    pub fn is_side_covered<F>(
+        &self,
+        x: u32,
+        y: u32,
+        z: u32,
+        direction: Direction,
+        mut shape: F,
+    ) -> bool
+    where
+        F: FnMut(&BlockState<'w, Cx>) -> Arc<VoxelShapeSlice<'static>>,
+    {
+        let Some(state) = self.block_state(x, y, z) else {
+            return false;
+        };
+        let pos = IVec3::new(x as i32, y as i32, z as i32) + IVec3::from(direction);
+        if pos.min_element() < 0 || pos.max_element() >= BORDER_LEN as i32 {
+            return false;
+        }
+        let Some(neighbor) = self.block_state(pos.x as u32, pos.y as u32, pos.z as u32) else {
+            return false;
+        };
+        neighbor.block.settings().opaque
+            && rimecraft_voxel_shape::is_side_covered(&shape(&state), &shape(&neighbor), direction)
+    }
+
+    /// Whether the block at given position is opaque and its shape is a full cube.
+    ///
+    /// `shape` gets the culling shape of a block state.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `AbstractBlockState.isOpaqueFullCube` (yarn).
+    pub fn is_opaque_full_cube<F>(&self, x: u32, y: u32, z: u32, shape: F) -> bool
+    where
+        F: FnOnce(&BlockState<'w, Cx>) -> Arc<VoxelShapeSlice<'static>>,
+    {
+        self.block_state(x, y, z).is_some_and(|state| {
+            if !state.block.settings().opaque {
+                return false;
+            }
+            let shape = shape(&state);
+            shape.is_full_cube()
+                || !rimecraft_voxel_shape::matches_anywhere(
+                    &rimecraft_voxel_shape::full_cube(),
+                    &shape,
+                    BooleanOp::NotSame,
+                )
+        })
+    }
 }
 
 impl<'w, Cx> ChunkSection<'w, Cx>
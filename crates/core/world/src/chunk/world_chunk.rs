@@ -1,20 +1,22 @@
 //! World chunks.
 
 use parking_lot::RwLock;
-use rimecraft_block::BlockState;
+use rimecraft_block::{behave::ProvideShapes, BlockState};
 use rimecraft_block_entity::{
     component::RawErasedComponentType, BlockEntity, DynRawBlockEntityType, ProvideBlockEntity,
 };
 use rimecraft_chunk_palette::{Maybe, SimpleOwned};
-use rimecraft_fluid::{BsToFs, FluidState};
+use rimecraft_fluid::{behave::ProvideFluidBehavior, BsToFs, FluidState};
 use rimecraft_registry::ProvideRegistry;
 use rimecraft_voxel_math::{BlockPos, IVec3};
+use rimecraft_voxel_shape::VoxelShapeSlice;
 use serde::{de::DeserializeSeed, Deserialize};
 
 use crate::{
-    heightmap,
+    fluid, heightmap,
     view::block::{
-        BlockLuminanceView, BlockView, BlockViewMut, LockFreeBlockView, LockedBlockViewMut,
+        BlockCollisionView, BlockLuminanceView, BlockRaycastView, BlockView, BlockViewMut,
+        LockFreeBlockView, LockedBlockViewMut,
     },
     Sealed,
 };
@@ -543,6 +545,53 @@ where
     }
 }
 
+impl<'w, Cx> BlockCollisionView<'w, Cx> for WorldChunk<'w, Cx>
+where
+    Cx: ChunkCx<'w>
+        + ComputeIndex<Cx::BlockStateList, BlockState<'w, Cx>>
+        + BsToFs<'w>
+        + ProvideRegistry<'w, Cx::Id, DynRawBlockEntityType<'w, Cx>>
+        + ProvideRegistry<'w, Cx::Id, RawErasedComponentType<'w, Cx>>,
+    Cx::BlockStateExt: ProvideBlockEntity<'w, Cx> + ProvideShapes<Self>,
+    Cx::Id: for<'de> Deserialize<'de>,
+{
+    fn peek_collision_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+    where
+        F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T,
+    {
+        let state = self.peek_block_state(pos, BlockState::clone)?;
+        Some(pk(&state.collision_shape(self, pos)))
+    }
+}
+
+impl<'w, Cx> BlockRaycastView<'w, Cx> for WorldChunk<'w, Cx>
+where
+    Cx: ChunkCx<'w>
+        + ComputeIndex<Cx::BlockStateList, BlockState<'w, Cx>>
+        + BsToFs<'w>
+        + ProvideRegistry<'w, Cx::Id, DynRawBlockEntityType<'w, Cx>>
+        + ProvideRegistry<'w, Cx::Id, RawErasedComponentType<'w, Cx>>,
+    Cx::BlockStateExt: ProvideBlockEntity<'w, Cx> + ProvideShapes<Self>,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::Id: for<'de> Deserialize<'de>,
+{
+    fn peek_outline_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+    where
+        F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T,
+    {
+        let state = self.peek_block_state(pos, BlockState::clone)?;
+        Some(pk(&state.outline_shape(self, pos)))
+    }
+
+    fn peek_fluid_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+    where
+        F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T,
+    {
+        let state = self.peek_fluid_state(pos, FluidState::clone)?;
+        Some(pk(&fluid::shape(self, pos, &state)))
+    }
+}
+
 impl<'w, Cx> Chunk<'w, Cx> for WorldChunk<'w, Cx>
 where
     Cx: ChunkCx<'w>
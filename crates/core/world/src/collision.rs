@@ -0,0 +1,80 @@
+//! Collision of moving boxes against blocks.
+
+use rimecraft_block::ProvideBlockStateExtTy;
+use rimecraft_fluid::ProvideFluidStateExtTy;
+use rimecraft_voxel_math::{direction::Axis, BBox, BlockPos, DVec3, IVec3};
+use rimecraft_voxel_shape::collision::{self, StepUp};
+
+use crate::view::block::BlockCollisionView;
+
+const EPSILON: f64 = 1.0E-7;
+
+/// Calculates how far given box could move along given axis before
+/// hitting any block collision shape in the view, up to `max_dist`.
+///
+/// Blocks on the edges of the swept box are only considered if their
+/// shapes exceed the unit cube.
+///
+/// See [`collision::calculate_max_offset`].
+pub fn calculate_max_offset<'w, Cx, V>(view: &V, axis: Axis, bbox: BBox, mut max_dist: f64) -> f64
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    if max_dist.abs() < EPSILON {
+        return 0.0;
+    }
+    let mut sweep = DVec3::ZERO;
+    sweep[axis as usize] = max_dist;
+    let swept = bbox.stretch(sweep);
+    let min = (swept.min() - EPSILON).floor().as_ivec3() - IVec3::ONE;
+    let max = (swept.max() + EPSILON).floor().as_ivec3() + IVec3::ONE;
+
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            for z in min.z..=max.z {
+                let edges = [(x, min.x, max.x), (y, min.y, max.y), (z, min.z, max.z)]
+                    .into_iter()
+                    .filter(|&(v, min, max)| v == min || v == max)
+                    .count();
+                if edges >= 2 {
+                    continue;
+                }
+                let pos = BlockPos::new(x, y, z);
+                let offset = DVec3::new(x as f64, y as f64, z as f64);
+                if let Some(dist) = view.peek_collision_shape(pos, |shape| {
+                    if edges == 1
+                        && Axis::ALL
+                            .into_iter()
+                            .all(|axis| shape.min(axis) >= 0.0 && shape.max(axis) <= 1.0)
+                    {
+                        max_dist
+                    } else {
+                        shape.calculate_max_distance(axis, bbox.offset(-offset), max_dist)
+                    }
+                }) {
+                    max_dist = dist;
+                }
+                if max_dist.abs() < EPSILON {
+                    return 0.0;
+                }
+            }
+        }
+    }
+    max_dist
+}
+
+/// Resolves the movement of given box against block collision shapes
+/// in the view, and returns the adjusted movement.
+///
+/// See [`collision::collide`].
+#[inline]
+pub fn collide<'w, Cx, V>(view: &V, bbox: BBox, movement: DVec3, step: StepUp) -> DVec3
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    collision::collide(bbox, movement, step, |axis, bbox, max_dist| {
+        calculate_max_offset(view, axis, bbox, max_dist)
+    })
+}
@@ -0,0 +1,589 @@
+//! Ticking and spreading of flowable fluids.
+//!
+//! Fluids are placed into and removed from the view as block states, see
+//! [`FsToBs`]. As block views have no neighbor updates, fluids around changed
+//! positions are scheduled to tick instead, see [`update_neighbors`].
+
+use ahash::AHashMap;
+use rimecraft_block::{behave::ProvideFluidFill, BlockState, ProvideBlockStateExtTy};
+use rimecraft_fluid::{
+    behave::{ProvideFlowingStates, ProvideFluidBehavior, MAX_LEVEL},
+    Fluid, FluidState, FsToBs, ProvideFluidStateExtTy,
+};
+use rimecraft_voxel_math::{direction::Direction, BBox, BlockPos, DVec3, IVec3};
+use rimecraft_voxel_shape::{adjacent_side_covers_square, VoxelShapeSlice};
+
+use crate::{
+    tick::{ScheduleTick, Tick},
+    view::block::{BlockCollisionView, BlockView, BlockViewMut},
+};
+
+/// Distance of flow searches that found no way down.
+const UNREACHABLE: u8 = u8::MAX;
+
+/// Ticks the fluid state at given position, which updates its level from
+/// its neighbors and then spreads it.
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.onScheduledTick` (yarn).
+pub fn on_scheduled_tick<'w, Cx, V>(view: &mut V, pos: BlockPos, state: FluidState<'w, Cx>)
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy + FsToBs<'w> + ProvideFlowingStates<'w>,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockViewMut<'w, Cx> + BlockCollisionView<'w, Cx> + ScheduleTick<Fluid<'w, Cx>> + ?Sized,
+{
+    let mut state = state;
+    if !state.is_still() {
+        let updated = updated_state(view, pos, &state);
+        if updated != state {
+            let delay = state.tick_rate();
+            state = updated;
+            view.set_block_state(pos, Cx::fluid_to_block_state(&state), false);
+            if !state.is_empty() {
+                schedule(view, pos, &state, delay);
+            }
+            update_neighbors(view, pos);
+        }
+    }
+    try_flow(view, pos, &state);
+}
+
+/// Schedules ticks of fluids next to given position, which stands in for
+/// neighbor updates after the block at the position changes.
+///
+/// # MCJE Reference
+///
+/// This represents `FluidBlock.neighborUpdate` (yarn).
+pub fn update_neighbors<'w, Cx, V>(view: &mut V, pos: BlockPos)
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    V: BlockViewMut<'w, Cx> + ScheduleTick<Fluid<'w, Cx>> + ?Sized,
+{
+    for direction in Direction::ALL {
+        let neighbor = pos.offset(direction);
+        if let Some(state) = view
+            .peek_fluid_state(neighbor, Clone::clone)
+            .filter(|state| !state.is_empty())
+        {
+            schedule(view, neighbor, &state, state.tick_rate());
+        }
+    }
+}
+
+/// Returns the velocity of the fluid state at given position, which points
+/// to the direction the fluid flows towards and is either normalized or zero.
+///
+/// Heights are compared between the fluid states themselves as in vanilla,
+/// ignoring fluids above them, unlike [`height`].
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.getVelocity` (yarn).
+pub fn velocity<'w, Cx, V>(view: &V, pos: BlockPos, state: &FluidState<'w, Cx>) -> DVec3
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    let height = state.height();
+    let is_empty_or_this = |fs: &FluidState<'w, Cx>| fs.is_empty() || fs.matches_type(state);
+    let mut velocity = DVec3::ZERO;
+    for direction in Direction::HORIZONTAL {
+        let neighbor = pos.offset(direction);
+        let Some(fs) = view.peek_fluid_state(neighbor, Clone::clone) else {
+            continue;
+        };
+        if !is_empty_or_this(&fs) {
+            continue;
+        }
+        let neighbor_height = fs.height();
+        let diff = if neighbor_height == 0.0 {
+            if blocks_movement(view, neighbor) {
+                0.0
+            } else {
+                view.peek_fluid_state(neighbor.down(), |below| {
+                    (is_empty_or_this(below) && below.height() > 0.0)
+                        .then(|| height - (below.height() - 8.0 / 9.0))
+                })
+                .flatten()
+                .unwrap_or(0.0)
+            }
+        } else {
+            height - neighbor_height
+        };
+        velocity += IVec3::from(direction).as_dvec3() * f64::from(diff);
+    }
+
+    if state.is_falling()
+        && Direction::HORIZONTAL.into_iter().any(|direction| {
+            let neighbor = pos.offset(direction);
+            is_flow_blocked(view, neighbor, direction, state)
+                || is_flow_blocked(view, neighbor.up(), direction, state)
+        })
+    {
+        velocity = velocity.normalize_or_zero() + DVec3::new(0.0, -6.0, 0.0);
+    }
+    velocity.normalize_or_zero()
+}
+
+/// Returns the height of the fluid state at given position, which is full
+/// if the same fluid is above it.
+///
+/// # MCJE Reference
+///
+/// This represents `FluidState.getHeight(BlockView, BlockPos)` (yarn).
+pub fn height<'w, Cx, V>(view: &V, pos: BlockPos, state: &FluidState<'w, Cx>) -> f32
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    V: BlockView<'w, Cx> + ?Sized,
+{
+    if view
+        .peek_fluid_state(pos.up(), |above| above.matches_type(state))
+        .unwrap_or(false)
+    {
+        1.0
+    } else {
+        state.height()
+    }
+}
+
+/// Returns the shape of the fluid state at given position, relative to the
+/// position.
+///
+/// # MCJE Reference
+///
+/// This represents `FluidState.getShape` (yarn).
+pub fn shape<'w, Cx, V>(
+    view: &V,
+    pos: BlockPos,
+    state: &FluidState<'w, Cx>,
+) -> Box<VoxelShapeSlice<'static>>
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    V: BlockView<'w, Cx> + ?Sized,
+{
+    if state.is_empty() {
+        rimecraft_voxel_shape::empty()
+    } else {
+        rimecraft_voxel_shape::cuboid(BBox::new(
+            DVec3::ZERO,
+            DVec3::new(1.0, f64::from(height(view, pos, state)), 1.0),
+        ))
+    }
+}
+
+/// Whether the side of the block at given position blocks the fluid from
+/// flowing along it.
+fn is_flow_blocked<'w, Cx, V>(
+    view: &V,
+    pos: BlockPos,
+    direction: Direction,
+    state: &FluidState<'w, Cx>,
+) -> bool
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    if view
+        .peek_fluid_state(pos, |fs| fs.matches_type(state))
+        .unwrap_or(false)
+    {
+        false
+    } else if direction == Direction::Up {
+        true
+    } else {
+        view.peek_collision_shape(pos, |shape| shape.is_face_full(direction))
+            .unwrap_or(true)
+    }
+}
+
+/// Computes the state the fluid at given position should be in, from the
+/// fluids of the same type around it.
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.getUpdatedState` (yarn).
+pub fn updated_state<'w, Cx, V>(
+    view: &V,
+    pos: BlockPos,
+    fluid: &FluidState<'w, Cx>,
+) -> FluidState<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy + ProvideFlowingStates<'w>,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    let mut max_level = 0;
+    let mut sources = 0;
+    for direction in Direction::HORIZONTAL {
+        let neighbor = pos.offset(direction);
+        let fs = fluid_state(view, neighbor);
+        if fs.matches_type(fluid) && receives_flow(view, direction, pos, neighbor) {
+            if fs.is_still() {
+                sources += 1;
+            }
+            max_level = max_level.max(fs.level());
+        }
+    }
+
+    let ext = fluid.state.data();
+    if ext.is_infinite() && sources >= 2 {
+        let below = pos.down();
+        if blocks_movement(view, below) || is_matching_and_still(fluid, &fluid_state(view, below)) {
+            return Cx::still_state(fluid, false);
+        }
+    }
+
+    let above = pos.up();
+    let fs = fluid_state(view, above);
+    if !fs.is_empty() && fs.matches_type(fluid) && receives_flow(view, Direction::Up, pos, above) {
+        return Cx::flowing_state(fluid, MAX_LEVEL, true);
+    }
+
+    match max_level.saturating_sub(ext.level_decrease_per_block()) {
+        0 => Cx::empty_state(),
+        level => Cx::flowing_state(fluid, level, false),
+    }
+}
+
+/// Spreads the fluid at given position downwards, or sideways if it can't.
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.tryFlow` (yarn).
+fn try_flow<'w, Cx, V>(view: &mut V, pos: BlockPos, state: &FluidState<'w, Cx>)
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy + FsToBs<'w> + ProvideFlowingStates<'w>,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockViewMut<'w, Cx> + BlockCollisionView<'w, Cx> + ScheduleTick<Fluid<'w, Cx>> + ?Sized,
+{
+    if state.is_empty() {
+        return;
+    }
+    let below = pos.down();
+    let updated = updated_state(view, below, state);
+    if can_flow(view, pos, Direction::Down, below, &updated) {
+        flow(view, below, &updated);
+        if count_neighboring_sources(view, pos, state) >= 3 {
+            flow_to_sides(view, pos, state);
+        }
+    } else if state.is_still() || !can_flow_down_to(view, state, pos, below) {
+        flow_to_sides(view, pos, state);
+    }
+}
+
+/// Spreads the fluid at given position sideways, towards the closest ways
+/// down.
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.flowToSides` (yarn).
+fn flow_to_sides<'w, Cx, V>(view: &mut V, pos: BlockPos, state: &FluidState<'w, Cx>)
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy + FsToBs<'w> + ProvideFlowingStates<'w>,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockViewMut<'w, Cx> + BlockCollisionView<'w, Cx> + ScheduleTick<Fluid<'w, Cx>> + ?Sized,
+{
+    let level = if state.is_falling() {
+        MAX_LEVEL - 1
+    } else {
+        state
+            .level()
+            .saturating_sub(state.state.data().level_decrease_per_block())
+    };
+    if level == 0 {
+        return;
+    }
+    for (direction, fs) in spread(view, pos, state) {
+        let to = pos.offset(direction);
+        if can_flow(view, pos, direction, to, &fs) {
+            flow(view, to, &fs);
+        }
+    }
+}
+
+/// Computes the directions the fluid at given position spreads towards,
+/// with the states it spreads as.
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.getSpread` (yarn).
+fn spread<'w, Cx, V>(
+    view: &V,
+    pos: BlockPos,
+    state: &FluidState<'w, Cx>,
+) -> Vec<(Direction, FluidState<'w, Cx>)>
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy + ProvideFlowingStates<'w>,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    let mut down_cache = AHashMap::new();
+    let mut min = UNREACHABLE;
+    let mut result = Vec::with_capacity(4);
+    for direction in Direction::HORIZONTAL {
+        let to = pos.offset(direction);
+        if !can_flow_through(view, state, pos, direction, to) {
+            continue;
+        }
+        let dist = if can_flow_down_to_cached(view, state, to, &mut down_cache) {
+            0
+        } else {
+            flow_distance(view, state, to, 1, direction.opposite(), &mut down_cache)
+        };
+        if dist < min {
+            result.clear();
+        }
+        if dist <= min {
+            result.push((direction, updated_state(view, to, state)));
+            min = dist;
+        }
+    }
+    result
+}
+
+/// Searches the distance from given position to the closest way down,
+/// without going back towards `from`.
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.getFlowSpeedBetween` (yarn).
+fn flow_distance<'w, Cx, V>(
+    view: &V,
+    state: &FluidState<'w, Cx>,
+    pos: BlockPos,
+    distance: u8,
+    from: Direction,
+    down_cache: &mut AHashMap<BlockPos, bool>,
+) -> u8
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    let mut min = UNREACHABLE;
+    for direction in Direction::HORIZONTAL {
+        if direction == from {
+            continue;
+        }
+        let to = pos.offset(direction);
+        if !can_flow_through(view, state, pos, direction, to) {
+            continue;
+        }
+        if can_flow_down_to_cached(view, state, to, down_cache) {
+            return distance;
+        }
+        if distance >= state.state.data().max_flow_distance() {
+            continue;
+        }
+        min = min.min(flow_distance(
+            view,
+            state,
+            to,
+            distance + 1,
+            direction.opposite(),
+            down_cache,
+        ));
+    }
+    min
+}
+
+/// Places given fluid state at given position, and schedules it to tick.
+///
+/// # MCJE Reference
+///
+/// This represents `FlowableFluid.flow` (yarn).
+fn flow<'w, Cx, V>(view: &mut V, pos: BlockPos, state: &FluidState<'w, Cx>)
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy + FsToBs<'w>,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    V: BlockViewMut<'w, Cx> + ScheduleTick<Fluid<'w, Cx>> + ?Sized,
+{
+    view.set_block_state(pos, Cx::fluid_to_block_state(state), false);
+    schedule(view, pos, state, state.tick_rate());
+    update_neighbors(view, pos);
+}
+
+#[inline]
+fn schedule<'w, Cx, V>(view: &mut V, pos: BlockPos, state: &FluidState<'w, Cx>, delay: u32)
+where
+    Cx: ProvideFluidStateExtTy,
+    V: ScheduleTick<Fluid<'w, Cx>> + ?Sized,
+{
+    view.schedule_tick(Tick::new(state.fluid, pos).with_delay(delay as i32));
+}
+
+fn count_neighboring_sources<'w, Cx, V>(
+    view: &V,
+    pos: BlockPos,
+    state: &FluidState<'w, Cx>,
+) -> usize
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    Direction::HORIZONTAL
+        .into_iter()
+        .filter(|direction| {
+            view.peek_fluid_state(pos.offset(*direction), |fs| {
+                is_matching_and_still(state, fs)
+            })
+            .unwrap_or(false)
+        })
+        .count()
+}
+
+/// Whether the fluid at `pos` could flow into `to`, the adjacent position
+/// in given direction, as given state.
+fn can_flow<'w, Cx, V>(
+    view: &V,
+    pos: BlockPos,
+    direction: Direction,
+    to: BlockPos,
+    state: &FluidState<'w, Cx>,
+) -> bool
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    view.peek_fluid_state(to, |fs| fs.can_be_replaced_with(state, direction))
+        .unwrap_or(false)
+        && receives_flow(view, direction, pos, to)
+        && can_fill(view, to)
+}
+
+/// Whether the fluid could spread through `to` when searching for a way down.
+fn can_flow_through<'w, Cx, V>(
+    view: &V,
+    fluid: &FluidState<'w, Cx>,
+    pos: BlockPos,
+    direction: Direction,
+    to: BlockPos,
+) -> bool
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    !view
+        .peek_fluid_state(to, |fs| is_matching_and_still(fluid, fs))
+        .unwrap_or(true)
+        && receives_flow(view, direction, pos, to)
+        && can_fill(view, to)
+}
+
+fn can_flow_down_to_cached<'w, Cx, V>(
+    view: &V,
+    fluid: &FluidState<'w, Cx>,
+    pos: BlockPos,
+    cache: &mut AHashMap<BlockPos, bool>,
+) -> bool
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    *cache
+        .entry(pos)
+        .or_insert_with(|| can_flow_down_to(view, fluid, pos, pos.down()))
+}
+
+/// Whether the fluid at `pos` could flow down into `below`.
+fn can_flow_down_to<'w, Cx, V>(
+    view: &V,
+    fluid: &FluidState<'w, Cx>,
+    pos: BlockPos,
+    below: BlockPos,
+) -> bool
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    receives_flow(view, Direction::Down, pos, below)
+        && (view
+            .peek_fluid_state(below, |fs| fs.matches_type(fluid))
+            .unwrap_or(false)
+            || can_fill(view, below))
+}
+
+/// Whether the collision shapes between `pos` and `from`, the adjacent
+/// position in given direction, leave a gap for fluids to flow through.
+fn receives_flow<'w, Cx, V>(view: &V, direction: Direction, pos: BlockPos, from: BlockPos) -> bool
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    view.peek_collision_shape(pos, |shape| {
+        view.peek_collision_shape(from, |neighbor| {
+            !adjacent_side_covers_square(shape, neighbor, direction)
+        })
+    })
+    .flatten()
+    .unwrap_or(false)
+}
+
+/// Whether fluids could flow into the block at given position, replacing it.
+///
+/// Blocks are filled only if they [could be](ProvideFluidFill::can_fill) and
+/// do not block movement, as fluids are not able to waterlog blocks.
+#[inline]
+fn can_fill<'w, Cx, V>(view: &V, pos: BlockPos) -> bool
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    Cx::BlockStateExt: ProvideFluidFill,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    view.peek_block_state(pos, BlockState::can_fill)
+        .unwrap_or(false)
+        && !blocks_movement(view, pos)
+}
+
+/// Whether the block at given position has a collision shape, or is out of
+/// the view.
+#[inline]
+fn blocks_movement<'w, Cx, V>(view: &V, pos: BlockPos) -> bool
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    view.peek_collision_shape(pos, |shape| !shape.is_empty())
+        .unwrap_or(true)
+}
+
+#[inline]
+fn is_matching_and_still<Cx>(fluid: &FluidState<'_, Cx>, state: &FluidState<'_, Cx>) -> bool
+where
+    Cx: ProvideFluidStateExtTy,
+    Cx::FluidStateExt: ProvideFluidBehavior,
+{
+    state.state.data().matches_type(fluid.state.data()) && state.is_still()
+}
+
+/// Gets the fluid state at given position, or the empty state if the
+/// position is out of the view.
+#[inline]
+fn fluid_state<'w, Cx, V>(view: &V, pos: BlockPos) -> FluidState<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy + ProvideFlowingStates<'w>,
+    V: BlockCollisionView<'w, Cx> + ?Sized,
+{
+    view.peek_fluid_state(pos, Clone::clone)
+        .unwrap_or_else(Cx::empty_state)
+}
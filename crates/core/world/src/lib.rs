@@ -8,13 +8,19 @@
 //! and `BlockState`s, `FluidState`s and the `Biome` registry should be bound to this lifetime.
 
 pub mod chunk;
+pub mod collision;
 pub mod event;
+pub mod fluid;
 pub mod heightmap;
+pub mod raycast;
 pub mod tick;
 pub mod view;
 
 pub mod behave;
 
+#[cfg(test)]
+mod tests;
+
 use std::sync::Arc;
 
 pub use ahash::{AHashMap, AHashSet};
@@ -0,0 +1,43 @@
+//! Ray casting through blocks.
+
+use rimecraft_block::ProvideBlockStateExtTy;
+use rimecraft_fluid::ProvideFluidStateExtTy;
+use rimecraft_voxel_math::DVec3;
+use rimecraft_voxel_shape::raycast;
+
+pub use rimecraft_voxel_shape::raycast::BlockHitResult;
+
+use crate::view::block::BlockRaycastView;
+
+/// Casts a ray from `start` to `end` through blocks in the view, and returns
+/// the first hit on a block outline shape, or on a fluid shape if `fluids`
+/// is `true`.
+///
+/// A missed result at `end` is returned if nothing is hit.
+///
+/// # MCJE Reference
+///
+/// This represents `BlockView.raycast(RaycastContext)` (yarn).
+pub fn raycast<'w, Cx, V>(view: &V, start: DVec3, end: DVec3, fluids: bool) -> BlockHitResult
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+    V: BlockRaycastView<'w, Cx> + ?Sized,
+{
+    raycast::raycast(start, end, |pos| {
+        let block = view
+            .peek_outline_shape(pos, |shape| shape.raycast(start, end, pos))
+            .flatten();
+        let fluid = fluids
+            .then(|| view.peek_fluid_shape(pos, |shape| shape.raycast(start, end, pos)))
+            .flatten()
+            .flatten();
+        let dist = |hit: &Option<BlockHitResult>| {
+            hit.map_or(f64::MAX, |hit| start.distance_squared(hit.pos()))
+        };
+        if dist(&block) <= dist(&fluid) {
+            block
+        } else {
+            fluid
+        }
+    })
+}
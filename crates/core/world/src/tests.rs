@@ -0,0 +1,787 @@
+mod fluid {
+    use std::{ops::RangeInclusive, sync::OnceLock};
+
+    use rimecraft_block::{behave::ProvideFluidFill, BlockState, ProvideBlockStateExtTy, RawBlock};
+    use rimecraft_fluid::{
+        behave::{ProvideFlowingStates, ProvideFluidBehavior},
+        Fluid, FluidState, FsToBs, ProvideFluidStateExtTy, RawFluid,
+    };
+    use rimecraft_global_cx::{GlobalContext, ProvideIdTy};
+    use rimecraft_registry::{Reg, Registry, RegistryKey, RegistryMut};
+    use rimecraft_state::{
+        property::{data::BoolData, data::IntData, BoolProperty, IntProperty},
+        State, StatesMut,
+    };
+    use rimecraft_voxel_math::{direction::Direction, BlockPos, DVec3};
+    use rimecraft_voxel_shape::VoxelShapeSlice;
+
+    use crate::{
+        fluid::{height, on_scheduled_tick, update_neighbors, velocity},
+        tick::{ScheduleTick, Tick},
+        view::block::{BlockCollisionView, BlockView, BlockViewMut},
+        AHashMap, BlockEntityCell,
+    };
+
+    static BLOCK_LEVEL: IntProperty<'static> = IntProperty::new("level", IntData(0..=15));
+    static FLUID_LEVEL: IntProperty<'static> = IntProperty::new("level", IntData(1..=8));
+    static FALLING: BoolProperty<'static> = BoolProperty::new("falling", BoolData);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Kind {
+        Empty,
+        Water,
+        Lava,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum BlockExt {
+        Air,
+        Stone,
+        Sign,
+        Fluid(Kind),
+    }
+
+    impl ProvideFluidFill for BlockExt {
+        fn can_fill(&self, _state: &State<'_, Self>) -> bool {
+            !matches!(self, Self::Sign)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    struct FluidExt {
+        kind: Kind,
+        still: bool,
+    }
+
+    impl ProvideFluidBehavior for FluidExt {
+        fn level(&self, state: &State<'_, Self>) -> u8 {
+            match (self.kind, self.still) {
+                (Kind::Empty, _) => 0,
+                (_, true) => 8,
+                _ => state.get(&FLUID_LEVEL).unwrap() as u8,
+            }
+        }
+
+        fn is_still(&self, _state: &State<'_, Self>) -> bool {
+            self.still
+        }
+
+        fn is_falling(&self, state: &State<'_, Self>) -> bool {
+            state.get(&FALLING).unwrap_or(false)
+        }
+
+        fn tick_rate(&self) -> u32 {
+            match self.kind {
+                Kind::Empty => 0,
+                Kind::Water => 5,
+                Kind::Lava => 30,
+            }
+        }
+
+        fn matches_type(&self, other: &Self) -> bool {
+            self.kind != Kind::Empty && self.kind == other.kind
+        }
+
+        fn can_be_replaced_with(
+            &self,
+            state: &State<'_, Self>,
+            other: &State<'_, Self>,
+            direction: Direction,
+        ) -> bool {
+            match self.kind {
+                Kind::Empty => true,
+                Kind::Water => direction == Direction::Down && other.data().kind != Kind::Water,
+                Kind::Lava => self.height(state) >= 4.0 / 9.0 && other.data().kind == Kind::Water,
+            }
+        }
+
+        fn is_infinite(&self) -> bool {
+            self.kind == Kind::Water
+        }
+
+        fn max_flow_distance(&self) -> u8 {
+            if self.kind == Kind::Lava {
+                2
+            } else {
+                4
+            }
+        }
+
+        fn level_decrease_per_block(&self) -> u8 {
+            if self.kind == Kind::Lava {
+                2
+            } else {
+                1
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    enum Context {}
+
+    unsafe impl GlobalContext for Context {}
+
+    impl ProvideIdTy for Context {
+        type Id = String;
+    }
+
+    impl ProvideBlockStateExtTy for Context {
+        type BlockStateExt = BlockExt;
+    }
+
+    impl ProvideFluidStateExtTy for Context {
+        type FluidStateExt = FluidExt;
+    }
+
+    type Blocks = Registry<String, RawBlock<'static, Context>>;
+    type Fluids = Registry<String, RawFluid<'static, Context>>;
+
+    fn registries() -> (&'static Blocks, &'static Fluids) {
+        static REGISTRIES: OnceLock<(&'static Blocks, &'static Fluids)> = OnceLock::new();
+        *REGISTRIES.get_or_init(|| {
+            let mut blocks: RegistryMut<String, RawBlock<'static, Context>> =
+                RegistryMut::new(RegistryKey::new("root".to_owned(), "block".to_owned()));
+            let key = |id: &str| RegistryKey::new("block".to_owned(), id.to_owned());
+            let plain = |ext| StatesMut::new(ext).freeze();
+            blocks
+                .register_default(
+                    key("air"),
+                    RawBlock::new(Default::default(), plain(BlockExt::Air)),
+                )
+                .unwrap();
+            blocks
+                .register(
+                    key("stone"),
+                    RawBlock::new(Default::default(), plain(BlockExt::Stone)),
+                )
+                .unwrap();
+            blocks
+                .register(
+                    key("sign"),
+                    RawBlock::new(Default::default(), plain(BlockExt::Sign)),
+                )
+                .unwrap();
+            for (id, kind) in [("water", Kind::Water), ("lava", Kind::Lava)] {
+                let mut states = StatesMut::new(BlockExt::Fluid(kind));
+                states
+                    .add::<IntData<RangeInclusive<i32>>, i32>(&BLOCK_LEVEL)
+                    .unwrap();
+                blocks
+                    .register(key(id), RawBlock::new(Default::default(), states.freeze()))
+                    .unwrap();
+            }
+
+            let mut fluids: RegistryMut<String, RawFluid<'static, Context>> =
+                RegistryMut::new(RegistryKey::new("root".to_owned(), "fluid".to_owned()));
+            let key = |id: &str| RegistryKey::new("fluid".to_owned(), id.to_owned());
+            let empty = rimecraft_fluid::Settings {
+                is_empty: true,
+                ..Default::default()
+            };
+            let ext = |kind, still| FluidExt { kind, still };
+            fluids
+                .register_default(
+                    key("empty"),
+                    RawFluid::new(empty, StatesMut::new(ext(Kind::Empty, false)).freeze()),
+                )
+                .unwrap();
+            for (id, kind) in [("water", Kind::Water), ("lava", Kind::Lava)] {
+                let mut states = StatesMut::new(ext(kind, true));
+                states.add::<BoolData, bool>(&FALLING).unwrap();
+                fluids
+                    .register(key(id), RawFluid::new(Default::default(), states.freeze()))
+                    .unwrap();
+                let mut states = StatesMut::new(ext(kind, false));
+                states
+                    .add::<IntData<RangeInclusive<i32>>, i32>(&FLUID_LEVEL)
+                    .unwrap();
+                states.add::<BoolData, bool>(&FALLING).unwrap();
+                fluids
+                    .register(
+                        key(&format!("flowing_{id}")),
+                        RawFluid::new(Default::default(), states.freeze()),
+                    )
+                    .unwrap();
+            }
+
+            (
+                Box::leak(Box::new(blocks.into())),
+                Box::leak(Box::new(fluids.into())),
+            )
+        })
+    }
+
+    fn kind_name(kind: Kind) -> &'static str {
+        match kind {
+            Kind::Empty => "empty",
+            Kind::Water => "water",
+            Kind::Lava => "lava",
+        }
+    }
+
+    fn fluid_state(id: &str, level: Option<u8>, falling: bool) -> FluidState<'static, Context> {
+        let fluid = registries().1.get(&id.to_owned()).unwrap();
+        let state = Reg::into_inner(fluid)
+            .states()
+            .find(|state| {
+                level.is_none_or(|level| state.get(&FLUID_LEVEL) == Some(i32::from(level)))
+                    && state.get(&FALLING).unwrap_or(false) == falling
+            })
+            .unwrap();
+        FluidState { fluid, state }
+    }
+
+    impl ProvideFlowingStates<'static> for Context {
+        fn flowing_state(
+            fs: &FluidState<'static, Self>,
+            level: u8,
+            falling: bool,
+        ) -> FluidState<'static, Self> {
+            let id = format!("flowing_{}", kind_name(fs.state.data().kind));
+            fluid_state(&id, Some(level), falling)
+        }
+
+        fn still_state(fs: &FluidState<'static, Self>, falling: bool) -> FluidState<'static, Self> {
+            fluid_state(kind_name(fs.state.data().kind), None, falling)
+        }
+
+        fn empty_state() -> FluidState<'static, Self> {
+            fluid_state("empty", None, false)
+        }
+    }
+
+    impl FsToBs<'static> for Context {
+        fn fluid_to_block_state(fs: &FluidState<'static, Self>) -> BlockState<'static, Self> {
+            let kind = fs.state.data().kind;
+            if kind == Kind::Empty {
+                return block_state("air");
+            }
+            let level = if fs.is_still() {
+                0
+            } else {
+                8 - fs.level().min(8) + if fs.is_falling() { 8 } else { 0 }
+            };
+            let block = block_state(kind_name(kind));
+            BlockState {
+                block: block.block,
+                state: block.state.with(&BLOCK_LEVEL, i32::from(level)).unwrap(),
+            }
+        }
+    }
+
+    fn block_state(id: &str) -> BlockState<'static, Context> {
+        let block = registries().0.get(&id.to_owned()).unwrap();
+        BlockState {
+            block,
+            state: Reg::into_inner(block).states().default_state(),
+        }
+    }
+
+    fn to_fluid_state(bs: &BlockState<'static, Context>) -> FluidState<'static, Context> {
+        match *bs.state.data() {
+            BlockExt::Fluid(kind) => {
+                let level = bs.state.get(&BLOCK_LEVEL).unwrap() as u8;
+                match level {
+                    0 => fluid_state(kind_name(kind), None, false),
+                    1..=7 => fluid_state(
+                        &format!("flowing_{}", kind_name(kind)),
+                        Some(8 - level),
+                        false,
+                    ),
+                    _ => fluid_state(&format!("flowing_{}", kind_name(kind)), Some(8), true),
+                }
+            }
+            _ => fluid_state("empty", None, false),
+        }
+    }
+
+    /// An unbounded world with stone below Y 0.
+    #[derive(Default)]
+    struct World {
+        blocks: AHashMap<BlockPos, BlockState<'static, Context>>,
+        ticks: Vec<(u64, Tick<Fluid<'static, Context>>)>,
+        time: u64,
+    }
+
+    impl World {
+        fn block(&self, pos: BlockPos) -> BlockState<'static, Context> {
+            self.blocks.get(&pos).cloned().unwrap_or_else(|| {
+                if pos.y() < 0 {
+                    block_state("stone")
+                } else {
+                    block_state("air")
+                }
+            })
+        }
+
+        fn place(&mut self, pos: BlockPos, state: BlockState<'static, Context>) {
+            self.set_block_state(pos, state, false);
+            update_neighbors(self, pos);
+        }
+
+        fn place_source(&mut self, pos: BlockPos, id: &str) {
+            let fs = fluid_state(id, None, false);
+            self.place(pos, Context::fluid_to_block_state(&fs));
+            self.schedule_tick(Tick::new(fs.fluid, pos).with_delay(fs.tick_rate() as i32));
+        }
+
+        fn run(&mut self, ticks: u64) {
+            for _ in 0..ticks {
+                self.time += 1;
+                let (due, rest) = std::mem::take(&mut self.ticks)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|(time, _)| *time <= self.time);
+                self.ticks = rest;
+                for (_, tick) in due {
+                    let fs = to_fluid_state(&self.block(tick.pos()));
+                    if fs.fluid == *tick.ty() {
+                        on_scheduled_tick(self, tick.pos(), fs);
+                    }
+                }
+            }
+        }
+
+        fn fluid(&self, x: i32, y: i32, z: i32) -> (Kind, u8, bool) {
+            let fs = to_fluid_state(&self.block(BlockPos::new(x, y, z)));
+            (fs.state.data().kind, fs.level(), fs.is_still())
+        }
+    }
+
+    impl BlockView<'static, Context> for World {
+        fn peek_block_state<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s BlockState<'static, Context>) -> T,
+        {
+            Some(pk(&self.block(pos)))
+        }
+
+        fn peek_fluid_state<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s FluidState<'static, Context>) -> T,
+        {
+            Some(pk(&to_fluid_state(&self.block(pos))))
+        }
+
+        fn peek_block_entity<F, T>(&self, _pos: BlockPos, _pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s BlockEntityCell<'static, Context>) -> T,
+        {
+            None
+        }
+    }
+
+    impl BlockViewMut<'static, Context> for World {
+        fn set_block_state(
+            &mut self,
+            pos: BlockPos,
+            state: BlockState<'static, Context>,
+            _moved: bool,
+        ) -> Option<BlockState<'static, Context>> {
+            let old = self.block(pos);
+            self.blocks.insert(pos, state.clone());
+            (old != state).then_some(old)
+        }
+
+        fn set_block_entity(
+            &mut self,
+            _block_entity: Box<rimecraft_block_entity::BlockEntity<'static, Context>>,
+        ) {
+        }
+
+        fn remove_block_entity(
+            &mut self,
+            _pos: BlockPos,
+        ) -> Option<BlockEntityCell<'static, Context>> {
+            None
+        }
+    }
+
+    impl BlockCollisionView<'static, Context> for World {
+        fn peek_collision_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T,
+        {
+            let shape = match self.block(pos).state.data() {
+                BlockExt::Stone => rimecraft_voxel_shape::full_cube(),
+                BlockExt::Air | BlockExt::Sign | BlockExt::Fluid(_) => {
+                    rimecraft_voxel_shape::empty()
+                }
+            };
+            Some(pk(&shape))
+        }
+    }
+
+    impl ScheduleTick<Fluid<'static, Context>> for World {
+        fn schedule_tick(&mut self, tick: Tick<Fluid<'static, Context>>) {
+            if !self.ticks.iter().any(|(_, scheduled)| *scheduled == tick) {
+                self.ticks.push((self.time + tick.delay() as u64, tick));
+            }
+        }
+    }
+
+    #[test]
+    fn water_spreads() {
+        let mut world = World::default();
+        world.place_source(BlockPos::ORIGIN, "water");
+        world.run(100);
+
+        assert_eq!(world.fluid(0, 0, 0), (Kind::Water, 8, true));
+        assert_eq!(world.fluid(1, 0, 0), (Kind::Water, 7, false));
+        assert_eq!(world.fluid(-7, 0, 0), (Kind::Water, 1, false));
+        assert_eq!(world.fluid(3, 0, -4), (Kind::Water, 1, false));
+        assert_eq!(world.fluid(8, 0, 0).0, Kind::Empty);
+        assert_eq!(world.fluid(4, 0, 4).0, Kind::Empty);
+        assert!(world.ticks.is_empty());
+
+        // Flowing water flows away from the source.
+        let pos = BlockPos::new(1, 0, 0);
+        let fs = to_fluid_state(&world.block(pos));
+        assert_eq!(velocity(&world, pos, &fs), DVec3::X);
+        let source = to_fluid_state(&world.block(BlockPos::ORIGIN));
+        assert_eq!(velocity(&world, BlockPos::ORIGIN, &source), DVec3::ZERO);
+
+        // Removing the source drains the water.
+        world.place(BlockPos::ORIGIN, block_state("air"));
+        world.run(100);
+        assert!(world
+            .blocks
+            .values()
+            .all(|bs| matches!(bs.state.data(), BlockExt::Air)));
+    }
+
+    #[test]
+    fn infinite_water() {
+        let mut world = World::default();
+        world.place_source(BlockPos::new(-1, 0, 0), "water");
+        world.place_source(BlockPos::new(1, 0, 0), "water");
+        world.run(20);
+        assert_eq!(world.fluid(0, 0, 0), (Kind::Water, 8, true));
+
+        let mut world = World::default();
+        world.place_source(BlockPos::new(-1, 0, 0), "lava");
+        world.place_source(BlockPos::new(1, 0, 0), "lava");
+        world.run(200);
+        assert_eq!(world.fluid(0, 0, 0), (Kind::Lava, 6, false));
+    }
+
+    #[test]
+    fn lava_spreads() {
+        let mut world = World::default();
+        world.place_source(BlockPos::ORIGIN, "lava");
+        world.run(300);
+
+        assert_eq!(world.fluid(1, 0, 0), (Kind::Lava, 6, false));
+        assert_eq!(world.fluid(0, 0, 2), (Kind::Lava, 4, false));
+        assert_eq!(world.fluid(-2, 0, 1), (Kind::Lava, 2, false));
+        assert_eq!(world.fluid(0, 0, 4).0, Kind::Empty);
+        assert_eq!(world.fluid(2, 0, 2).0, Kind::Empty);
+    }
+
+    #[test]
+    fn flows_around_unfillable_blocks() {
+        let mut world = World::default();
+        let sign = BlockPos::new(1, 0, 0);
+        world.place(sign, block_state("sign"));
+        world.place_source(BlockPos::ORIGIN, "water");
+        world.run(100);
+
+        assert!(matches!(world.block(sign).state.data(), BlockExt::Sign));
+        assert_eq!(world.fluid(1, 0, 1), (Kind::Water, 6, false));
+        assert_eq!(world.fluid(2, 0, 0), (Kind::Water, 4, false));
+    }
+
+    #[test]
+    fn flows_towards_holes() {
+        let mut world = World::default();
+        let hole = BlockPos::new(3, -1, 0);
+        world.place(hole, block_state("air"));
+        world.place_source(BlockPos::ORIGIN, "water");
+        world.run(100);
+
+        for x in 1..=3 {
+            assert_eq!(world.fluid(x, 0, 0).0, Kind::Water);
+        }
+        assert_eq!(world.fluid(3, -1, 0), (Kind::Water, 8, false));
+        for (x, z) in [(-1, 0), (0, 1), (0, -1), (4, 0), (2, 1)] {
+            assert_eq!(world.fluid(x, 0, z).0, Kind::Empty, "at ({x}, {z})");
+        }
+
+        // Falling water spreads sideways from where it lands, while the source
+        // spreads sideways once the column below it is full.
+        let mut world = World::default();
+        world.place_source(BlockPos::new(0, 3, 0), "water");
+        world.run(100);
+        assert_eq!(world.fluid(0, 1, 0), (Kind::Water, 8, false));
+        assert_eq!(world.fluid(1, 3, 0), (Kind::Water, 7, false));
+        assert_eq!(world.fluid(2, 3, 0).0, Kind::Empty);
+        assert_eq!(world.fluid(1, 2, 0), (Kind::Water, 8, false));
+        assert_eq!(world.fluid(2, 0, 0), (Kind::Water, 7, false));
+        assert_eq!(world.fluid(3, 0, 0), (Kind::Water, 6, false));
+        assert_eq!(world.fluid(2, 1, 0).0, Kind::Empty);
+        let fs = to_fluid_state(&world.block(BlockPos::new(0, 1, 0)));
+        assert!(fs.is_falling());
+        assert_eq!(Reg::id(fs.fluid), "flowing_water");
+
+        // Fluids below the same fluid are full.
+        let pos = BlockPos::new(1, 3, 0);
+        let fs = to_fluid_state(&world.block(pos));
+        assert_eq!(height(&world, pos, &fs), 7.0 / 9.0);
+        let pos = BlockPos::new(0, 2, 0);
+        let fs = to_fluid_state(&world.block(pos));
+        assert_eq!(height(&world, pos, &fs), 1.0);
+    }
+}
+
+mod view {
+    use rimecraft_block::{BlockState, ProvideBlockStateExtTy};
+    use rimecraft_fluid::{FluidState, ProvideFluidStateExtTy};
+    use rimecraft_global_cx::{GlobalContext, ProvideIdTy};
+    use rimecraft_voxel_math::{direction::Direction, BBox, BlockPos, DVec3};
+    use rimecraft_voxel_shape::{collision::StepUp, VoxelShapeSlice};
+
+    use crate::{
+        collision::collide,
+        raycast::raycast,
+        view::block::{BlockCollisionView, BlockRaycastView, BlockView},
+        AHashMap, BlockEntityCell,
+    };
+
+    #[derive(Debug)]
+    enum Context {}
+
+    unsafe impl GlobalContext for Context {}
+
+    impl ProvideIdTy for Context {
+        type Id = String;
+    }
+
+    impl ProvideBlockStateExtTy for Context {
+        type BlockStateExt = ();
+    }
+
+    impl ProvideFluidStateExtTy for Context {
+        type FluidStateExt = ();
+    }
+
+    /// A world of bare block shapes and fluid heights, where absent blocks
+    /// have no shape.
+    #[derive(Default)]
+    struct World {
+        shapes: AHashMap<BlockPos, Box<VoxelShapeSlice<'static>>>,
+        fluids: AHashMap<BlockPos, f64>,
+    }
+
+    impl World {
+        fn with(mut self, x: i32, y: i32, z: i32, shape: Box<VoxelShapeSlice<'static>>) -> Self {
+            self.shapes.insert(BlockPos::new(x, y, z), shape);
+            self
+        }
+
+        fn with_fluid(mut self, x: i32, y: i32, z: i32, height: f64) -> Self {
+            self.fluids.insert(BlockPos::new(x, y, z), height);
+            self
+        }
+
+        /// Fills the 5x5 layer around the origin at Y -1 with full cubes.
+        fn floor(mut self) -> Self {
+            for x in -2..=2 {
+                for z in -2..=2 {
+                    self = self.with(x, -1, z, rimecraft_voxel_shape::full_cube());
+                }
+            }
+            self
+        }
+    }
+
+    impl BlockView<'static, Context> for World {
+        fn peek_block_state<F, T>(&self, _pos: BlockPos, _pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s BlockState<'static, Context>) -> T,
+        {
+            None
+        }
+
+        fn peek_fluid_state<F, T>(&self, _pos: BlockPos, _pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s FluidState<'static, Context>) -> T,
+        {
+            None
+        }
+
+        fn peek_block_entity<F, T>(&self, _pos: BlockPos, _pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s BlockEntityCell<'static, Context>) -> T,
+        {
+            None
+        }
+    }
+
+    impl BlockCollisionView<'static, Context> for World {
+        fn peek_collision_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T,
+        {
+            self.shapes.get(&pos).map(|shape| pk(shape))
+        }
+    }
+
+    impl BlockRaycastView<'static, Context> for World {
+        fn peek_outline_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T,
+        {
+            self.shapes.get(&pos).map(|shape| pk(shape))
+        }
+
+        fn peek_fluid_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+        where
+            F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T,
+        {
+            self.fluids
+                .get(&pos)
+                .map(|&height| pk(&cuboid([0.0, 0.0, 0.0], [1.0, height, 1.0])))
+        }
+    }
+
+    fn cuboid(min: [f64; 3], max: [f64; 3]) -> Box<VoxelShapeSlice<'static>> {
+        rimecraft_voxel_shape::cuboid(BBox::new(DVec3::from_array(min), DVec3::from_array(max)))
+    }
+
+    /// A player-sized box standing at the center of the origin block.
+    fn player() -> BBox {
+        BBox::new(DVec3::new(0.2, 0.0, 0.2), DVec3::new(0.8, 1.8, 0.8))
+    }
+
+    fn assert_near(a: DVec3, b: DVec3) {
+        assert!((a - b).length() < 1.0E-9, "{a} is not near {b}");
+    }
+
+    #[test]
+    fn collide_floor_and_walls() {
+        let world = World::default()
+            .floor()
+            .with(2, 0, 0, rimecraft_voxel_shape::full_cube())
+            .with(2, 1, 0, rimecraft_voxel_shape::full_cube());
+        let falling = player().offset(DVec3::new(0.0, 0.5, 0.0));
+        let step = StepUp::default();
+
+        // Falling onto the floor.
+        assert_near(
+            collide(&world, falling, DVec3::new(0.0, -1.0, 0.0), step),
+            DVec3::new(0.0, -0.5, 0.0),
+        );
+
+        // Walking into the wall, while free along the other axes.
+        assert_near(
+            collide(&world, player(), DVec3::new(2.0, 0.0, 0.5), step),
+            DVec3::new(1.2, 0.0, 0.5),
+        );
+        assert_near(
+            collide(&world, player(), DVec3::new(-1.0, 0.0, 0.0), step),
+            DVec3::new(-1.0, 0.0, 0.0),
+        );
+
+        // Falling diagonally against the wall.
+        assert_near(
+            collide(&world, falling, DVec3::new(2.0, -1.0, 0.0), step),
+            DVec3::new(1.2, -0.5, 0.0),
+        );
+
+        // Nothing to collide with.
+        let empty = World::default();
+        assert_near(
+            collide(&empty, player(), DVec3::new(3.0, -4.0, 5.0), step),
+            DVec3::new(3.0, -4.0, 5.0),
+        );
+    }
+
+    #[test]
+    fn collide_step_up() {
+        let world =
+            World::default()
+                .floor()
+                .with(1, 0, 0, cuboid([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+        let movement = DVec3::new(1.0, 0.0, 0.0);
+        let step = |height, on_ground| StepUp { height, on_ground };
+
+        // Stepping onto the slab while on ground.
+        assert_near(
+            collide(&world, player(), movement, step(0.6, true)),
+            DVec3::new(1.0, 0.5, 0.0),
+        );
+        // The slab is too high to step onto.
+        assert_near(
+            collide(&world, player(), movement, step(0.4, true)),
+            DVec3::new(0.2, 0.0, 0.0),
+        );
+        // Airborne boxes don't step up.
+        assert_near(
+            collide(&world, player(), movement, step(0.6, false)),
+            DVec3::new(0.2, 0.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn collide_tall_shapes() {
+        // A fence-like shape exceeding its block blocks boxes above the block.
+        let world = World::default().with(1, -1, 0, cuboid([0.0, 0.0, 0.0], [1.0, 1.5, 1.0]));
+        assert_near(
+            collide(
+                &world,
+                player(),
+                DVec3::new(1.0, 0.0, 0.0),
+                StepUp::default(),
+            ),
+            DVec3::new(0.2, 0.0, 0.0),
+        );
+        assert_near(
+            collide(
+                &world,
+                player().offset(DVec3::new(0.0, 0.5, 0.0)),
+                DVec3::new(1.0, 0.0, 0.0),
+                StepUp::default(),
+            ),
+            DVec3::new(1.0, 0.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn raycast_blocks_and_fluids() {
+        let world = World::default()
+            .with(0, 0, 0, cuboid([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]))
+            .with(3, 0, 0, rimecraft_voxel_shape::full_cube())
+            .with_fluid(0, 1, 0, 0.875);
+        let start = DVec3::new(0.5, 3.0, 0.5);
+        let end = DVec3::new(0.5, -3.0, 0.5);
+
+        // Passing through the fluid onto the slab.
+        let hit = raycast(&world, start, end, false);
+        assert!(!hit.is_missed());
+        assert_eq!(hit.block_pos(), BlockPos::ORIGIN);
+        assert_eq!(hit.side(), Direction::Up);
+        assert_near(hit.pos(), DVec3::new(0.5, 0.5, 0.5));
+
+        // Hitting the fluid surface.
+        let hit = raycast(&world, start, end, true);
+        assert_eq!(hit.block_pos(), BlockPos::new(0, 1, 0));
+        assert_eq!(hit.side(), Direction::Up);
+        assert_near(hit.pos(), DVec3::new(0.5, 1.875, 0.5));
+
+        // Passing above the slab into the cube.
+        let hit = raycast(
+            &world,
+            DVec3::new(-1.5, 0.75, 0.5),
+            DVec3::new(5.5, 0.75, 0.5),
+            false,
+        );
+        assert_eq!(hit.block_pos(), BlockPos::new(3, 0, 0));
+        assert_eq!(hit.side(), Direction::West);
+        assert_near(hit.pos(), DVec3::new(3.0, 0.75, 0.5));
+
+        // Missing everything.
+        let end = DVec3::new(-2.5, 0.5, 4.5);
+        let hit = raycast(&world, DVec3::new(-2.5, 3.0, 4.5), end, true);
+        assert!(hit.is_missed());
+        assert_eq!(hit.pos(), end);
+    }
+}
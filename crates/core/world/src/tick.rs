@@ -49,6 +49,30 @@ impl<T> Tick<T> {
     pub fn priority(&self) -> Priority {
         self.priority
     }
+
+    /// Returns this tick with given delay.
+    #[inline]
+    pub fn with_delay(self, delay: i32) -> Self {
+        Self { delay, ..self }
+    }
+
+    /// Returns this tick with given priority.
+    #[inline]
+    pub fn with_priority(self, priority: Priority) -> Self {
+        Self { priority, ..self }
+    }
+}
+
+/// Views that could schedule [`Tick`]s of type `T`.
+///
+/// Scheduling a tick equal to an already scheduled one should be a no-op.
+///
+/// # MCJE Reference
+///
+/// This represents `ScheduledTickView` (yarn).
+pub trait ScheduleTick<T> {
+    /// Schedules given tick, which is triggered after its delay.
+    fn schedule_tick(&mut self, tick: Tick<T>);
 }
 
 impl<T> PartialEq for Tick<T>
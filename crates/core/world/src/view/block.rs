@@ -4,6 +4,7 @@ use rimecraft_block::{BlockState, ProvideBlockStateExtTy};
 use rimecraft_block_entity::BlockEntity;
 use rimecraft_fluid::{FluidState, ProvideFluidStateExtTy};
 use rimecraft_voxel_math::BlockPos;
+use rimecraft_voxel_shape::VoxelShapeSlice;
 
 use crate::{BlockEntityCell, DEFAULT_MAX_LIGHT_LEVEL};
 
@@ -47,6 +48,39 @@ where
     }
 }
 
+/// View of block collision shapes.
+pub trait BlockCollisionView<'w, Cx>: BlockView<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+{
+    /// Peeks the collision shape of the block at the given position.
+    ///
+    /// The shape is relative to the position.
+    fn peek_collision_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+    where
+        F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T;
+}
+
+/// View of block shapes a ray could hit.
+pub trait BlockRaycastView<'w, Cx>: BlockView<'w, Cx>
+where
+    Cx: ProvideBlockStateExtTy + ProvideFluidStateExtTy,
+{
+    /// Peeks the outline shape of the block at the given position.
+    ///
+    /// The shape is relative to the position.
+    fn peek_outline_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+    where
+        F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T;
+
+    /// Peeks the shape of the fluid at the given position.
+    ///
+    /// The shape is relative to the position.
+    fn peek_fluid_shape<F, T>(&self, pos: BlockPos, pk: F) -> Option<T>
+    where
+        F: for<'s> FnOnce(&'s VoxelShapeSlice<'_>) -> T;
+}
+
 /// Lock-free variant of [`BlockView`].
 pub trait LockFreeBlockView<'w, Cx>: BlockView<'w, Cx>
 where
@@ -60,3 +60,30 @@ impl<'de> serde::Deserialize<'de> for Id {
         identifier::vanilla::Identifier::deserialize(deserializer).map(Self)
     }
 }
+
+#[cfg(feature = "edcode")]
+mod edcode {
+    use edcode2::{Buf, BufMut, Decode, Encode};
+
+    use super::Id;
+
+    impl<B> Encode<B> for Id
+    where
+        B: BufMut,
+    {
+        #[inline]
+        fn encode(&self, buf: B) -> Result<(), edcode2::BoxedError<'static>> {
+            self.0.encode(buf)
+        }
+    }
+
+    impl<'de, B> Decode<'de, B> for Id
+    where
+        B: Buf,
+    {
+        #[inline]
+        fn decode(buf: B) -> Result<Self, edcode2::BoxedError<'de>> {
+            identifier::vanilla::Identifier::decode(buf).map(Self)
+        }
+    }
+}
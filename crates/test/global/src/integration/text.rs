@@ -0,0 +1,39 @@
+//! `rimecraft-text` integrations.
+
+#![cfg(feature = "text")]
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use text::ProvideTextTy;
+
+use crate::TestContext;
+
+/// Literal text content.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Content {
+    /// The literal text.
+    pub text: String,
+}
+
+impl From<&str> for Content {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self {
+            text: value.to_owned(),
+        }
+    }
+}
+
+impl Display for Content {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl ProvideTextTy for TestContext {
+    type Content = Content;
+
+    type StyleExt = ();
+}
@@ -27,6 +27,7 @@ pub use ::registry as __priv_registry;
 /// Integration with several Rimecraft crates.
 pub mod integration {
     pub mod registry;
+    pub mod text;
 }
 
 pub use identifier::Id;
@@ -2,7 +2,10 @@ use std::hash::Hash;
 
 use glam::{DVec3, IVec3};
 
-use crate::BlockPos;
+use crate::{
+    direction::{Axis, AxisDirection, BlockMirror, BlockRotation, Direction},
+    BlockPos, BlockPosIter,
+};
 
 /// A 3D axis-aligned bounding box.
 ///
@@ -33,6 +36,277 @@ impl BBox {
     pub fn max(&self) -> DVec3 {
         self.max
     }
+
+    /// Returns this box moved by given offset.
+    #[inline]
+    pub fn offset(self, offset: DVec3) -> Self {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// Returns this box stretched by given vector, where the negative
+    /// components extend the minimum corner and the positive ones extend
+    /// the maximum corner.
+    #[inline]
+    pub fn stretch(self, v: DVec3) -> Self {
+        Self {
+            min: self.min + v.min(DVec3::ZERO),
+            max: self.max + v.max(DVec3::ZERO),
+        }
+    }
+
+    /// Returns this box shrunk by given vector, which is the inverse
+    /// of [`Self::stretch`].
+    #[inline]
+    pub fn shrink(self, v: DVec3) -> Self {
+        Self::new(self.min - v.min(DVec3::ZERO), self.max - v.max(DVec3::ZERO))
+    }
+
+    /// Returns this box expanded by given vector on both sides of each axis.
+    ///
+    /// Negative components contract the box, and the corners are swapped
+    /// if they cross each other.
+    #[inline]
+    pub fn expand(self, v: DVec3) -> Self {
+        Self::new(self.min - v, self.max + v)
+    }
+
+    /// Returns this box expanded by given value on all sides.
+    #[inline]
+    pub fn expand_all(self, value: f64) -> Self {
+        self.expand(DVec3::splat(value))
+    }
+
+    /// Returns this box contracted by given vector on both sides of each axis.
+    #[inline]
+    pub fn contract(self, v: DVec3) -> Self {
+        self.expand(-v)
+    }
+
+    /// Returns this box contracted by given value on all sides.
+    #[inline]
+    pub fn contract_all(self, value: f64) -> Self {
+        self.expand_all(-value)
+    }
+
+    /// Returns the smallest box containing both this box and the other one.
+    #[inline]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Returns the overlapping region of this box and the other one,
+    /// or `None` if they are disjoint.
+    ///
+    /// Boxes only touching each other result in a box with zero volume.
+    #[inline]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        min.cmple(max).all().then_some(Self { min, max })
+    }
+
+    /// Whether this box and the other one overlap with a positive volume.
+    #[inline]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.cmplt(other.max).all() && self.max.cmpgt(other.min).all()
+    }
+
+    /// Whether given point is inside this box, where the minimum corner
+    /// is inclusive and the maximum corner is exclusive.
+    #[inline]
+    pub fn contains(&self, point: DVec3) -> bool {
+        self.min.cmple(point).all() && point.cmplt(self.max).all()
+    }
+
+    /// Whether the other box is entirely inside this box.
+    #[inline]
+    pub fn contains_box(&self, other: &Self) -> bool {
+        self.min.cmple(other.min).all() && other.max.cmple(self.max).all()
+    }
+
+    /// Gets the minimum coordinate along given axis.
+    #[inline]
+    pub fn min_of(&self, axis: Axis) -> f64 {
+        self.min[axis as usize]
+    }
+
+    /// Gets the maximum coordinate along given axis.
+    #[inline]
+    pub fn max_of(&self, axis: Axis) -> f64 {
+        self.max[axis as usize]
+    }
+
+    /// Gets the lengths of this box along each axis.
+    #[inline]
+    pub fn size(&self) -> DVec3 {
+        self.max - self.min
+    }
+
+    /// Gets the length of this box along given axis.
+    #[inline]
+    pub fn len_of(&self, axis: Axis) -> f64 {
+        self.max_of(axis) - self.min_of(axis)
+    }
+
+    /// Gets the average length of the sides of this box.
+    #[inline]
+    pub fn average_side_len(&self) -> f64 {
+        self.size().element_sum() / 3.0
+    }
+
+    /// Gets the center of this box.
+    #[inline]
+    pub fn center(&self) -> DVec3 {
+        self.min.midpoint(self.max)
+    }
+
+    /// Gets the volume of this box.
+    #[inline]
+    pub fn volume(&self) -> f64 {
+        self.size().element_product()
+    }
+
+    /// Returns this box rotated around the vertical line through given
+    /// pivot point.
+    ///
+    /// Rotating the box of a block around the center of a pivot block
+    /// yields the box of the [rotated block](BlockPos::rotate).
+    pub fn rotate(self, rotation: BlockRotation, pivot: DVec3) -> Self {
+        let rotate = |p: DVec3| {
+            let d = p - pivot;
+            let (x, z) = match rotation {
+                BlockRotation::None => (d.x, d.z),
+                BlockRotation::Clockwise90 => (-d.z, d.x),
+                BlockRotation::Clockwise180 => (-d.x, -d.z),
+                BlockRotation::Counterclockwise90 => (d.z, -d.x),
+            };
+            pivot + DVec3::new(x, d.y, z)
+        };
+        Self::new(rotate(self.min), rotate(self.max))
+    }
+
+    /// Returns this box mirrored through the plane crossing given pivot
+    /// point.
+    pub fn mirror(self, mirror: BlockMirror, pivot: DVec3) -> Self {
+        let Some(axis) = mirror.axis() else {
+            return self;
+        };
+        let i = axis as usize;
+        let (mut min, mut max) = (self.min, self.max);
+        (min[i], max[i]) = (2.0 * pivot[i] - max[i], 2.0 * pivot[i] - min[i]);
+        Self { min, max }
+    }
+
+    /// Whether this box has zero volume.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.min.cmpeq(self.max).any()
+    }
+
+    /// Calculates how far the other box could move along given axis by
+    /// `offset` before hitting this box.
+    ///
+    /// The offset is only clipped if the boxes overlap on the other two axes,
+    /// and the other box is entirely on the side it moves from. The result
+    /// has the same sign as `offset` and is no further from zero.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `Box.calculateXOffset` and its friends in older
+    /// versions (yarn).
+    pub fn calculate_offset(&self, axis: Axis, other: &Self, offset: f64) -> f64 {
+        let i = axis as usize;
+        let overlaps = (0..3)
+            .filter(|&j| j != i)
+            .all(|j| other.max[j] > self.min[j] && other.min[j] < self.max[j]);
+        if !overlaps {
+            return offset;
+        }
+        if offset > 0.0 && other.max[i] <= self.min[i] {
+            offset.min(self.min[i] - other.max[i])
+        } else if offset < 0.0 && other.min[i] >= self.max[i] {
+            offset.max(self.max[i] - other.min[i])
+        } else {
+            offset
+        }
+    }
+
+    /// See [`Self::calculate_offset`].
+    #[inline]
+    pub fn calculate_x_offset(&self, other: &Self, offset: f64) -> f64 {
+        self.calculate_offset(Axis::X, other, offset)
+    }
+
+    /// See [`Self::calculate_offset`].
+    #[inline]
+    pub fn calculate_y_offset(&self, other: &Self, offset: f64) -> f64 {
+        self.calculate_offset(Axis::Y, other, offset)
+    }
+
+    /// See [`Self::calculate_offset`].
+    #[inline]
+    pub fn calculate_z_offset(&self, other: &Self, offset: f64) -> f64 {
+        self.calculate_offset(Axis::Z, other, offset)
+    }
+
+    /// Casts a ray from `start` to `end` against this box, and returns the point
+    /// where the segment enters this box with the side it enters through.
+    ///
+    /// Returns `None` if the segment misses this box or starts inside it.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `Box.raycast(Vec3d, Vec3d)` (yarn).
+    pub fn raycast(&self, start: DVec3, end: DVec3) -> Option<(DVec3, Direction)> {
+        let delta = end - start;
+        let mut result = None;
+        let mut max = 1.0;
+        for axis in [Axis::X, Axis::Y, Axis::Z] {
+            let i = axis as usize;
+            let (plane, dir) = if delta[i] > 0.0 {
+                (self.min[i], AxisDirection::Negative)
+            } else if delta[i] < 0.0 {
+                (self.max[i], AxisDirection::Positive)
+            } else {
+                continue;
+            };
+            let t = (plane - start[i]) / delta[i];
+            if !(0.0..=max).contains(&t) || result.is_some() && t == max {
+                continue;
+            }
+            let hit = start + delta * t;
+            if (0..3)
+                .filter(|&j| j != i)
+                .all(|j| self.min[j] <= hit[j] && hit[j] <= self.max[j])
+            {
+                max = t;
+                result = Some((hit, Direction::from((axis, dir))));
+            }
+        }
+        result
+    }
+
+    /// Whether the segment from `start` to `end` touches this box.
+    pub fn intersects_segment(&self, start: DVec3, end: DVec3) -> bool {
+        let inside = |p: DVec3| self.min.cmple(p).all() && p.cmple(self.max).all();
+        inside(start) || inside(end) || self.raycast(start, end).is_some()
+    }
+
+    /// Returns an iterator over positions of blocks this box overlaps.
+    ///
+    /// An axis along which this box has zero length is treated as
+    /// overlapping the block containing the coordinate.
+    pub fn block_positions(&self) -> BlockPosIter {
+        let min = self.min.floor();
+        let max = (self.max.ceil() - 1.0).max(min);
+        BlockPosIter::new(BlockPos(min.as_ivec3()), BlockPos(max.as_ivec3()))
+    }
 }
 
 impl Hash for BBox {
@@ -78,3 +352,206 @@ impl From<(BlockPos, BlockPos)> for BBox {
         Self::new(v1.into(), v2.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COORDS: [f64; 4] = [-1.0, 0.0, 0.5, 2.0];
+
+    /// All boxes with corners on the grid of [`COORDS`].
+    fn boxes() -> Vec<BBox> {
+        let ranges: Vec<_> = COORDS
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &a)| COORDS[i..].iter().map(move |&b| (a, b)))
+            .collect();
+        let mut boxes = Vec::new();
+        for &(x0, x1) in &ranges {
+            for &(y0, y1) in &ranges {
+                for &(z0, z1) in &ranges {
+                    boxes.push(BBox::new(DVec3::new(x0, y0, z0), DVec3::new(x1, y1, z1)));
+                }
+            }
+        }
+        boxes
+    }
+
+    #[test]
+    fn unary() {
+        for bbox in boxes() {
+            assert_eq!(bbox.size(), bbox.max() - bbox.min());
+            assert_eq!(bbox.is_empty(), bbox.volume() == 0.0);
+            assert_eq!(
+                bbox.average_side_len(),
+                (bbox.len_of(Axis::X) + bbox.len_of(Axis::Y) + bbox.len_of(Axis::Z)) / 3.0
+            );
+            assert!(bbox.contains_box(&bbox));
+            assert_eq!(bbox.contains(bbox.center()), !bbox.is_empty());
+            assert!(!bbox.contains(bbox.max()));
+
+            let v = DVec3::new(0.25, 1.0, 0.5);
+            assert_eq!(bbox.offset(v).size(), bbox.size());
+            assert_eq!(bbox.expand(v).contract(v), bbox);
+            assert_eq!(bbox.expand_all(0.5).contract_all(0.5), bbox);
+            assert!(bbox.expand(v).contains_box(&bbox));
+            assert_eq!(bbox.stretch(v).shrink(v), bbox);
+            assert_eq!(bbox.stretch(-v).shrink(-v), bbox);
+            assert_eq!(bbox.stretch(v).size(), bbox.size() + v);
+        }
+    }
+
+    #[test]
+    fn binary() {
+        let boxes = boxes();
+        for a in &boxes {
+            for b in &boxes {
+                let union = a.union(*b);
+                assert_eq!(union, b.union(*a));
+                assert!(union.contains_box(a) && union.contains_box(b));
+
+                let intersection = a.intersection(*b);
+                assert_eq!(intersection, b.intersection(*a));
+                if !a.is_empty() && !b.is_empty() {
+                    assert_eq!(
+                        a.intersects(b),
+                        intersection.is_some_and(|i| i.volume() > 0.0)
+                    );
+                }
+                if let Some(i) = intersection {
+                    assert!(a.contains_box(&i) && b.contains_box(&i));
+                }
+                if a.contains_box(b) {
+                    assert_eq!(intersection, Some(*b));
+                    assert_eq!(union, *a);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn offset() {
+        let boxes = boxes();
+        for a in boxes.iter().filter(|b| !b.is_empty()) {
+            for b in boxes.iter().filter(|b| !b.is_empty() && !b.intersects(a)) {
+                for axis in [Axis::X, Axis::Y, Axis::Z] {
+                    for offset in [-4.0, -0.5, 0.0, 0.5, 4.0] {
+                        let clipped = a.calculate_offset(axis, b, offset);
+                        assert!(clipped.abs() <= offset.abs());
+                        assert!(clipped * offset >= 0.0);
+                        let mut v = DVec3::ZERO;
+                        v[axis as usize] = clipped;
+                        // Moving box must not tunnel through or into the other box.
+                        assert!(!a.intersects(&b.offset(v)));
+                        assert!(!a.intersects(&b.stretch(v)));
+                    }
+                }
+            }
+        }
+
+        let wall = BBox::new(DVec3::new(1.0, 0.0, 0.0), DVec3::new(2.0, 1.0, 1.0));
+        let mover = BBox::new(DVec3::ZERO, DVec3::new(0.5, 1.0, 1.0));
+        assert_eq!(wall.calculate_x_offset(&mover, 1.0), 0.5);
+        assert_eq!(wall.calculate_x_offset(&mover, -1.0), -1.0);
+        assert_eq!(wall.calculate_y_offset(&mover, 1.0), 1.0);
+        assert_eq!(wall.calculate_z_offset(&mover, 1.0), 1.0);
+    }
+
+    #[test]
+    fn raycast() {
+        let boxes = boxes();
+        let origins = [
+            DVec3::new(-3.0, 0.25, 0.25),
+            DVec3::new(0.25, 5.0, 0.25),
+            DVec3::new(3.0, -3.0, 3.0),
+        ];
+        for bbox in boxes.iter().filter(|b| !b.is_empty()) {
+            for start in origins {
+                let end = bbox.center();
+                let (hit, side) = bbox
+                    .raycast(start, end)
+                    .expect("ray towards the center should hit");
+                assert!(bbox.expand_all(1.0E-9).contains_box(&BBox::new(hit, hit)));
+                let i = Axis::from(side) as usize;
+                let face = if AxisDirection::from(side) == AxisDirection::Positive {
+                    bbox.max()[i]
+                } else {
+                    bbox.min()[i]
+                };
+                assert!((hit[i] - face).abs() < 1.0E-9);
+                assert!(bbox.intersects_segment(start, end));
+                assert!(!bbox.intersects_segment(start, start + (start - end)));
+            }
+            // Starting inside.
+            assert!(bbox.raycast(bbox.center(), origins[0]).is_none());
+            assert!(bbox.intersects_segment(bbox.center(), origins[0]));
+        }
+    }
+
+    #[test]
+    fn block_positions() {
+        for bbox in boxes() {
+            let positions: Vec<_> = bbox.block_positions().collect();
+            assert_eq!(positions.len(), bbox.block_positions().len());
+            for pos in &positions {
+                let block = BBox::from(*pos);
+                if bbox.is_empty() {
+                    assert!(block.contains_box(&bbox.intersection(block).unwrap()));
+                } else {
+                    assert!(block.intersects(&bbox));
+                }
+            }
+            if !bbox.is_empty() {
+                let covered = positions
+                    .iter()
+                    .map(|&p| BBox::from(p).intersection(bbox).unwrap().volume())
+                    .sum::<f64>();
+                assert!((covered - bbox.volume()).abs() < 1.0E-9);
+            }
+        }
+
+        let bbox = BBox::new(DVec3::new(-0.5, 0.0, 0.0), DVec3::new(1.0, 2.0, 0.5));
+        let positions: Vec<_> = bbox.block_positions().collect();
+        assert_eq!(
+            positions,
+            [
+                BlockPos::new(-1, 0, 0),
+                BlockPos::new(0, 0, 0),
+                BlockPos::new(-1, 1, 0),
+                BlockPos::new(0, 1, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn transform() {
+        let pivot = BlockPos::new(2, 0, -1);
+        let block = |pos: BlockPos| BBox::new(pos.0.as_dvec3(), pos.0.as_dvec3() + 1.0);
+        for pos in [BlockPos::new(5, 3, 0), BlockPos::new(-1, 0, 4), pivot] {
+            for rotation in BlockRotation::ALL {
+                assert_eq!(
+                    block(pos).rotate(rotation, pivot.center()),
+                    block(pos.rotate(rotation, pivot))
+                );
+            }
+            for mirror in BlockMirror::ALL {
+                assert_eq!(
+                    block(pos).mirror(mirror, pivot.center()),
+                    block(pos.mirror(mirror, pivot))
+                );
+            }
+        }
+
+        for bbox in boxes() {
+            let pivot = DVec3::new(0.5, 0.0, -1.0);
+            for rotation in BlockRotation::ALL {
+                let rotated = bbox.rotate(rotation, pivot);
+                assert!((rotated.volume() - bbox.volume()).abs() < 1.0E-9);
+                assert_eq!(rotated.rotate(rotation.inverse(), pivot), bbox);
+            }
+            for mirror in BlockMirror::ALL {
+                assert_eq!(bbox.mirror(mirror, pivot).mirror(mirror, pivot), bbox);
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Position types.
 
-use glam::IVec3;
+use glam::{DVec3, IVec3};
+
+use crate::direction::{Axis, BlockMirror, BlockRotation, Direction};
 
 /// A position of a block in a three-dimensional volume.
 ///
@@ -35,6 +37,263 @@ impl BlockPos {
     pub const fn z(&self) -> i32 {
         self.0.z
     }
+
+    /// Returns the position of the block containing given point.
+    #[doc(alias = "of_floored")]
+    #[inline]
+    pub fn floored(point: DVec3) -> Self {
+        Self(point.floor().as_ivec3())
+    }
+
+    /// Returns the center point of the block.
+    #[inline]
+    pub fn center(self) -> DVec3 {
+        self.0.as_dvec3() + 0.5
+    }
+
+    /// Returns the position next to this one in given direction.
+    #[inline]
+    pub fn offset(self, direction: Direction) -> Self {
+        self + IVec3::from(direction)
+    }
+
+    /// Returns the position `n` blocks away from this one in given direction.
+    #[inline]
+    pub fn offset_n(self, direction: Direction, n: i32) -> Self {
+        self + IVec3::from(direction) * n
+    }
+
+    /// Returns the position `n` blocks away from this one along given axis.
+    #[inline]
+    pub fn offset_axis(self, axis: Axis, n: i32) -> Self {
+        let mut pos = self;
+        pos.0[axis as usize] += n;
+        pos
+    }
+
+    /// Returns the position above this one.
+    #[inline]
+    pub fn up(self) -> Self {
+        self.offset(Direction::Up)
+    }
+
+    /// Returns the position below this one.
+    #[inline]
+    pub fn down(self) -> Self {
+        self.offset(Direction::Down)
+    }
+
+    /// Returns the position north of this one.
+    #[inline]
+    pub fn north(self) -> Self {
+        self.offset(Direction::North)
+    }
+
+    /// Returns the position south of this one.
+    #[inline]
+    pub fn south(self) -> Self {
+        self.offset(Direction::South)
+    }
+
+    /// Returns the position west of this one.
+    #[inline]
+    pub fn west(self) -> Self {
+        self.offset(Direction::West)
+    }
+
+    /// Returns the position east of this one.
+    #[inline]
+    pub fn east(self) -> Self {
+        self.offset(Direction::East)
+    }
+
+    /// Returns this position rotated around the Y axis through given pivot.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockPos.rotate` and `StructureTemplate.transformAround` (yarn).
+    #[inline]
+    pub fn rotate(self, rotation: BlockRotation, pivot: Self) -> Self {
+        let d = self.0 - pivot.0;
+        let (x, z) = match rotation {
+            BlockRotation::None => (d.x, d.z),
+            BlockRotation::Clockwise90 => (-d.z, d.x),
+            BlockRotation::Clockwise180 => (-d.x, -d.z),
+            BlockRotation::Counterclockwise90 => (d.z, -d.x),
+        };
+        Self(pivot.0 + IVec3::new(x, d.y, z))
+    }
+
+    /// Returns this position mirrored through the plane crossing given pivot.
+    #[inline]
+    pub fn mirror(self, mirror: BlockMirror, pivot: Self) -> Self {
+        let mut pos = self.0;
+        if let Some(axis) = mirror.axis() {
+            let i = axis as usize;
+            pos[i] = 2 * pivot.0[i] - pos[i];
+        }
+        Self(pos)
+    }
+
+    /// Returns this position mirrored and then rotated around given pivot.
+    #[inline]
+    pub fn transform(self, mirror: BlockMirror, rotation: BlockRotation, pivot: Self) -> Self {
+        self.mirror(mirror, pivot).rotate(rotation, pivot)
+    }
+
+    /// Returns the Manhattan distance between this position and the other one.
+    #[inline]
+    pub fn manhattan_distance(self, other: Self) -> i32 {
+        (self.0 - other.0).abs().element_sum()
+    }
+
+    /// Returns the Chebyshev distance between this position and the other one.
+    #[inline]
+    pub fn chebyshev_distance(self, other: Self) -> i32 {
+        (self.0 - other.0).abs().max_element()
+    }
+
+    /// Returns the squared Euclidean distance between this position and the other one.
+    #[inline]
+    pub fn distance_squared(self, other: Self) -> i64 {
+        let d = (self.0 - other.0).as_i64vec3();
+        d.dot(d)
+    }
+
+    /// Packs this position into a long, with 26 bits of X, 12 bits of Y and
+    /// 26 bits of Z from the most significant bit.
+    ///
+    /// Coordinates out of the range are truncated.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockPos.asLong` (yarn).
+    #[inline]
+    pub const fn as_long(self) -> i64 {
+        let IVec3 { x, y, z } = self.0;
+        (x as i64 & BITS_X) << BIT_SHIFT_X
+            | (y as i64 & BITS_Y)
+            | (z as i64 & BITS_Z) << BIT_SHIFT_Z
+    }
+
+    /// Unpacks a position from a long packed by [`Self::as_long`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockPos.fromLong` (yarn).
+    #[inline]
+    pub const fn from_long(l: i64) -> Self {
+        Self::new(
+            (l << (64 - BIT_SHIFT_X - LEN_BITS_X) >> (64 - LEN_BITS_X)) as i32,
+            (l << (64 - LEN_BITS_Y) >> (64 - LEN_BITS_Y)) as i32,
+            (l << (64 - BIT_SHIFT_Z - LEN_BITS_Z) >> (64 - LEN_BITS_Z)) as i32,
+        )
+    }
+
+    /// Returns an iterator over positions between given corners, inclusively.
+    ///
+    /// See [`BlockPosIter`].
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockPos.iterate` (yarn).
+    #[inline]
+    pub fn iterate(p1: Self, p2: Self) -> BlockPosIter {
+        BlockPosIter::new(p1, p2)
+    }
+
+    /// Returns an iterator over positions around this one within given range
+    /// of each axis, in order of increasing Manhattan distance from this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the ranges is negative.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockPos.iterateOutwards` (yarn).
+    pub fn iterate_outwards(self, range_x: i32, range_y: i32, range_z: i32) -> OutwardIter {
+        assert!(
+            range_x >= 0 && range_y >= 0 && range_z >= 0,
+            "ranges must not be negative"
+        );
+        OutwardIter {
+            center: self.0,
+            range: IVec3::new(range_x, range_y, range_z),
+            distance: 0,
+            limit_x: 0,
+            limit_y: 0,
+            dx: 0,
+            dy: 0,
+            mirrored: None,
+        }
+    }
+
+    /// Returns an iterator spiraling out from this position in the square
+    /// within given radius on the plane of given directions, starting with
+    /// this position, then turning from `first` to `second` and their opposites.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two directions are on the same axis, or if the radius
+    /// is negative.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockPos.iterateInSquare` (yarn).
+    pub fn iterate_in_square(self, radius: i32, first: Direction, second: Direction) -> SpiralIter {
+        assert_ne!(
+            Axis::from(first),
+            Axis::from(second),
+            "the two directions cannot be on the same axis"
+        );
+        assert!(radius >= 0, "radius must not be negative");
+        SpiralIter {
+            directions: [first, second, first.opposite(), second.opposite()],
+            pos: self.offset(second).0,
+            max_turns: 4 * radius,
+            turns: -1,
+            max_steps: 0,
+            steps: 0,
+            done: false,
+        }
+    }
+}
+
+impl std::ops::Add for BlockPos {
+    type Output = BlockPos;
+
+    #[inline]
+    fn add(self, rhs: BlockPos) -> BlockPos {
+        BlockPos(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::Sub for BlockPos {
+    type Output = BlockPos;
+
+    #[inline]
+    fn sub(self, rhs: BlockPos) -> BlockPos {
+        BlockPos(self.0 - rhs.0)
+    }
+}
+
+impl std::ops::Mul<i32> for BlockPos {
+    type Output = BlockPos;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> BlockPos {
+        BlockPos(self.0 * rhs)
+    }
+}
+
+impl std::ops::Neg for BlockPos {
+    type Output = BlockPos;
+
+    #[inline]
+    fn neg(self) -> BlockPos {
+        BlockPos(-self.0)
+    }
 }
 
 impl From<IVec3> for BlockPos {
@@ -97,6 +356,155 @@ impl std::ops::SubAssign<IVec3> for BlockPos {
     }
 }
 
+/// Iterator over block positions in a cuboid region, where the x-coordinate
+/// changes fastest and the z-coordinate changes slowest.
+#[derive(Debug, Clone)]
+pub struct BlockPosIter {
+    min: IVec3,
+    max: IVec3,
+    next: Option<IVec3>,
+    remaining: usize,
+}
+
+impl BlockPosIter {
+    /// Creates an iterator over positions between given corners, inclusively.
+    ///
+    /// The corners could be in any order.
+    pub fn new(p1: BlockPos, p2: BlockPos) -> Self {
+        let (min, max) = (p1.0.min(p2.0), p1.0.max(p2.0));
+        let len = (max - min).as_i64vec3() + 1;
+        Self {
+            min,
+            max,
+            next: Some(min),
+            remaining: (len.x * len.y * len.z) as usize,
+        }
+    }
+}
+
+impl Iterator for BlockPosIter {
+    type Item = BlockPos;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.next?;
+        self.remaining -= 1;
+        self.next = if pos.x < self.max.x {
+            Some(IVec3::new(pos.x + 1, pos.y, pos.z))
+        } else if pos.y < self.max.y {
+            Some(IVec3::new(self.min.x, pos.y + 1, pos.z))
+        } else if pos.z < self.max.z {
+            Some(IVec3::new(self.min.x, self.min.y, pos.z + 1))
+        } else {
+            None
+        };
+        Some(BlockPos(pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for BlockPosIter {}
+
+impl std::iter::FusedIterator for BlockPosIter {}
+
+/// Iterator over positions around a center in order of increasing Manhattan
+/// distance from it.
+///
+/// See [`BlockPos::iterate_outwards`].
+#[derive(Debug, Clone)]
+pub struct OutwardIter {
+    center: IVec3,
+    range: IVec3,
+    distance: i32,
+    limit_x: i32,
+    limit_y: i32,
+    dx: i32,
+    dy: i32,
+    /// The position mirrored along the Z axis to be yielded next.
+    mirrored: Option<IVec3>,
+}
+
+impl Iterator for OutwardIter {
+    type Item = BlockPos;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pos) = self.mirrored.take() {
+            return Some(BlockPos(pos));
+        }
+        loop {
+            if self.dy > self.limit_y {
+                self.dx += 1;
+                if self.dx > self.limit_x {
+                    self.distance += 1;
+                    if self.distance > self.range.element_sum() {
+                        // Stay exhausted.
+                        self.distance -= 1;
+                        self.dx -= 1;
+                        return None;
+                    }
+                    self.limit_x = self.range.x.min(self.distance);
+                    self.dx = -self.limit_x;
+                }
+                self.limit_y = self.range.y.min(self.distance - self.dx.abs());
+                self.dy = -self.limit_y;
+            }
+            let (dx, dy) = (self.dx, self.dy);
+            self.dy += 1;
+            let dz = self.distance - dx.abs() - dy.abs();
+            if dz <= self.range.z {
+                let pos = self.center + IVec3::new(dx, dy, dz);
+                if dz != 0 {
+                    self.mirrored = Some(self.center + IVec3::new(dx, dy, -dz));
+                }
+                return Some(BlockPos(pos));
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for OutwardIter {}
+
+/// Iterator spiraling out from a center in a square.
+///
+/// See [`BlockPos::iterate_in_square`].
+#[derive(Debug, Clone)]
+pub struct SpiralIter {
+    directions: [Direction; 4],
+    pos: IVec3,
+    max_turns: i32,
+    turns: i32,
+    max_steps: i32,
+    steps: i32,
+    done: bool,
+}
+
+impl Iterator for SpiralIter {
+    type Item = BlockPos;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.pos += IVec3::from(self.directions[((self.turns + 4) % 4) as usize]);
+        if self.steps >= self.max_steps {
+            if self.turns >= self.max_turns {
+                self.done = true;
+                return None;
+            }
+            self.turns += 1;
+            self.steps = 0;
+            self.max_steps = self.turns / 2 + 1;
+        }
+        self.steps += 1;
+        Some(BlockPos(self.pos))
+    }
+}
+
+impl std::iter::FusedIterator for SpiralIter {}
+
 const LEN_BITS_X: i32 = 1 + (1i32 << (32 - (30000000i32 - 1).leading_zeros())).ilog2() as i32;
 const LEN_BITS_Y: i32 = 64 - LEN_BITS_X - LEN_BITS_Z;
 const LEN_BITS_Z: i32 = LEN_BITS_X;
@@ -110,22 +518,15 @@ const BIT_SHIFT_Z: i32 = LEN_BITS_Y;
 
 impl From<BlockPos> for i64 {
     #[inline]
-    fn from(BlockPos(IVec3 { x, y, z }): BlockPos) -> i64 {
-        let mut l = 0i64;
-        l |= (x as i64 & BITS_X) << BIT_SHIFT_X;
-        l |= y as i64 & BITS_Y;
-        l | (z as i64 & BITS_Z) << BIT_SHIFT_Z
+    fn from(pos: BlockPos) -> i64 {
+        pos.as_long()
     }
 }
 
 impl From<i64> for BlockPos {
     #[inline]
     fn from(l: i64) -> BlockPos {
-        Self(IVec3 {
-            x: (l << (64 - BIT_SHIFT_X - LEN_BITS_X) >> (64 - LEN_BITS_X)) as i32,
-            y: (l << (64 - LEN_BITS_Y) >> (64 - LEN_BITS_Y)) as i32,
-            z: (l << (64 - BIT_SHIFT_Z - LEN_BITS_Z) >> (64 - LEN_BITS_Z)) as i32,
-        })
+        BlockPos::from_long(l)
     }
 }
 
@@ -253,4 +654,163 @@ mod tests {
         let pos2: BlockPos = l.into();
         assert_eq!(pos, pos2);
     }
+
+    #[test]
+    fn iter() {
+        let iter = BlockPosIter::new(BlockPos::new(1, 2, 0), BlockPos::new(0, 0, 1));
+        assert_eq!(iter.len(), 12);
+        let positions: Vec<_> = iter.collect();
+        assert_eq!(positions.len(), 12);
+        assert_eq!(positions[0], BlockPos::new(0, 0, 0));
+        assert_eq!(positions[1], BlockPos::new(1, 0, 0));
+        assert_eq!(positions[2], BlockPos::new(0, 1, 0));
+        assert_eq!(positions[11], BlockPos::new(1, 2, 1));
+
+        let mut single = BlockPosIter::new(BlockPos::ORIGIN, BlockPos::ORIGIN);
+        assert_eq!(single.next(), Some(BlockPos::ORIGIN));
+        assert_eq!(single.next(), None);
+        assert_eq!(single.next(), None);
+    }
+
+    #[test]
+    fn packing() {
+        assert_eq!(BlockPos::new(1, 2, 3).as_long(), (1 << 38) | (3 << 12) | 2);
+        for pos in [
+            BlockPos::ORIGIN,
+            BlockPos::new(-1, -1, -1),
+            BlockPos::new((1 << 25) - 1, 2047, -(1 << 25)),
+            BlockPos::new(-(1 << 25), -2048, (1 << 25) - 1),
+            BlockPos::new(29_999_999, -64, -29_999_999),
+        ] {
+            assert_eq!(BlockPos::from_long(pos.as_long()), pos);
+            assert_eq!(BlockPos::from(i64::from(pos)), pos);
+        }
+    }
+
+    #[cfg(feature = "edcode")]
+    #[test]
+    fn edcode() {
+        use edcode2::{Decode, Encode};
+
+        let pos = BlockPos::new(-12, 300, 4096);
+        let mut buf: Vec<u8> = Vec::new();
+        pos.encode(&mut buf).expect("failed to encode");
+        assert_eq!(buf, pos.as_long().to_be_bytes());
+        assert_eq!(BlockPos::decode(&buf[..]).expect("failed to decode"), pos);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let pos = BlockPos::new(1, 2, 3);
+        assert_eq!(pos.up(), BlockPos::new(1, 3, 3));
+        assert_eq!(pos.down(), BlockPos::new(1, 1, 3));
+        assert_eq!(pos.north(), BlockPos::new(1, 2, 2));
+        assert_eq!(pos.south(), BlockPos::new(1, 2, 4));
+        assert_eq!(pos.west(), BlockPos::new(0, 2, 3));
+        assert_eq!(pos.east(), BlockPos::new(2, 2, 3));
+        assert_eq!(pos.offset_n(Direction::East, -3), BlockPos::new(-2, 2, 3));
+        assert_eq!(pos.offset_axis(Axis::Y, 5), BlockPos::new(1, 7, 3));
+        assert_eq!(pos + pos, pos * 2);
+        assert_eq!(pos - pos, BlockPos::ORIGIN);
+        assert_eq!(-pos + pos, BlockPos::ORIGIN);
+
+        let other = BlockPos::new(-2, 2, 7);
+        assert_eq!(pos.manhattan_distance(other), 7);
+        assert_eq!(pos.chebyshev_distance(other), 4);
+        assert_eq!(pos.distance_squared(other), 25);
+        assert_eq!(
+            BlockPos::floored(DVec3::new(-0.5, 1.0, 2.9)),
+            BlockPos::new(-1, 1, 2)
+        );
+        assert_eq!(pos.center(), DVec3::new(1.5, 2.5, 3.5));
+    }
+
+    #[test]
+    fn outwards() {
+        let center = BlockPos::new(5, -3, 8);
+        let positions: Vec<_> = center.iterate_outwards(2, 1, 3).collect();
+        assert_eq!(positions.len(), 5 * 3 * 7);
+        assert_eq!(positions[0], center);
+        assert!(positions
+            .windows(2)
+            .all(|w| center.manhattan_distance(w[0]) <= center.manhattan_distance(w[1])));
+        let mut sorted = positions.clone();
+        sorted.sort_by_key(|p| p.as_long());
+        sorted.dedup();
+        assert_eq!(sorted.len(), positions.len());
+        assert!(positions.iter().all(|p| {
+            let d = (p.0 - center.0).abs();
+            d.x <= 2 && d.y <= 1 && d.z <= 3
+        }));
+
+        let mut iter = center.iterate_outwards(0, 0, 0);
+        assert_eq!(iter.next(), Some(center));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn spiral() {
+        let center = BlockPos::new(0, 64, 0);
+        assert_eq!(
+            center
+                .iterate_in_square(0, Direction::North, Direction::East)
+                .collect::<Vec<_>>(),
+            [center]
+        );
+        for radius in 1..4 {
+            let positions: Vec<_> = center
+                .iterate_in_square(radius, Direction::North, Direction::East)
+                .collect();
+            let side = 2 * radius as usize + 1;
+            assert_eq!(positions.len(), side * side);
+            assert_eq!(positions[0], center);
+            assert_eq!(positions[1], center.north());
+            assert!(positions
+                .windows(2)
+                .all(|w| w[0].manhattan_distance(w[1]) == 1));
+            assert!(positions
+                .iter()
+                .all(|p| p.y() == 64 && center.chebyshev_distance(*p) <= radius));
+        }
+    }
+
+    #[test]
+    fn transform() {
+        let pivot = BlockPos::new(1, 5, 1);
+        let pos = BlockPos::new(3, 7, 0);
+        assert_eq!(
+            pos.rotate(BlockRotation::Clockwise90, pivot),
+            BlockPos::new(2, 7, 3)
+        );
+        assert_eq!(
+            pos.rotate(BlockRotation::Clockwise180, pivot),
+            BlockPos::new(-1, 7, 2)
+        );
+        assert_eq!(
+            pos.rotate(BlockRotation::Counterclockwise90, pivot),
+            BlockPos::new(0, 7, -1)
+        );
+        assert_eq!(
+            pos.mirror(BlockMirror::LeftRight, pivot),
+            BlockPos::new(3, 7, 2)
+        );
+        assert_eq!(
+            pos.mirror(BlockMirror::FrontBack, pivot),
+            BlockPos::new(-1, 7, 0)
+        );
+
+        // Offsets follow their directions through every transform.
+        for mirror in BlockMirror::ALL {
+            for rotation in BlockRotation::ALL {
+                for dir in Direction::ALL {
+                    assert_eq!(
+                        pos.offset(dir).transform(mirror, rotation, pivot),
+                        pos.transform(mirror, rotation, pivot)
+                            .offset(rotation.apply(mirror.apply(dir)))
+                    );
+                }
+            }
+        }
+    }
 }
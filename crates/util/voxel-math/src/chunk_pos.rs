@@ -1,3 +1,5 @@
+use crate::{direction::Direction, BlockPos, BlockPosIter, SpiralIter};
+
 /// A pair of two integers representing the X and Z coordinates of a chunk.
 ///
 /// Chunk positions are usually serialized as an [`u64`].
@@ -39,8 +41,179 @@ impl ChunkPos {
     pub const fn z(&self) -> i32 {
         self.z
     }
+
+    /// Returns the x-coordinate of the first block in the chunk.
+    #[inline]
+    pub const fn start_x(&self) -> i32 {
+        self.x << 4
+    }
+
+    /// Returns the z-coordinate of the first block in the chunk.
+    #[inline]
+    pub const fn start_z(&self) -> i32 {
+        self.z << 4
+    }
+
+    /// Returns the x-coordinate of the last block in the chunk.
+    #[inline]
+    pub const fn end_x(&self) -> i32 {
+        (self.x << 4) + 15
+    }
+
+    /// Returns the z-coordinate of the last block in the chunk.
+    #[inline]
+    pub const fn end_z(&self) -> i32 {
+        (self.z << 4) + 15
+    }
+
+    /// Returns the position of the block in the chunk at given offsets
+    /// from the first block of the chunk.
+    #[inline]
+    pub const fn block_pos(&self, offset_x: i32, y: i32, offset_z: i32) -> BlockPos {
+        BlockPos::new(self.start_x() + offset_x, y, self.start_z() + offset_z)
+    }
+
+    /// Returns the Chebyshev distance between this position and the other one.
+    #[inline]
+    pub const fn chebyshev_distance(&self, other: Self) -> i32 {
+        let dx = (self.x - other.x).abs();
+        let dz = (self.z - other.z).abs();
+        if dx > dz {
+            dx
+        } else {
+            dz
+        }
+    }
+
+    /// Returns the squared Euclidean distance between this position and the other one.
+    #[inline]
+    pub const fn distance_squared(&self, other: Self) -> i64 {
+        let dx = (self.x - other.x) as i64;
+        let dz = (self.z - other.z) as i64;
+        dx * dx + dz * dz
+    }
+
+    /// Packs this position into a long, with X in the lower 32 bits and
+    /// Z in the upper 32 bits.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ChunkPos.toLong` (yarn).
+    #[inline]
+    pub const fn as_long(&self) -> i64 {
+        (self.x as i64 & 0xFFFF_FFFF) | (self.z as i64 & 0xFFFF_FFFF) << 32
+    }
+
+    /// Unpacks a position from a long packed by [`Self::as_long`].
+    #[inline]
+    pub const fn from_long(l: i64) -> Self {
+        Self::new(l as i32, (l >> 32) as i32)
+    }
+
+    /// Returns an iterator over chunks in the square within given radius
+    /// around this chunk, where the x-coordinate changes fastest.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ChunkPos.stream(ChunkPos, int)` (yarn).
+    #[inline]
+    pub fn iterate_square(self, radius: i32) -> ChunkPosIter {
+        ChunkPosIter::new(
+            Self::new(self.x - radius, self.z - radius),
+            Self::new(self.x + radius, self.z + radius),
+        )
+    }
+
+    /// Returns an iterator spiraling out from this chunk in the square
+    /// within given radius, starting with this chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the radius is negative.
+    #[inline]
+    pub fn iterate_spiral(self, radius: i32) -> ChunkSpiralIter {
+        ChunkSpiralIter(BlockPos::new(self.x, 0, self.z).iterate_in_square(
+            radius,
+            Direction::North,
+            Direction::East,
+        ))
+    }
+}
+
+impl From<BlockPos> for ChunkPos {
+    #[inline]
+    fn from(pos: BlockPos) -> Self {
+        Self::new(pos.x() >> 4, pos.z() >> 4)
+    }
+}
+
+impl From<ChunkPos> for i64 {
+    #[inline]
+    fn from(pos: ChunkPos) -> Self {
+        pos.as_long()
+    }
+}
+
+impl From<i64> for ChunkPos {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::from_long(value)
+    }
+}
+
+/// Iterator over chunk positions in a rectangle, where the x-coordinate
+/// changes fastest.
+#[derive(Debug, Clone)]
+pub struct ChunkPosIter(BlockPosIter);
+
+impl ChunkPosIter {
+    /// Creates an iterator over positions between given corners, inclusively.
+    ///
+    /// The corners could be in any order.
+    #[inline]
+    pub fn new(p1: ChunkPos, p2: ChunkPos) -> Self {
+        Self(BlockPosIter::new(
+            BlockPos::new(p1.x, p1.z, 0),
+            BlockPos::new(p2.x, p2.z, 0),
+        ))
+    }
+}
+
+impl Iterator for ChunkPosIter {
+    type Item = ChunkPos;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|pos| ChunkPos::new(pos.x(), pos.y()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
 
+impl ExactSizeIterator for ChunkPosIter {}
+
+impl std::iter::FusedIterator for ChunkPosIter {}
+
+/// Iterator spiraling out from a chunk in a square.
+///
+/// See [`ChunkPos::iterate_spiral`].
+#[derive(Debug, Clone)]
+pub struct ChunkSpiralIter(SpiralIter);
+
+impl Iterator for ChunkSpiralIter {
+    type Item = ChunkPos;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|pos| ChunkPos::new(pos.x(), pos.z()))
+    }
+}
+
+impl std::iter::FusedIterator for ChunkSpiralIter {}
+
 impl From<(i32, i32)> for ChunkPos {
     #[inline]
     fn from((x, z): (i32, i32)) -> Self {
@@ -72,6 +245,80 @@ impl From<ChunkPos> for (i32, i32) {
     }
 }
 
+#[cfg(feature = "serde")]
+mod _serde {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    impl Serialize for ChunkPos {
+        /// Serializes the position as a sequence of two integers.
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            [self.x(), self.z()].serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ChunkPos {
+        /// Deserializes the position from a sequence of two integers
+        /// or a struct of two dimensions.
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = ChunkPos;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    formatter.write_str("a sequence of two integers, or a struct of two dimensions")
+                }
+
+                fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                where
+                    A: serde::de::SeqAccess<'de>,
+                {
+                    let x = seq.next_element()?.ok_or_else(|| {
+                        serde::de::Error::invalid_length(0, &"a sequence of two integers")
+                    })?;
+                    let z = seq.next_element()?.ok_or_else(|| {
+                        serde::de::Error::invalid_length(1, &"a sequence of two integers")
+                    })?;
+                    Ok(ChunkPos::new(x, z))
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: serde::de::MapAccess<'de>,
+                {
+                    use ::serde::de::Error;
+                    let mut x = None;
+                    let mut z = None;
+                    while let Some((k, v)) = map.next_entry::<&str, i32>()? {
+                        let Some(c) = k.chars().next() else {
+                            continue;
+                        };
+                        match c {
+                            'x' | 'X' => x = Some(v),
+                            'z' | 'Z' => z = Some(v),
+                            _ => return Err(Error::unknown_field(k, &["x", "z"])),
+                        }
+                    }
+                    let x = x.ok_or_else(|| Error::missing_field("x"))?;
+                    let z = z.ok_or_else(|| Error::missing_field("z"))?;
+                    Ok(ChunkPos::new(x, z))
+                }
+            }
+
+            deserializer.deserialize_any(Visitor)
+        }
+    }
+}
+
 #[cfg(feature = "edcode")]
 mod _edcode {
     use edcode2::{Buf, BufMut, Decode, Encode};
@@ -99,3 +346,58 @@ mod _edcode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing() {
+        for pos in [
+            ChunkPos::new(0, 0),
+            ChunkPos::new(-1, 1),
+            ChunkPos::new(i32::MIN, i32::MAX),
+            ChunkPos::new(1_875_000, -1_875_000),
+        ] {
+            assert_eq!(ChunkPos::from_long(pos.as_long()), pos);
+            assert_eq!(ChunkPos::from(u64::from(pos)), pos);
+            assert_eq!(u64::from(pos) as i64, pos.as_long());
+        }
+        assert_eq!(ChunkPos::new(1, 2).as_long(), (2 << 32) | 1);
+    }
+
+    #[test]
+    fn blocks() {
+        let pos = ChunkPos::from(BlockPos::new(-1, 64, 17));
+        assert_eq!(pos, ChunkPos::new(-1, 1));
+        assert_eq!((pos.start_x(), pos.end_x()), (-16, -1));
+        assert_eq!((pos.start_z(), pos.end_z()), (16, 31));
+        assert_eq!(pos.block_pos(3, 5, 7), BlockPos::new(-13, 5, 23));
+    }
+
+    #[test]
+    fn iteration() {
+        let center = ChunkPos::new(3, -2);
+        let square: Vec<_> = center.iterate_square(2).collect();
+        assert_eq!(square.len(), 25);
+        assert_eq!(square[0], ChunkPos::new(1, -4));
+        assert_eq!(square[1], ChunkPos::new(2, -4));
+        assert_eq!(square[24], ChunkPos::new(5, 0));
+        assert!(square.iter().all(|p| center.chebyshev_distance(*p) <= 2));
+
+        let spiral: Vec<_> = center.iterate_spiral(2).collect();
+        assert_eq!(spiral.len(), 25);
+        assert_eq!(spiral[0], center);
+        // Spiral visits rings in order of increasing distance.
+        assert!(spiral[..9]
+            .iter()
+            .all(|p| center.chebyshev_distance(*p) <= 1));
+        let mut sorted = spiral.clone();
+        sorted.sort_by_key(|p| p.as_long());
+        let mut expected = square;
+        expected.sort_by_key(|p| p.as_long());
+        assert_eq!(sorted, expected);
+
+        assert_eq!(center.distance_squared(ChunkPos::new(0, 2)), 25);
+    }
+}
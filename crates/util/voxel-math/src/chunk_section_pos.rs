@@ -1,5 +1,7 @@
 use glam::IVec3;
 
+use crate::{BlockPos, BlockPosIter, ChunkPos};
+
 /// Position of a chunk section.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct ChunkSectionPos(pub IVec3);
@@ -28,6 +30,90 @@ impl ChunkSectionPos {
     pub const fn z(&self) -> i32 {
         self.0.z
     }
+
+    /// Creates a new `ChunkSectionPos` from the given chunk position and
+    /// section Y coordinate.
+    #[inline]
+    pub const fn from_chunk(pos: ChunkPos, y: i32) -> Self {
+        Self::new(pos.x, y, pos.z)
+    }
+
+    /// Returns the position of the chunk this section is in.
+    #[inline]
+    pub const fn chunk_pos(&self) -> ChunkPos {
+        ChunkPos::new(self.0.x, self.0.z)
+    }
+
+    /// Returns the position of the first block in the section.
+    #[inline]
+    pub const fn min_pos(&self) -> BlockPos {
+        BlockPos::new(self.0.x << 4, self.0.y << 4, self.0.z << 4)
+    }
+
+    /// Returns the position of the last block in the section.
+    #[inline]
+    pub const fn max_pos(&self) -> BlockPos {
+        BlockPos::new(
+            (self.0.x << 4) + 15,
+            (self.0.y << 4) + 15,
+            (self.0.z << 4) + 15,
+        )
+    }
+
+    /// Returns the position of the center block of the section.
+    #[inline]
+    pub const fn center_pos(&self) -> BlockPos {
+        BlockPos::new(
+            (self.0.x << 4) + 8,
+            (self.0.y << 4) + 8,
+            (self.0.z << 4) + 8,
+        )
+    }
+
+    /// Returns an iterator over all block positions in the section.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ChunkSectionPos.streamBlocks` (yarn).
+    #[inline]
+    pub fn blocks(&self) -> BlockPosIter {
+        BlockPos::iterate(self.min_pos(), self.max_pos())
+    }
+
+    /// Returns the Chebyshev distance between two sections.
+    #[inline]
+    pub fn chebyshev_distance(&self, other: Self) -> i32 {
+        (self.0 - other.0).abs().max_element()
+    }
+
+    /// Packs the position into a long, with 22 bits for X and Z, and 20
+    /// bits for Y.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `ChunkSectionPos.asLong` (yarn).
+    #[inline]
+    pub const fn as_long(&self) -> i64 {
+        let IVec3 { x, y, z } = self.0;
+        (x as i64 & 0x003F_FFFF) << 42 | (y as i64 & 0x000F_FFFF) | (z as i64 & 0x003F_FFFF) << 20
+    }
+
+    /// Unpacks a position from a long packed by [`Self::as_long`].
+    #[inline]
+    pub const fn from_long(l: i64) -> Self {
+        Self::new(
+            (l >> 42) as i32,
+            (l << 44 >> 44) as i32,
+            (l << 22 >> 42) as i32,
+        )
+    }
+}
+
+impl From<BlockPos> for ChunkSectionPos {
+    #[inline]
+    fn from(pos: BlockPos) -> Self {
+        Self(pos.0 >> 4)
+    }
 }
 
 impl From<IVec3> for ChunkSectionPos {
@@ -93,21 +179,109 @@ impl std::ops::SubAssign<IVec3> for ChunkSectionPos {
 impl From<u64> for ChunkSectionPos {
     #[inline]
     fn from(value: u64) -> Self {
-        Self(IVec3 {
-            x: (value >> 42) as i32,
-            y: (value << 44 >> 44) as i32,
-            z: (value << 22 >> 42) as i32,
-        })
+        Self::from_long(value as i64)
     }
 }
 
 impl From<ChunkSectionPos> for u64 {
     #[inline]
-    fn from(ChunkSectionPos(IVec3 { x, y, z }): ChunkSectionPos) -> Self {
-        let mut l = 0u64;
-        l |= (x as u64 & 0x003F_FFFF) << 42;
-        l |= y as u64 & 0x000F_FFFF;
-        l | (z as u64 & 0x003F_FFFF) << 20
+    fn from(pos: ChunkSectionPos) -> Self {
+        pos.as_long() as u64
+    }
+}
+
+impl From<i64> for ChunkSectionPos {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::from_long(value)
+    }
+}
+
+impl From<ChunkSectionPos> for i64 {
+    #[inline]
+    fn from(pos: ChunkSectionPos) -> Self {
+        pos.as_long()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod _serde {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    impl Serialize for ChunkSectionPos {
+        /// Serializes the position as a sequence of three integers.
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            [self.x(), self.y(), self.z()].serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ChunkSectionPos {
+        /// Deserializes the position from a sequence of three integers
+        /// or a struct of three dimensions.
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = ChunkSectionPos;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    formatter
+                        .write_str("a sequence of three integers, or a struct of three dimensions")
+                }
+
+                fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                where
+                    A: serde::de::SeqAccess<'de>,
+                {
+                    let x = seq.next_element()?.ok_or_else(|| {
+                        serde::de::Error::invalid_length(0, &"a sequence of three integers")
+                    })?;
+                    let y = seq.next_element()?.ok_or_else(|| {
+                        serde::de::Error::invalid_length(1, &"a sequence of three integers")
+                    })?;
+                    let z = seq.next_element()?.ok_or_else(|| {
+                        serde::de::Error::invalid_length(2, &"a sequence of three integers")
+                    })?;
+                    Ok(ChunkSectionPos::new(x, y, z))
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: serde::de::MapAccess<'de>,
+                {
+                    use ::serde::de::Error;
+                    let mut x = None;
+                    let mut y = None;
+                    let mut z = None;
+                    while let Some((k, v)) = map.next_entry::<&str, i32>()? {
+                        let Some(c) = k.chars().next() else {
+                            continue;
+                        };
+                        match c {
+                            'x' | 'X' => x = Some(v),
+                            'y' | 'Y' => y = Some(v),
+                            'z' | 'Z' => z = Some(v),
+                            _ => return Err(Error::unknown_field(k, &["x", "y", "z"])),
+                        }
+                    }
+                    let x = x.ok_or_else(|| Error::missing_field("x"))?;
+                    let y = y.ok_or_else(|| Error::missing_field("y"))?;
+                    let z = z.ok_or_else(|| Error::missing_field("z"))?;
+                    Ok(ChunkSectionPos::new(x, y, z))
+                }
+            }
+
+            deserializer.deserialize_any(Visitor)
+        }
     }
 }
 
@@ -139,3 +313,41 @@ mod _edcode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing() {
+        for pos in [
+            ChunkSectionPos::new(0, 0, 0),
+            ChunkSectionPos::new(-1, -4, -1),
+            ChunkSectionPos::new(1_875_000, 19, -1_875_000),
+            ChunkSectionPos::new(-(1 << 21), -(1 << 19), (1 << 21) - 1),
+        ] {
+            assert_eq!(ChunkSectionPos::from_long(pos.as_long()), pos);
+            assert_eq!(ChunkSectionPos::from(u64::from(pos)), pos);
+        }
+        assert_eq!(
+            ChunkSectionPos::new(1, 2, 3).as_long(),
+            (1 << 42) | (3 << 20) | 2
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        let pos = ChunkSectionPos::from(BlockPos::new(-1, -64, 40));
+        assert_eq!(pos, ChunkSectionPos::new(-1, -4, 2));
+        assert_eq!(pos.chunk_pos(), ChunkPos::new(-1, 2));
+        assert_eq!(ChunkSectionPos::from_chunk(pos.chunk_pos(), -4), pos);
+        assert_eq!(pos.min_pos(), BlockPos::new(-16, -64, 32));
+        assert_eq!(pos.max_pos(), BlockPos::new(-1, -49, 47));
+        assert_eq!(pos.center_pos(), BlockPos::new(-8, -56, 40));
+
+        let blocks: Vec<_> = pos.blocks().collect();
+        assert_eq!(blocks.len(), 16 * 16 * 16);
+        assert!(blocks.iter().all(|b| ChunkSectionPos::from(*b) == pos));
+        assert_eq!(pos.chebyshev_distance(ChunkSectionPos::new(2, -3, 0)), 3);
+    }
+}
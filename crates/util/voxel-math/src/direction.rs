@@ -2,7 +2,7 @@
 
 use std::fmt;
 
-use glam::IVec3;
+use glam::{DVec3, IVec3};
 
 macro_rules! directions {
     ($($i:literal => $dir:ident: $doc:literal {
@@ -37,6 +37,14 @@ macro_rules! directions {
                     $(Self::$dir => Self::$oppo),*
                 }
             }
+
+            /// Gets the name of this direction.
+            #[inline]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$dir => $name),*
+                }
+            }
         }
 
         impl TryFrom<u8> for Direction {
@@ -183,6 +191,62 @@ directions! {
     },
 }
 
+impl Direction {
+    /// All horizontal directions, in clockwise order starting from north.
+    pub const HORIZONTAL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// Gets the direction closest to given vector.
+    ///
+    /// Returns [`Direction::North`] if the vector is zero.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `Direction.getFacing` (yarn).
+    pub fn from_vec(vec: DVec3) -> Self {
+        let mut result = Self::North;
+        let mut max = f64::MIN_POSITIVE;
+        for dir in Self::ALL {
+            let dot = IVec3::from(dir).as_dvec3().dot(vec);
+            if dot > max {
+                max = dot;
+                result = dir;
+            }
+        }
+        result
+    }
+}
+
+impl Direction {
+    /// Rotates this direction clockwise around the Y axis, seen from above.
+    ///
+    /// Vertical directions are returned unchanged.
+    #[inline]
+    pub const fn rotate_y_clockwise(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+            dir => dir,
+        }
+    }
+
+    /// Rotates this direction counterclockwise around the Y axis, seen
+    /// from above.
+    ///
+    /// Vertical directions are returned unchanged.
+    #[inline]
+    pub const fn rotate_y_counterclockwise(self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::West => Self::South,
+            Self::South => Self::East,
+            Self::East => Self::North,
+            dir => dir,
+        }
+    }
+}
+
 impl From<(AxisDirection, Axis)> for Direction {
     #[inline]
     fn from((ad, a): (AxisDirection, Axis)) -> Self {
@@ -204,6 +268,19 @@ pub enum Axis {
 }
 
 impl Axis {
+    /// All axes.
+    pub const ALL: [Self; 3] = [Self::X, Self::Y, Self::Z];
+
+    /// Gets the name of this axis.
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z",
+        }
+    }
+
     /// Chooses a value from a position based on the axis.
     #[inline]
     pub fn choose<T>(self, x: T, y: T, z: T) -> T {
@@ -319,6 +396,281 @@ impl From<EightWayDirection> for IVec3 {
     }
 }
 
+/// Rotation of blocks around the Y axis, in quarter turns.
+///
+/// # MCJE Reference
+///
+/// This type represents `net.minecraft.util.BlockRotation` (yarn).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[allow(clippy::exhaustive_enums)]
+#[repr(u8)]
+pub enum BlockRotation {
+    /// No rotation.
+    #[default]
+    None,
+    /// Rotation by 90 degrees clockwise, seen from above.
+    Clockwise90,
+    /// Rotation by 180 degrees.
+    Clockwise180,
+    /// Rotation by 90 degrees counterclockwise, seen from above.
+    Counterclockwise90,
+}
+
+impl BlockRotation {
+    /// All rotations, in order of clockwise quarter turns.
+    pub const ALL: [Self; 4] = [
+        Self::None,
+        Self::Clockwise90,
+        Self::Clockwise180,
+        Self::Counterclockwise90,
+    ];
+
+    /// Gets the name of this rotation.
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Clockwise90 => "clockwise_90",
+            Self::Clockwise180 => "180",
+            Self::Counterclockwise90 => "counterclockwise_90",
+        }
+    }
+
+    /// Gets the number of clockwise quarter turns of this rotation.
+    #[inline]
+    pub const fn quarter_turns(self) -> u8 {
+        self as u8
+    }
+
+    /// Gets the rotation of given number of clockwise quarter turns.
+    #[inline]
+    pub const fn from_quarter_turns(turns: i32) -> Self {
+        Self::ALL[turns.rem_euclid(4) as usize]
+    }
+
+    /// Returns the rotation of applying this rotation and then the
+    /// other one.
+    #[inline]
+    pub const fn compose(self, other: Self) -> Self {
+        Self::from_quarter_turns(self as i32 + other as i32)
+    }
+
+    /// Returns the rotation that undoes this rotation.
+    #[inline]
+    pub const fn inverse(self) -> Self {
+        Self::from_quarter_turns(-(self as i32))
+    }
+
+    /// Rotates the given direction.
+    ///
+    /// Vertical directions are returned unchanged.
+    #[inline]
+    pub const fn apply(self, dir: Direction) -> Direction {
+        match self {
+            Self::None => dir,
+            Self::Clockwise90 => dir.rotate_y_clockwise(),
+            Self::Clockwise180 => dir.rotate_y_clockwise().rotate_y_clockwise(),
+            Self::Counterclockwise90 => dir.rotate_y_counterclockwise(),
+        }
+    }
+
+    /// Rotates the given axis, which swaps the X and Z axes on quarter
+    /// turns.
+    #[inline]
+    pub const fn apply_axis(self, axis: Axis) -> Axis {
+        match (self, axis) {
+            (Self::Clockwise90 | Self::Counterclockwise90, Axis::X) => Axis::Z,
+            (Self::Clockwise90 | Self::Counterclockwise90, Axis::Z) => Axis::X,
+            _ => axis,
+        }
+    }
+
+    /// Rotates the given index of a rotation split into `full_turn`
+    /// parts clockwise, such as the 16 rotations of standing signs.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockRotation.rotate(int, int)` (yarn).
+    #[inline]
+    pub const fn apply_index(self, rotation: i32, full_turn: i32) -> i32 {
+        match self {
+            Self::None => rotation,
+            Self::Clockwise90 => (rotation + full_turn / 4) % full_turn,
+            Self::Clockwise180 => (rotation + full_turn / 2) % full_turn,
+            Self::Counterclockwise90 => (rotation + full_turn * 3 / 4) % full_turn,
+        }
+    }
+}
+
+/// Mirroring of blocks along a horizontal axis.
+///
+/// # MCJE Reference
+///
+/// This type represents `net.minecraft.util.BlockMirror` (yarn).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[allow(clippy::exhaustive_enums)]
+#[repr(u8)]
+pub enum BlockMirror {
+    /// No mirroring.
+    #[default]
+    None,
+    /// Mirroring along the Z axis, which swaps north and south.
+    LeftRight,
+    /// Mirroring along the X axis, which swaps west and east.
+    FrontBack,
+}
+
+impl BlockMirror {
+    /// All mirrors.
+    pub const ALL: [Self; 3] = [Self::None, Self::LeftRight, Self::FrontBack];
+
+    /// Gets the name of this mirror.
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::LeftRight => "left_right",
+            Self::FrontBack => "front_back",
+        }
+    }
+
+    /// Gets the axis this mirror flips, or `None` if this is
+    /// [`BlockMirror::None`].
+    #[inline]
+    pub const fn axis(self) -> Option<Axis> {
+        match self {
+            Self::None => None,
+            Self::LeftRight => Some(Axis::Z),
+            Self::FrontBack => Some(Axis::X),
+        }
+    }
+
+    /// Returns the transform of applying this mirror and then the other
+    /// one, which is a mirror followed by a rotation.
+    ///
+    /// Mirroring along both axes equals a half turn.
+    #[inline]
+    pub const fn compose(self, other: Self) -> (Self, BlockRotation) {
+        match (self, other) {
+            (Self::None, mirror) | (mirror, Self::None) => (mirror, BlockRotation::None),
+            (Self::LeftRight, Self::LeftRight) | (Self::FrontBack, Self::FrontBack) => {
+                (Self::None, BlockRotation::None)
+            }
+            _ => (Self::None, BlockRotation::Clockwise180),
+        }
+    }
+
+    /// Mirrors the given direction.
+    #[inline]
+    pub const fn apply(self, dir: Direction) -> Direction {
+        match (self, dir) {
+            (Self::LeftRight, Direction::North | Direction::South)
+            | (Self::FrontBack, Direction::West | Direction::East) => dir.opposite(),
+            _ => dir,
+        }
+    }
+
+    /// Gets the rotation that has the same effect as this mirror on the
+    /// given direction.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockMirror.getRotation` (yarn).
+    #[inline]
+    pub const fn to_rotation(self, dir: Direction) -> BlockRotation {
+        match (self, dir) {
+            (Self::LeftRight, Direction::North | Direction::South)
+            | (Self::FrontBack, Direction::West | Direction::East) => BlockRotation::Clockwise180,
+            _ => BlockRotation::None,
+        }
+    }
+
+    /// Mirrors the given index of a rotation split into `full_turn` parts
+    /// clockwise starting from south, such as the 16 rotations of standing
+    /// signs.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `BlockMirror.mirror(int, int)` (yarn).
+    #[inline]
+    pub const fn apply_index(self, rotation: i32, full_turn: i32) -> i32 {
+        let half = full_turn / 2;
+        let signed = if rotation > half {
+            rotation - full_turn
+        } else {
+            rotation
+        };
+        match self {
+            Self::None => rotation,
+            Self::FrontBack => (full_turn - signed) % full_turn,
+            Self::LeftRight => (half - signed + full_turn) % full_turn,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod _serde_transform {
+    use serde::{Deserialize, Serialize};
+
+    use super::{BlockMirror, BlockRotation};
+
+    macro_rules! str_serde {
+        ($($t:ident: $expecting:literal [$($name:literal),*]),*$(,)?) => {
+            $(
+                impl Serialize for $t {
+                    #[inline]
+                    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where
+                        S: serde::Serializer,
+                    {
+                        serializer.serialize_str(self.name())
+                    }
+                }
+
+                impl<'de> Deserialize<'de> for $t {
+                    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                    where
+                        D: serde::Deserializer<'de>,
+                    {
+                        struct Visitor;
+
+                        impl serde::de::Visitor<'_> for Visitor {
+                            type Value = $t;
+
+                            fn expecting(
+                                &self,
+                                formatter: &mut std::fmt::Formatter<'_>,
+                            ) -> std::fmt::Result {
+                                write!(formatter, $expecting)
+                            }
+
+                            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                            where
+                                E: serde::de::Error,
+                            {
+                                $t::ALL
+                                    .into_iter()
+                                    .find(|v| v.name() == value)
+                                    .ok_or_else(|| {
+                                        serde::de::Error::unknown_variant(value, &[$($name),*])
+                                    })
+                            }
+                        }
+
+                        deserializer.deserialize_str(Visitor)
+                    }
+                }
+            )*
+        };
+    }
+
+    str_serde! {
+        BlockRotation: "a block rotation name"
+            ["none", "clockwise_90", "180", "counterclockwise_90"],
+        BlockMirror: "a block mirror name" ["none", "left_right", "front_back"],
+    }
+}
+
 /// An error that can occur when converting a direction.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -336,3 +688,72 @@ impl fmt::Display for Error {
 }
 
 impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        assert_eq!(
+            BlockRotation::Clockwise90.apply(Direction::North),
+            Direction::East
+        );
+        assert_eq!(
+            BlockRotation::Counterclockwise90.apply(Direction::North),
+            Direction::West
+        );
+        assert_eq!(
+            BlockRotation::Clockwise180.apply(Direction::Up),
+            Direction::Up
+        );
+        assert_eq!(BlockRotation::Clockwise90.apply_axis(Axis::X), Axis::Z);
+
+        for a in BlockRotation::ALL {
+            assert_eq!(a.compose(a.inverse()), BlockRotation::None);
+            for b in BlockRotation::ALL {
+                for dir in Direction::ALL {
+                    assert_eq!(a.compose(b).apply(dir), b.apply(a.apply(dir)));
+                }
+            }
+        }
+
+        assert_eq!(BlockRotation::Clockwise90.apply_index(14, 16), 2);
+        assert_eq!(BlockRotation::Clockwise180.apply_index(3, 16), 11);
+        assert_eq!(BlockRotation::Counterclockwise90.apply_index(0, 16), 12);
+    }
+
+    #[test]
+    fn mirror() {
+        assert_eq!(
+            BlockMirror::LeftRight.apply(Direction::North),
+            Direction::South
+        );
+        assert_eq!(
+            BlockMirror::LeftRight.apply(Direction::East),
+            Direction::East
+        );
+        assert_eq!(
+            BlockMirror::FrontBack.apply(Direction::East),
+            Direction::West
+        );
+
+        for a in BlockMirror::ALL {
+            for b in BlockMirror::ALL {
+                let (mirror, rotation) = a.compose(b);
+                for dir in Direction::ALL {
+                    assert_eq!(rotation.apply(mirror.apply(dir)), b.apply(a.apply(dir)));
+                }
+            }
+            for dir in Direction::ALL {
+                assert_eq!(a.to_rotation(dir).apply(dir), a.apply(dir));
+            }
+        }
+
+        // Standing sign rotations start from south and go clockwise.
+        assert_eq!(BlockMirror::FrontBack.apply_index(4, 16), 12);
+        assert_eq!(BlockMirror::LeftRight.apply_index(4, 16), 4);
+        assert_eq!(BlockMirror::LeftRight.apply_index(0, 16), 8);
+        assert_eq!(BlockMirror::None.apply_index(5, 16), 5);
+    }
+}
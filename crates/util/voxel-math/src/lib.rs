@@ -4,8 +4,8 @@ mod block_pos;
 mod chunk_pos;
 mod chunk_section_pos;
 
-pub use block_pos::BlockPos;
-pub use chunk_pos::ChunkPos;
+pub use block_pos::{BlockPos, BlockPosIter, OutwardIter, SpiralIter};
+pub use chunk_pos::{ChunkPos, ChunkPosIter, ChunkSpiralIter};
 pub use chunk_section_pos::ChunkSectionPos;
 
 mod bbox;
@@ -0,0 +1,212 @@
+//! Collision resolution of moving boxes against voxel shapes.
+
+use rimecraft_voxel_math::{direction::Axis, BBox, DVec3};
+
+use crate::{VoxelShapeSlice, EPSILON};
+
+/// Step-up behavior of a moving box.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct StepUp {
+    /// The max height the box could step up onto.
+    pub height: f64,
+    /// Whether the box is on ground before moving.
+    pub on_ground: bool,
+}
+
+/// Calculates how far given box could move along given axis before
+/// hitting any of given shapes, up to `max_dist`.
+///
+/// See [`VoxelShapeSlice::calculate_max_distance`].
+///
+/// # MCJE Reference
+///
+/// This represents `VoxelShapes.calculateMaxOffset` (yarn).
+pub fn calculate_max_offset<'a, I>(axis: Axis, bbox: BBox, shapes: I, mut max_dist: f64) -> f64
+where
+    I: IntoIterator<Item = &'a VoxelShapeSlice<'a>>,
+{
+    for shape in shapes {
+        if max_dist.abs() < EPSILON {
+            return 0.0;
+        }
+        max_dist = shape.calculate_max_distance(axis, bbox, max_dist);
+    }
+    max_dist
+}
+
+/// Resolves the movement of given box against collisions, and returns
+/// the adjusted movement.
+///
+/// `max_offset` calculates how far a box could move along an axis,
+/// with the same contract as [`calculate_max_offset`].
+///
+/// The movement is resolved along the Y axis first, then along the horizontal
+/// axis with the larger movement first. If the box collides horizontally while
+/// on ground, it tries to step up onto the obstacle, and the stepped movement
+/// is taken if it goes further horizontally.
+///
+/// # MCJE Reference
+///
+/// This represents `Entity.adjustMovementForCollisions` (yarn).
+pub fn collide<F>(bbox: BBox, movement: DVec3, step: StepUp, mut max_offset: F) -> DVec3
+where
+    F: FnMut(Axis, BBox, f64) -> f64,
+{
+    let adjusted = if movement.length_squared() == 0.0 {
+        movement
+    } else {
+        adjust(bbox, movement, &mut max_offset)
+    };
+    let collided = movement.cmpne(adjusted);
+    let grounded = step.on_ground || (collided.y && movement.y < 0.0);
+    if step.height > 0.0 && grounded && (collided.x || collided.z) {
+        let horizontal = DVec3::new(movement.x, 0.0, movement.z);
+        let mut stepped = adjust(
+            bbox,
+            DVec3::new(movement.x, step.height, movement.z),
+            &mut max_offset,
+        );
+        let up = adjust(
+            bbox.stretch(horizontal),
+            DVec3::new(0.0, step.height, 0.0),
+            &mut max_offset,
+        );
+        if up.y < step.height {
+            let alt = adjust(bbox.offset(up), horizontal, &mut max_offset) + up;
+            if horizontal_length_squared(alt) > horizontal_length_squared(stepped) {
+                stepped = alt;
+            }
+        }
+        if horizontal_length_squared(stepped) > horizontal_length_squared(adjusted) {
+            return stepped
+                + adjust(
+                    bbox.offset(stepped),
+                    DVec3::new(0.0, movement.y - stepped.y, 0.0),
+                    &mut max_offset,
+                );
+        }
+    }
+    adjusted
+}
+
+fn adjust<F>(mut bbox: BBox, movement: DVec3, max_offset: &mut F) -> DVec3
+where
+    F: FnMut(Axis, BBox, f64) -> f64,
+{
+    let DVec3 {
+        mut x,
+        mut y,
+        mut z,
+    } = movement;
+    if y != 0.0 {
+        y = max_offset(Axis::Y, bbox, y);
+        if y != 0.0 {
+            bbox = bbox.offset(DVec3::new(0.0, y, 0.0));
+        }
+    }
+    let z_first = x.abs() < z.abs();
+    if z_first && z != 0.0 {
+        z = max_offset(Axis::Z, bbox, z);
+        if z != 0.0 {
+            bbox = bbox.offset(DVec3::new(0.0, 0.0, z));
+        }
+    }
+    if x != 0.0 {
+        x = max_offset(Axis::X, bbox, x);
+        if !z_first && x != 0.0 {
+            bbox = bbox.offset(DVec3::new(x, 0.0, 0.0));
+        }
+    }
+    if !z_first && z != 0.0 {
+        z = max_offset(Axis::Z, bbox, z);
+    }
+    DVec3::new(x, y, z)
+}
+
+#[inline]
+fn horizontal_length_squared(v: DVec3) -> f64 {
+    v.x * v.x + v.z * v.z
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cuboid;
+
+    use super::*;
+
+    fn bbox(min: [f64; 3], max: [f64; 3]) -> BBox {
+        BBox::new(DVec3::from_array(min), DVec3::from_array(max))
+    }
+
+    fn collide_shapes(
+        shapes: &[Box<VoxelShapeSlice<'static>>],
+        bbox: BBox,
+        movement: DVec3,
+        step: StepUp,
+    ) -> DVec3 {
+        collide(bbox, movement, step, |axis, bbox, max_dist| {
+            calculate_max_offset(axis, bbox, shapes.iter().map(|s| &**s), max_dist)
+        })
+    }
+
+    const PLAYER: [[f64; 3]; 2] = [[-0.3, 0.0, -0.3], [0.3, 1.8, 0.3]];
+
+    #[test]
+    fn fall_and_walk() {
+        let floor = cuboid(bbox([-5.0, -1.0, -5.0], [5.0, 0.0, 5.0]));
+        let wall = cuboid(bbox([1.0, 0.0, -5.0], [2.0, 1.0, 5.0]));
+        let shapes = [floor, wall];
+        let player = bbox(PLAYER[0], PLAYER[1]);
+
+        let step = StepUp {
+            height: 0.6,
+            on_ground: true,
+        };
+        let falling = player.offset(DVec3::new(0.0, 2.0, 0.0));
+        assert_eq!(
+            collide_shapes(&shapes, falling, DVec3::new(0.0, -3.0, 0.0), step),
+            DVec3::new(0.0, -2.0, 0.0)
+        );
+        assert_eq!(
+            collide_shapes(&shapes, player, DVec3::new(1.0, -0.1, 0.5), step),
+            DVec3::new(0.7, 0.0, 0.5)
+        );
+        // The wall is too high to step onto.
+        assert_eq!(
+            collide_shapes(&shapes, player, DVec3::new(1.0, 0.0, 0.0), step),
+            DVec3::new(0.7, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn step_up() {
+        let floor = cuboid(bbox([-5.0, -1.0, -5.0], [5.0, 0.0, 5.0]));
+        let slab = cuboid(bbox([1.0, 0.0, -5.0], [2.0, 0.5, 5.0]));
+        let shapes = [floor, slab];
+        let player = bbox(PLAYER[0], PLAYER[1]);
+        let movement = DVec3::new(1.0, -0.08, 0.0);
+
+        let step = StepUp {
+            height: 0.6,
+            on_ground: true,
+        };
+        let stepped = collide_shapes(&shapes, player, movement, step);
+        assert!((stepped - DVec3::new(1.0, 0.5, 0.0)).abs().max_element() < 1.0E-6);
+
+        // Not stepping up without step height.
+        assert_eq!(
+            collide_shapes(&shapes, player, movement, StepUp::default()),
+            DVec3::new(0.7, 0.0, 0.0)
+        );
+        // Not stepping up while in air.
+        let flying = player.offset(DVec3::new(0.0, 0.2, 0.0));
+        let step = StepUp {
+            height: 0.6,
+            on_ground: false,
+        };
+        assert_eq!(
+            collide_shapes(&shapes, flying, DVec3::new(1.0, 0.0, 0.0), step),
+            DVec3::new(0.7, 0.0, 0.0)
+        );
+    }
+}
@@ -1,15 +1,30 @@
 //! Minecraft voxel shapes.
+//!
+//! This corresponds to `net.minecraft.util.shape` in `yarn`.
 
+pub mod collision;
+pub mod op;
+mod pair;
+pub mod raycast;
 pub mod set;
 
 use std::{
     fmt::Debug,
     ops::{Deref, DerefMut},
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 
-use rimecraft_voxel_math::direction::Axis;
-use set::VoxelSet;
+use pair::PairList;
+use raycast::BlockHitResult;
+use rimecraft_voxel_math::{
+    direction::{Axis, AxisDirection, Direction},
+    BBox, BlockPos, DVec3,
+};
+use set::{Props, VoxelSet};
+
+pub use op::BooleanOp;
+
+const EPSILON: f64 = 1.0E-7;
 
 trait AbstVoxelShape {
     fn as_raw(&self) -> &RawVoxelShape;
@@ -17,6 +32,54 @@ trait AbstVoxelShape {
 
     fn index_point_pos(&self, axis: Axis, index: u32) -> Option<f64>;
     fn point_poss<'a>(&'a self, axis: Axis) -> Box<dyn Iterator<Item = f64> + 'a>;
+
+    fn points(&self, axis: Axis) -> Points<'_>;
+}
+
+/// Point positions of a shape along an axis.
+#[derive(Debug, Clone, Copy)]
+enum Points<'a> {
+    /// Points evenly dividing `0..=1` into given count of sections.
+    Fractional(u32),
+    /// Arbitrary points.
+    Array(&'a [f64]),
+}
+
+impl Points<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        match self {
+            Points::Fractional(sections) => *sections as usize + 1,
+            Points::Array(points) => points.len(),
+        }
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> f64 {
+        match self {
+            Points::Fractional(sections) => index as f64 / *sections as f64,
+            Points::Array(points) => points[index],
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> impl Iterator<Item = f64> + '_ {
+        (0..self.len()).map(|i| self.get(i))
+    }
+
+    #[inline]
+    fn to_boxed_slice(self) -> Box<[f64]> {
+        self.iter().collect()
+    }
+}
+
+impl PartialEq for Points<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Points::Fractional(a), Points::Fractional(b)) => a == b,
+            _ => self.len() == other.len() && self.iter().eq(other.iter()),
+        }
+    }
 }
 
 /// Slice of a `VoxelShape`.
@@ -31,7 +94,7 @@ impl VoxelShapeSlice<'_> {
         let voxels = &self.inner.as_raw().voxels;
         let i = *voxels.bounds_of(axis).start();
 
-        (i >= voxels.len_of(axis))
+        (i < voxels.len_of(axis))
             .then(|| self.inner.index_point_pos(axis, i))
             .flatten()
             .unwrap_or(f64::INFINITY)
@@ -42,11 +105,269 @@ impl VoxelShapeSlice<'_> {
         let voxels = &self.inner.as_raw().voxels;
         let i = *voxels.bounds_of(axis).end();
 
-        (i >= voxels.len_of(axis))
+        (i > 0)
             .then(|| self.inner.index_point_pos(axis, i))
             .flatten()
             .unwrap_or(f64::NEG_INFINITY)
     }
+
+    /// Calculates how far given box could move along given axis before
+    /// hitting this shape, up to `max_dist`.
+    ///
+    /// The sign of `max_dist` is the direction of the movement, and the result
+    /// is either `max_dist` or a value with the same sign closer to zero.
+    /// Boxes already intersecting with this shape are not blocked by it.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `VoxelShape.calculateMaxDistance` (yarn).
+    pub fn calculate_max_distance(&self, axis: Axis, bbox: BBox, mut max_dist: f64) -> f64 {
+        if self.is_empty() {
+            return max_dist;
+        }
+        if max_dist.abs() < EPSILON {
+            return 0.0;
+        }
+
+        let voxels = &self.inner.as_raw().voxels;
+        let (a1, a2) = match axis {
+            Axis::X => (Axis::Y, Axis::Z),
+            Axis::Y => (Axis::Z, Axis::X),
+            Axis::Z => (Axis::X, Axis::Y),
+        };
+        let (min, max) = (bbox.min(), bbox.max());
+        let get = |v: DVec3, axis: Axis| axis.choose(v.x, v.y, v.z);
+        let range = |axis| {
+            self.coord_index(axis, get(min, axis) + EPSILON).max(0)
+                ..(self.coord_index(axis, get(max, axis) - EPSILON) + 1)
+                    .min(voxels.len_of(axis) as i32)
+        };
+        let (r1, r2) = (range(a1), range(a2));
+        let blocked = |p: i32| {
+            r1.clone().any(|q| {
+                r2.clone().any(|r| {
+                    let mut pos = [0; 3];
+                    pos[axis as usize] = p;
+                    pos[a1 as usize] = q;
+                    pos[a2 as usize] = r;
+                    voxels.in_bounds_and_contains(pos[0], pos[1], pos[2])
+                })
+            })
+        };
+
+        let points = self.inner.points(axis);
+        if max_dist > 0.0 {
+            let start = self.coord_index(axis, get(max, axis) - EPSILON) + 1;
+            if let Some(p) = (start..voxels.len_of(axis) as i32).find(|&p| blocked(p)) {
+                let dist = points.get(p as usize) - get(max, axis);
+                if dist >= -EPSILON {
+                    max_dist = max_dist.min(dist);
+                }
+            }
+        } else {
+            let end = self.coord_index(axis, get(min, axis) + EPSILON);
+            if let Some(p) = (0..end).rev().find(|&p| blocked(p)) {
+                let dist = points.get(p as usize + 1) - get(min, axis);
+                if dist <= EPSILON {
+                    max_dist = max_dist.max(dist);
+                }
+            }
+        }
+        max_dist
+    }
+
+    /// Gets the index of the voxel containing given coordinate along given axis,
+    /// which is `-1` if the coordinate is before the first point.
+    fn coord_index(&self, axis: Axis, coord: f64) -> i32 {
+        let points = self.inner.points(axis);
+        let (mut lo, mut hi) = (0, self.inner.as_raw().voxels.len_of(axis) as usize + 1);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if coord < points.get(mid) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        lo as i32 - 1
+    }
+
+    /// Whether this shape is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.as_raw().voxels.is_empty()
+    }
+
+    /// Calls given function with each box of this shape.
+    ///
+    /// See [`VoxelSetSlice::for_each_box`](set::VoxelSetSlice::for_each_box).
+    pub fn for_each_box<F>(&self, mut f: F)
+    where
+        F: FnMut(BBox),
+    {
+        let [xs, ys, zs] = Axis::ALL.map(|axis| self.inner.points(axis));
+        self.inner.as_raw().voxels.for_each_box(|min, max| {
+            let corner = |[x, y, z]: [u32; 3]| {
+                DVec3::new(xs.get(x as usize), ys.get(y as usize), zs.get(z as usize))
+            };
+            f(BBox::new(corner(min), corner(max)))
+        });
+    }
+
+    /// Whether given point is inside this shape.
+    pub fn contains(&self, point: DVec3) -> bool {
+        let [x, y, z] =
+            Axis::ALL.map(|axis| self.coord_index(axis, axis.choose(point.x, point.y, point.z)));
+        self.inner.as_raw().voxels.in_bounds_and_contains(x, y, z)
+    }
+
+    /// Casts a ray from `start` to `end` against this shape placed at
+    /// given block position, and returns the hit with the box of the
+    /// shape it hits.
+    ///
+    /// All coordinates are in world space. If the ray starts inside this
+    /// shape, the hit is at the start, facing against the ray.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `VoxelShape.raycast` (yarn).
+    pub fn raycast(&self, start: DVec3, end: DVec3, pos: BlockPos) -> Option<BlockHitResult> {
+        if self.is_empty() {
+            return None;
+        }
+        let delta = end - start;
+        if delta.length_squared() < EPSILON {
+            return None;
+        }
+        let origin = pos.0.as_dvec3();
+
+        let probe = start + delta * 0.001;
+        if self.contains(probe - origin) {
+            let local = probe - origin;
+            let mut hit =
+                BlockHitResult::new(probe, Direction::from_vec(delta).opposite(), pos, true);
+            self.for_each_box(|bbox| {
+                let (min, max) = (bbox.min() - EPSILON, bbox.max() + EPSILON);
+                if hit.bbox().is_none() && local.cmpge(min).all() && local.cmple(max).all() {
+                    hit = hit.with_bbox(bbox.offset(origin));
+                }
+            });
+            return Some(hit);
+        }
+
+        let mut result: Option<(DVec3, Direction, BBox)> = None;
+        self.for_each_box(|bbox| {
+            let bbox = bbox.offset(origin);
+            if let Some((hit, side)) = bbox.raycast(start, end) {
+                if result.is_none_or(|(nearest, _, _)| {
+                    start.distance_squared(hit) < start.distance_squared(nearest)
+                }) {
+                    result = Some((hit, side, bbox));
+                }
+            }
+        });
+        result.map(|(hit, side, bbox)| BlockHitResult::new(hit, side, pos, false).with_bbox(bbox))
+    }
+
+    /// Whether this shape is a single voxel covering exactly the unit cube.
+    ///
+    /// This is a fast path for [`full_cube`] and its equivalents.
+    pub fn is_full_cube(&self) -> bool {
+        Axis::ALL.into_iter().all(|axis| self.is_square(axis))
+            && self.inner.as_raw().voxels.contains(0, 0, 0)
+    }
+
+    /// Whether this shape has exactly one section spanning `0..=1`
+    /// along given axis.
+    fn is_square(&self, axis: Axis) -> bool {
+        let points = self.inner.points(axis);
+        points.len() == 2
+            && points.get(0).abs() <= EPSILON
+            && (points.get(1) - 1.0).abs() <= EPSILON
+    }
+
+    /// Gets the projection of this shape on the side of the unit cube
+    /// towards given direction, which is the slice of this shape next to the side.
+    ///
+    /// The result is cached in this shape.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `VoxelShape.getFace` (yarn).
+    pub fn face(&self, direction: Direction) -> Arc<VoxelShapeSlice<'static>> {
+        if self.is_empty() {
+            shared_empty()
+        } else if self.is_full_cube() {
+            shared_full_cube()
+        } else {
+            self.inner.as_raw().shape_cache[direction as usize]
+                .get_or_init(|| self.uncached_face(direction))
+                .clone()
+        }
+    }
+
+    fn uncached_face(&self, direction: Direction) -> Arc<VoxelShapeSlice<'static>> {
+        let axis = Axis::from(direction);
+        if self.is_square(axis) {
+            return self.to_boxed().into();
+        }
+        let coord = match AxisDirection::from(direction) {
+            AxisDirection::Positive => 1.0 - EPSILON,
+            AxisDirection::Negative => EPSILON,
+        };
+        let Ok(index) = u32::try_from(self.coord_index(axis, coord)) else {
+            return shared_empty();
+        };
+        let sliced = Sliced::new(self, axis, index);
+        if sliced.is_empty() {
+            shared_empty()
+        } else if sliced.is_full_cube() {
+            shared_full_cube()
+        } else {
+            sliced.to_boxed().into()
+        }
+    }
+
+    /// Whether the face of this shape towards given direction covers
+    /// the whole side of the unit cube.
+    ///
+    /// # MCJE Reference
+    ///
+    /// This represents `Block.isFaceFullSquare` (yarn).
+    pub fn is_face_full(&self, direction: Direction) -> bool {
+        !matches_anywhere(
+            &shared_full_cube(),
+            &self.face(direction),
+            BooleanOp::NotSame,
+        )
+    }
+
+    /// Simplifies this shape by merging its boxes into a shape with
+    /// as few points as possible.
+    pub fn simplify(&self) -> Box<VoxelShapeSlice<'static>> {
+        let mut shape = empty();
+        self.for_each_box(|bbox| shape = combine(&shape, &cuboid(bbox), BooleanOp::Or));
+        shape
+    }
+
+    /// Copies this shape into an owned shape.
+    pub fn to_boxed(&self) -> Box<VoxelShapeSlice<'static>> {
+        let voxels = self.inner.as_raw().voxels.clone();
+        let [xs, ys, zs] = Axis::ALL.map(|axis| self.inner.points(axis));
+        if [xs, ys, zs].into_iter().zip(Axis::ALL).all(
+            |(points, axis)| matches!(points, Points::Fractional(s) if s == voxels.len_of(axis)),
+        ) {
+            Simple::new(voxels).into_boxed_slice()
+        } else {
+            Array::new(
+                voxels,
+                xs.to_boxed_slice(),
+                ys.to_boxed_slice(),
+                zs.to_boxed_slice(),
+            )
+            .into_boxed_slice()
+        }
+    }
 }
 
 impl Debug for VoxelShapeSlice<'_> {
@@ -78,7 +399,18 @@ impl<'a> VoxelShapeSlice<'a> {
 #[derive(Debug, Clone)]
 struct RawVoxelShape {
     voxels: VoxelSet,
-    shape_cache: Vec<Arc<VoxelShapeSlice<'static>>>, //TODO: done these
+    /// Cached faces of the shape, indexed by [`Direction`].
+    shape_cache: [OnceLock<Arc<VoxelShapeSlice<'static>>>; 6],
+}
+
+impl From<VoxelSet> for RawVoxelShape {
+    #[inline]
+    fn from(voxels: VoxelSet) -> Self {
+        Self {
+            voxels,
+            shape_cache: Default::default(),
+        }
+    }
 }
 
 /// A simple voxel shape.
@@ -86,6 +418,13 @@ struct RawVoxelShape {
 pub struct Simple(RawVoxelShape);
 
 impl Simple {
+    /// Creates a new simple shape, whose points evenly divide each axis
+    /// into sections of the voxel set.
+    #[inline]
+    pub fn new(voxels: VoxelSet) -> Self {
+        Self(voxels.into())
+    }
+
     /// Converts the shape into a boxed slice.
     #[inline]
     pub fn into_boxed_slice(self) -> Box<VoxelShapeSlice<'static>> {
@@ -122,6 +461,11 @@ impl AbstVoxelShape for Simple {
             Box::new((0u32..=len).map(move |index| index as f64 / len2))
         }
     }
+
+    #[inline]
+    fn points(&self, axis: Axis) -> Points<'_> {
+        Points::Fractional(self.0.voxels.len_of(axis))
+    }
 }
 
 impl Deref for Simple {
@@ -151,6 +495,28 @@ pub struct Array {
 }
 
 impl Array {
+    /// Creates a new shape backed by given point arrays.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of any of the point arrays is not
+    /// one more than the length of the voxel set along the axis.
+    pub fn new(voxels: VoxelSet, xp: Box<[f64]>, yp: Box<[f64]>, zp: Box<[f64]>) -> Self {
+        for (axis, points) in Axis::ALL.into_iter().zip([&xp, &yp, &zp]) {
+            assert_eq!(
+                points.len(),
+                voxels.len_of(axis) as usize + 1,
+                "length of points along axis {axis:?} does not match the voxel set"
+            );
+        }
+        Self {
+            raw: voxels.into(),
+            xp,
+            yp,
+            zp,
+        }
+    }
+
     /// Converts the shape into a boxed slice.
     #[inline]
     pub fn into_boxed_slice(self) -> Box<VoxelShapeSlice<'static>> {
@@ -186,6 +552,10 @@ impl AbstVoxelShape for Array {
         };
         Box::new(arr.iter().copied())
     }
+
+    fn points(&self, axis: Axis) -> Points<'_> {
+        Points::Array(axis.choose(&self.xp, &self.yp, &self.zp))
+    }
 }
 
 impl Deref for Array {
@@ -212,6 +582,40 @@ pub struct Sliced<'a, 's> {
     axis: Axis,
 }
 
+impl<'a, 's> Sliced<'a, 's> {
+    /// Creates a slice of given shape along given axis, which is one voxel
+    /// thick and spans `0..=1` along the axis.
+    ///
+    /// The slice is empty if the index is out of the shape.
+    pub fn new(parent: &'a VoxelShapeSlice<'s>, axis: Axis, index: u32) -> Self {
+        let voxels = &parent.inner.as_raw().voxels;
+        let mut lens = Axis::ALL.map(|axis| voxels.len_of(axis));
+        lens[axis as usize] = 1;
+        let [len_x, len_y, len_z] = lens;
+        let mut set = VoxelSet::new(Props {
+            len_x,
+            len_y,
+            len_z,
+        });
+        for x in 0..len_x {
+            for y in 0..len_y {
+                for z in 0..len_z {
+                    let mut pos = [x, y, z].map(|i| i as i32);
+                    pos[axis as usize] = index as i32;
+                    if voxels.in_bounds_and_contains(pos[0], pos[1], pos[2]) {
+                        set.set(x, y, z);
+                    }
+                }
+            }
+        }
+        Self {
+            parent,
+            shape: set.into(),
+            axis,
+        }
+    }
+}
+
 impl<'a> Sliced<'a, 'a> {
     /// Converts the shape into a boxed slice.
     #[inline]
@@ -245,6 +649,14 @@ impl AbstVoxelShape for Sliced<'_, '_> {
             self.parent.inner.point_poss(axis)
         }
     }
+
+    fn points(&self, axis: Axis) -> Points<'_> {
+        if axis == self.axis {
+            Points::Fractional(1)
+        } else {
+            self.parent.inner.points(axis)
+        }
+    }
 }
 
 /// A mutable voxel shape that is a slice of another shape.
@@ -289,6 +701,14 @@ impl AbstVoxelShape for SlicedMut<'_, '_> {
             self.parent.inner.point_poss(axis)
         }
     }
+
+    fn points(&self, axis: Axis) -> Points<'_> {
+        if axis == self.axis {
+            Points::Fractional(1)
+        } else {
+            self.parent.inner.points(axis)
+        }
+    }
 }
 
 impl<'s> Deref for Sliced<'s, 's> {
@@ -315,3 +735,499 @@ impl<'s> DerefMut for SlicedMut<'s, 's> {
         VoxelShapeSlice::from_mut(self)
     }
 }
+
+/// Creates an empty shape.
+pub fn empty() -> Box<VoxelShapeSlice<'static>> {
+    let voxels = VoxelSet::new(Props {
+        len_x: 0,
+        len_y: 0,
+        len_z: 0,
+    });
+    Array::new(voxels, Box::new([0.0]), Box::new([0.0]), Box::new([0.0])).into_boxed_slice()
+}
+
+/// Creates a shape of a full cube from `(0, 0, 0)` to `(1, 1, 1)`.
+pub fn full_cube() -> Box<VoxelShapeSlice<'static>> {
+    let props = Props {
+        len_x: 1,
+        len_y: 1,
+        len_z: 1,
+    };
+    Simple::new(VoxelSet::with_region(props, [0; 3], [1; 3])).into_boxed_slice()
+}
+
+fn shared_empty() -> Arc<VoxelShapeSlice<'static>> {
+    static EMPTY: OnceLock<Arc<VoxelShapeSlice<'static>>> = OnceLock::new();
+    EMPTY.get_or_init(|| empty().into()).clone()
+}
+
+fn shared_full_cube() -> Arc<VoxelShapeSlice<'static>> {
+    static FULL_CUBE: OnceLock<Arc<VoxelShapeSlice<'static>>> = OnceLock::new();
+    FULL_CUBE.get_or_init(|| full_cube().into()).clone()
+}
+
+/// Creates a shape of given cuboid.
+///
+/// The shape is backed by a fractional voxel set if the bounds are
+/// multiples of `1/8` inside the unit cube, and by point arrays otherwise.
+/// An empty shape is returned if the cuboid has no volume.
+pub fn cuboid(bbox: BBox) -> Box<VoxelShapeSlice<'static>> {
+    let (min, max) = (bbox.min(), bbox.max());
+    if (max - min).min_element() < EPSILON {
+        return empty();
+    }
+    let [i, j, k] = Axis::ALL.map(|axis| {
+        let [min, max] = [min, max].map(|v| axis.choose(v.x, v.y, v.z));
+        required_bit_resolution(min, max)
+    });
+    match (i, j, k) {
+        (Some(0), Some(0), Some(0)) => full_cube(),
+        (Some(i), Some(j), Some(k)) => {
+            let props = Props {
+                len_x: 1 << i,
+                len_y: 1 << j,
+                len_z: 1 << k,
+            };
+            let scale = DVec3::new(props.len_x as f64, props.len_y as f64, props.len_z as f64);
+            let min = (min * scale).round().as_uvec3().to_array();
+            let max = (max * scale).round().as_uvec3().to_array();
+            Simple::new(VoxelSet::with_region(props, min, max)).into_boxed_slice()
+        }
+        _ => {
+            let props = Props {
+                len_x: 1,
+                len_y: 1,
+                len_z: 1,
+            };
+            Array::new(
+                VoxelSet::with_region(props, [0; 3], [1; 3]),
+                Box::new([min.x, max.x]),
+                Box::new([min.y, max.y]),
+                Box::new([min.z, max.z]),
+            )
+            .into_boxed_slice()
+        }
+    }
+}
+
+/// Finds the smallest resolution `n` where both of the values are multiples
+/// of `1 / 2^n`, for `n` in `0..=3`.
+fn required_bit_resolution(min: f64, max: f64) -> Option<u32> {
+    if min < -EPSILON || max > 1.0 + EPSILON {
+        return None;
+    }
+    (0..=3).find(|i| {
+        let j = (1 << i) as f64;
+        let (d, e) = (min * j, max * j);
+        (d - d.round()).abs() < EPSILON * j && (e - e.round()).abs() < EPSILON * j
+    })
+}
+
+/// Combines two shapes with given boolean function.
+///
+/// The result is not simplified. See [`combine_and_simplify`] for a simplified result.
+///
+/// # Panics
+///
+/// Panics if `op` returns `true` for `(false, false)`, as the result would be infinite.
+pub fn combine(
+    first: &VoxelShapeSlice<'_>,
+    second: &VoxelShapeSlice<'_>,
+    op: BooleanOp,
+) -> Box<VoxelShapeSlice<'static>> {
+    assert!(
+        !op.apply(false, false),
+        "boolean function {op:?} results in an infinite shape"
+    );
+    if std::ptr::addr_eq(first, second) {
+        return if op.apply(true, true) {
+            first.to_boxed()
+        } else {
+            empty()
+        };
+    }
+    let keep_first = op.apply(true, false);
+    let keep_second = op.apply(false, true);
+    if first.is_empty() {
+        return if keep_second {
+            second.to_boxed()
+        } else {
+            empty()
+        };
+    }
+    if second.is_empty() {
+        return if keep_first {
+            first.to_boxed()
+        } else {
+            empty()
+        };
+    }
+
+    let pairs = |size, axis| {
+        PairList::new(
+            size,
+            first.inner.points(axis),
+            second.inner.points(axis),
+            keep_first,
+            keep_second,
+        )
+    };
+    let x = pairs(1, Axis::X);
+    let y = pairs(x.len() - 1, Axis::Y);
+    let z = pairs((x.len() - 1) * (y.len() - 1), Axis::Z);
+    let voxels = VoxelSet::combine(
+        &first.inner.as_raw().voxels,
+        &second.inner.as_raw().voxels,
+        &x,
+        &y,
+        &z,
+        op,
+    );
+    if x.is_fractional() && y.is_fractional() && z.is_fractional() {
+        Simple::new(voxels).into_boxed_slice()
+    } else {
+        Array::new(voxels, x.to_points(), y.to_points(), z.to_points()).into_boxed_slice()
+    }
+}
+
+/// Combines two shapes with given boolean function, and simplifies the result.
+///
+/// # Panics
+///
+/// Panics if `op` returns `true` for `(false, false)`, as the result would be infinite.
+#[inline]
+pub fn combine_and_simplify(
+    first: &VoxelShapeSlice<'_>,
+    second: &VoxelShapeSlice<'_>,
+    op: BooleanOp,
+) -> Box<VoxelShapeSlice<'static>> {
+    combine(first, second, op).simplify()
+}
+
+/// Unions two shapes into a simplified shape.
+#[inline]
+pub fn union(
+    first: &VoxelShapeSlice<'_>,
+    second: &VoxelShapeSlice<'_>,
+) -> Box<VoxelShapeSlice<'static>> {
+    combine_and_simplify(first, second, BooleanOp::Or)
+}
+
+/// Whether combining two shapes with given boolean function results
+/// in any voxel.
+///
+/// # Panics
+///
+/// Panics if `op` returns `true` for `(false, false)`, as the result would be infinite.
+///
+/// # MCJE Reference
+///
+/// This represents `VoxelShapes.matchesAnywhere` (yarn).
+#[inline]
+pub fn matches_anywhere(
+    first: &VoxelShapeSlice<'_>,
+    second: &VoxelShapeSlice<'_>,
+    op: BooleanOp,
+) -> bool {
+    !combine(first, second, op).is_empty()
+}
+
+/// Whether the side of `shape` towards given direction is covered by
+/// `neighbor`, which is the shape of the adjacent block in the direction.
+///
+/// # MCJE Reference
+///
+/// This represents `VoxelShapes.isSideCovered` (yarn).
+pub fn is_side_covered(
+    shape: &VoxelShapeSlice<'_>,
+    neighbor: &VoxelShapeSlice<'_>,
+    direction: Direction,
+) -> bool {
+    if shape.is_full_cube() && neighbor.is_full_cube() {
+        return true;
+    }
+    if neighbor.is_empty() {
+        return false;
+    }
+    let axis = Axis::from(direction);
+    let (first, second, op) = match AxisDirection::from(direction) {
+        AxisDirection::Positive => (shape, neighbor, BooleanOp::OnlyFirst),
+        AxisDirection::Negative => (neighbor, shape, BooleanOp::OnlySecond),
+    };
+    if (first.max(axis) - 1.0).abs() > EPSILON || second.min(axis).abs() > EPSILON {
+        return false;
+    }
+    let last = first.inner.as_raw().voxels.len_of(axis) - 1;
+    !matches_anywhere(
+        &Sliced::new(first, axis, last),
+        &Sliced::new(second, axis, 0),
+        op,
+    )
+}
+
+/// Whether the faces between `shape` and `neighbor`, which is the shape of
+/// the adjacent block in given direction, cover the whole square together.
+///
+/// # MCJE Reference
+///
+/// This represents `VoxelShapes.adjacentSideCoversSquare` (yarn).
+pub fn adjacent_side_covers_square(
+    shape: &VoxelShapeSlice<'_>,
+    neighbor: &VoxelShapeSlice<'_>,
+    direction: Direction,
+) -> bool {
+    if shape.is_full_cube() || neighbor.is_full_cube() {
+        return true;
+    }
+    let axis = Axis::from(direction);
+    let (first, second) = match AxisDirection::from(direction) {
+        AxisDirection::Positive => (shape, neighbor),
+        AxisDirection::Negative => (neighbor, shape),
+    };
+    let first = if !first.is_empty() && (first.max(axis) - 1.0).abs() <= EPSILON {
+        Sliced::new(first, axis, first.inner.as_raw().voxels.len_of(axis) - 1).into_boxed_slice()
+    } else {
+        empty()
+    };
+    let second = if !second.is_empty() && second.min(axis).abs() <= EPSILON {
+        Sliced::new(second, axis, 0).into_boxed_slice()
+    } else {
+        empty()
+    };
+    !matches_anywhere(
+        &full_cube(),
+        &combine(&first, &second, BooleanOp::Or),
+        BooleanOp::OnlyFirst,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boxes(shape: &VoxelShapeSlice<'_>) -> Vec<BBox> {
+        let mut boxes = Vec::new();
+        shape.for_each_box(|bbox| boxes.push(bbox));
+        boxes
+    }
+
+    fn bbox(min: [f64; 3], max: [f64; 3]) -> BBox {
+        BBox::new(DVec3::from_array(min), DVec3::from_array(max))
+    }
+
+    #[test]
+    fn boolean_op() {
+        for op in BooleanOp::ALL {
+            assert_eq!(op.apply(true, true), op as u8 & 0b1000 != 0);
+        }
+        assert!(BooleanOp::OnlyFirst.apply(true, false));
+        assert!(!BooleanOp::OnlyFirst.apply(true, true));
+        assert!(BooleanOp::NotSame.apply(false, true));
+        assert!(BooleanOp::SecondOrNotFirst.apply(false, false));
+        assert!(!BooleanOp::SecondOrNotFirst.apply(true, false));
+    }
+
+    #[test]
+    fn cuboid_bounds() {
+        let slab = cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+        assert_eq!(slab.min(Axis::Y), 0.0);
+        assert_eq!(slab.max(Axis::Y), 0.5);
+        assert_eq!(slab.max(Axis::X), 1.0);
+
+        let odd = cuboid(bbox([0.1, 0.2, 0.3], [0.4, 0.5, 0.6]));
+        assert_eq!(boxes(&odd), [bbox([0.1, 0.2, 0.3], [0.4, 0.5, 0.6])]);
+
+        let empty = empty();
+        assert!(empty.is_empty());
+        assert_eq!(empty.min(Axis::X), f64::INFINITY);
+        assert_eq!(empty.max(Axis::X), f64::NEG_INFINITY);
+        assert!(cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.0, 1.0])).is_empty());
+    }
+
+    #[test]
+    fn union_slabs() {
+        let bottom = cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+        let top = cuboid(bbox([0.0, 0.5, 0.0], [1.0, 1.0, 1.0]));
+        let full = union(&bottom, &top);
+        assert_eq!(boxes(&full), [bbox([0.0; 3], [1.0; 3])]);
+
+        // Unaligned boxes are merged into point arrays.
+        let a = cuboid(bbox([0.1, 0.0, 0.0], [0.3, 1.0, 1.0]));
+        let b = cuboid(bbox([0.2, 0.0, 0.0], [0.6, 1.0, 1.0]));
+        assert_eq!(
+            boxes(&union(&a, &b)),
+            [bbox([0.1, 0.0, 0.0], [0.6, 1.0, 1.0])]
+        );
+
+        // Disjoint boxes.
+        let c = cuboid(bbox([0.7, 0.0, 0.0], [0.8, 1.0, 1.0]));
+        assert_eq!(
+            boxes(&union(&a, &c)),
+            [
+                bbox([0.1, 0.0, 0.0], [0.3, 1.0, 1.0]),
+                bbox([0.7, 0.0, 0.0], [0.8, 1.0, 1.0])
+            ]
+        );
+    }
+
+    #[test]
+    fn intersection_subtraction() {
+        let full = full_cube();
+        let bottom = cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+        let corner = cuboid(bbox([0.5, 0.25, 0.5], [1.0, 1.0, 1.0]));
+
+        let top = combine_and_simplify(&full, &bottom, BooleanOp::OnlyFirst);
+        assert_eq!(boxes(&top), [bbox([0.0, 0.5, 0.0], [1.0, 1.0, 1.0])]);
+
+        let inter = combine_and_simplify(&bottom, &corner, BooleanOp::And);
+        assert_eq!(boxes(&inter), [bbox([0.5, 0.25, 0.5], [1.0, 0.5, 1.0])]);
+
+        assert!(combine(&top, &bottom, BooleanOp::And).is_empty());
+        assert!(combine(&full, &full, BooleanOp::OnlyFirst).is_empty());
+        assert!(!combine(&full, &empty(), BooleanOp::Or).is_empty());
+
+        let mut volume = 0.0;
+        combine(&full, &corner, BooleanOp::OnlyFirst).for_each_box(|bbox| {
+            volume += (bbox.max() - bbox.min()).element_product();
+        });
+        assert!((volume - (1.0 - 0.25 * 0.75)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn max_distance() {
+        let full = full_cube();
+        let slab = cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+
+        let above = bbox([0.25, 2.0, 0.25], [0.75, 3.0, 0.75]);
+        assert_eq!(full.calculate_max_distance(Axis::Y, above, -5.0), -1.0);
+        assert_eq!(slab.calculate_max_distance(Axis::Y, above, -5.0), -1.5);
+        assert_eq!(slab.calculate_max_distance(Axis::Y, above, -1.0), -1.0);
+        assert_eq!(slab.calculate_max_distance(Axis::Y, above, 5.0), 5.0);
+
+        let below = bbox([0.25, -2.0, 0.25], [0.75, -1.0, 0.75]);
+        assert_eq!(slab.calculate_max_distance(Axis::Y, below, 5.0), 1.0);
+
+        let beside = bbox([1.5, 0.0, 0.0], [2.5, 1.0, 1.0]);
+        assert_eq!(full.calculate_max_distance(Axis::X, beside, -1.0), -0.5);
+        assert_eq!(full.calculate_max_distance(Axis::Y, beside, -1.0), -1.0);
+        // Touching boxes are blocked.
+        let touching = beside.offset(DVec3::new(-0.5, 0.0, 0.0));
+        assert_eq!(full.calculate_max_distance(Axis::X, touching, -1.0), 0.0);
+        // Intersecting boxes are not.
+        let inside = bbox([0.25, 0.25, 0.25], [0.75, 0.75, 0.75]);
+        assert_eq!(full.calculate_max_distance(Axis::X, inside, 1.0), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn infinite_combine() {
+        combine(&full_cube(), &empty(), BooleanOp::NotOr);
+    }
+
+    #[test]
+    fn faces() {
+        let slab = cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+        let full = full_cube();
+        assert!(full.is_full_cube());
+        assert!(!slab.is_full_cube());
+
+        assert!(slab.face(Direction::Up).is_empty());
+        assert!(slab.face(Direction::Down).is_full_cube());
+        assert!(slab.is_face_full(Direction::Down));
+        assert!(!slab.is_face_full(Direction::Up));
+        assert!(!slab.is_face_full(Direction::North));
+        assert_eq!(
+            boxes(&slab.face(Direction::North)),
+            [bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0])]
+        );
+        assert!(Arc::ptr_eq(
+            &slab.face(Direction::North),
+            &slab.face(Direction::North)
+        ));
+
+        let post = cuboid(bbox([0.25, 0.0, 0.25], [0.75, 1.0, 0.75]));
+        assert!(post.face(Direction::East).is_empty());
+        assert!(!post.is_face_full(Direction::Up));
+        assert!(!post.face(Direction::Up).is_empty());
+    }
+
+    #[test]
+    fn side_covered() {
+        let full = full_cube();
+        let slab = cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+        let top = cuboid(bbox([0.0, 0.5, 0.0], [1.0, 1.0, 1.0]));
+        let post = cuboid(bbox([0.25, 0.0, 0.25], [0.75, 1.0, 0.75]));
+
+        assert!(is_side_covered(&full, &full, Direction::Up));
+        assert!(!is_side_covered(&full, &empty(), Direction::Up));
+        // Bottom face of the slab above is full.
+        assert!(is_side_covered(&full, &slab, Direction::Up));
+        assert!(!is_side_covered(&full, &top, Direction::Up));
+        assert!(is_side_covered(&full, &top, Direction::Down));
+        // A slab is covered by a full cube on its side, but not on its top.
+        assert!(is_side_covered(&slab, &full, Direction::North));
+        assert!(!is_side_covered(&slab, &full, Direction::Up));
+        assert!(is_side_covered(&slab, &slab, Direction::East));
+        assert!(!is_side_covered(&slab, &top, Direction::East));
+        assert!(is_side_covered(&post, &full, Direction::Up));
+        assert!(!is_side_covered(&full, &post, Direction::Up));
+    }
+
+    #[test]
+    fn adjacent_side() {
+        let bottom = cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+        let top = cuboid(bbox([0.0, 0.5, 0.0], [1.0, 1.0, 1.0]));
+        let west = cuboid(bbox([0.0, 0.0, 0.0], [0.5, 1.0, 1.0]));
+        let east = cuboid(bbox([0.5, 0.0, 0.0], [1.0, 1.0, 1.0]));
+
+        assert!(adjacent_side_covers_square(
+            &full_cube(),
+            &empty(),
+            Direction::Up
+        ));
+        assert!(adjacent_side_covers_square(
+            &empty(),
+            &full_cube(),
+            Direction::Up
+        ));
+        assert!(!adjacent_side_covers_square(
+            &empty(),
+            &empty(),
+            Direction::Up
+        ));
+        // Bottom slabs leave the upper half of side faces open.
+        assert!(!adjacent_side_covers_square(
+            &bottom,
+            &bottom,
+            Direction::East
+        ));
+        assert!(adjacent_side_covers_square(&bottom, &top, Direction::East));
+        assert!(adjacent_side_covers_square(&top, &bottom, Direction::West));
+        // Faces away from the boundary don't count.
+        assert!(adjacent_side_covers_square(
+            &east,
+            &empty(),
+            Direction::East
+        ));
+        assert!(!adjacent_side_covers_square(
+            &west,
+            &empty(),
+            Direction::East
+        ));
+        assert!(adjacent_side_covers_square(
+            &empty(),
+            &west,
+            Direction::East
+        ));
+        assert!(adjacent_side_covers_square(
+            &bottom,
+            &empty(),
+            Direction::Down
+        ));
+        assert!(!adjacent_side_covers_square(
+            &bottom,
+            &empty(),
+            Direction::Up
+        ));
+    }
+}
@@ -0,0 +1,78 @@
+//! Boolean operations for combining shapes.
+
+/// A boolean function of two arguments, which tells whether a voxel
+/// should be present in the combined shape from its presence in
+/// the first and the second shape.
+///
+/// The discriminant of each variant is its truth table, where the bit at
+/// `(first << 1) | second` is the result of applying the function.
+///
+/// # MCJE Reference
+///
+/// This type represents `net.minecraft.util.function.BooleanBiFunction` (yarn).
+#[doc(alias = "BooleanBiFunction")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::exhaustive_enums)]
+#[repr(u8)]
+pub enum BooleanOp {
+    /// Always `false`.
+    False = 0b0000,
+    /// Neither of the arguments.
+    NotOr = 0b0001,
+    /// The second argument but not the first one.
+    OnlySecond = 0b0010,
+    /// Not the first argument.
+    NotFirst = 0b0011,
+    /// The first argument but not the second one.
+    OnlyFirst = 0b0100,
+    /// Not the second argument.
+    NotSecond = 0b0101,
+    /// Either of the arguments but not both.
+    NotSame = 0b0110,
+    /// Not both of the arguments.
+    NotAnd = 0b0111,
+    /// Both of the arguments.
+    And = 0b1000,
+    /// Both or neither of the arguments.
+    Same = 0b1001,
+    /// The second argument.
+    Second = 0b1010,
+    /// The second argument, or not the first one.
+    SecondOrNotFirst = 0b1011,
+    /// The first argument.
+    First = 0b1100,
+    /// The first argument, or not the second one.
+    FirstOrNotSecond = 0b1101,
+    /// Either of the arguments.
+    Or = 0b1110,
+    /// Always `true`.
+    True = 0b1111,
+}
+
+impl BooleanOp {
+    /// All boolean functions.
+    pub const ALL: [Self; 16] = [
+        Self::False,
+        Self::NotOr,
+        Self::OnlySecond,
+        Self::NotFirst,
+        Self::OnlyFirst,
+        Self::NotSecond,
+        Self::NotSame,
+        Self::NotAnd,
+        Self::And,
+        Self::Same,
+        Self::Second,
+        Self::SecondOrNotFirst,
+        Self::First,
+        Self::FirstOrNotSecond,
+        Self::Or,
+        Self::True,
+    ];
+
+    /// Applies this function to given arguments.
+    #[inline]
+    pub const fn apply(self, first: bool, second: bool) -> bool {
+        (self as u8 >> (((first as u8) << 1) | second as u8)) & 1 != 0
+    }
+}
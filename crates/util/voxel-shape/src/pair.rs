@@ -0,0 +1,227 @@
+//! Merging of point lists of two shapes along an axis.
+
+use crate::Points;
+
+const EPSILON: f64 = 1.0E-7;
+
+/// A merged list of points, where each section between two adjacent
+/// points refers to a section of both of the original lists.
+///
+/// An index of `-1` refers to the space outside of the original list.
+///
+/// # MCJE Reference
+///
+/// This type represents `net.minecraft.util.shape.PairList` and its
+/// implementations (yarn).
+#[derive(Debug)]
+pub(crate) enum PairList<'a> {
+    /// Both lists are identical.
+    Identity(Points<'a>),
+    /// Lists merged point by point.
+    Simple {
+        points: Box<[f64]>,
+        first: Box<[i32]>,
+        second: Box<[i32]>,
+    },
+    /// Lists that don't overlap, where `first` is before `second`.
+    Disjoint {
+        first: Points<'a>,
+        second: Points<'a>,
+        inverted: bool,
+    },
+    /// Fractional lists merged into one with the least common multiple
+    /// of their section counts.
+    Fractional {
+        sections: u32,
+        first_div: u32,
+        second_div: u32,
+    },
+}
+
+impl<'a> PairList<'a> {
+    /// Merges two point lists.
+    ///
+    /// `size` is the count of voxels the merged list will be multiplied with,
+    /// used for deciding whether a fractional list is small enough.
+    pub fn new(
+        size: usize,
+        first: Points<'a>,
+        second: Points<'a>,
+        include_first: bool,
+        include_second: bool,
+    ) -> Self {
+        if let (Points::Fractional(i), Points::Fractional(j)) = (first, second) {
+            if i > 0 && j > 0 {
+                let gcd = gcd(i, j);
+                let lcm = u64::from(i / gcd) * u64::from(j);
+                if size as u64 * lcm <= 256 {
+                    return Self::Fractional {
+                        sections: lcm as u32,
+                        first_div: j / gcd,
+                        second_div: i / gcd,
+                    };
+                }
+            }
+        }
+
+        if first.get(first.len() - 1) < second.get(0) - EPSILON {
+            Self::Disjoint {
+                first,
+                second,
+                inverted: false,
+            }
+        } else if second.get(second.len() - 1) < first.get(0) - EPSILON {
+            Self::Disjoint {
+                first: second,
+                second: first,
+                inverted: true,
+            }
+        } else if first == second {
+            Self::Identity(first)
+        } else {
+            Self::simple(first, second, include_first, include_second)
+        }
+    }
+
+    fn simple(
+        first: Points<'_>,
+        second: Points<'_>,
+        include_first: bool,
+        include_second: bool,
+    ) -> Self {
+        let (i, j) = (first.len(), second.len());
+        let mut points = Vec::with_capacity(i + j);
+        let mut firsts = Vec::with_capacity(i + j);
+        let mut seconds = Vec::with_capacity(i + j);
+
+        let mut last = f64::NAN;
+        let (mut m, mut n) = (0, 0);
+        loop {
+            let first_done = m >= i;
+            let second_done = n >= j;
+            if first_done && second_done {
+                break;
+            }
+            let from_first = !first_done && (second_done || first.get(m) < second.get(n) + EPSILON);
+            if from_first {
+                m += 1;
+                if !include_first && (n == 0 || second_done) {
+                    continue;
+                }
+            } else {
+                n += 1;
+                if !include_second && (m == 0 || first_done) {
+                    continue;
+                }
+            }
+
+            let (o, p) = (m as i32 - 1, n as i32 - 1);
+            let point = if from_first {
+                first.get(m - 1)
+            } else {
+                second.get(n - 1)
+            };
+            if last >= point - EPSILON {
+                *firsts.last_mut().expect("no point present") = o;
+                *seconds.last_mut().expect("no point present") = p;
+            } else {
+                points.push(point);
+                firsts.push(o);
+                seconds.push(p);
+                last = point;
+            }
+        }
+
+        if points.len() <= 1 {
+            // Nothing is merged, so there is no section.
+            points = vec![0.0];
+        }
+
+        Self::Simple {
+            points: points.into_boxed_slice(),
+            first: firsts.into_boxed_slice(),
+            second: seconds.into_boxed_slice(),
+        }
+    }
+
+    /// Gets the count of points in this list.
+    pub fn len(&self) -> usize {
+        match self {
+            PairList::Identity(points) => points.len(),
+            PairList::Simple { points, .. } => points.len(),
+            PairList::Disjoint { first, second, .. } => first.len() + second.len(),
+            PairList::Fractional { sections, .. } => *sections as usize + 1,
+        }
+    }
+
+    /// Whether this list is merged from two fractional lists.
+    #[inline]
+    pub fn is_fractional(&self) -> bool {
+        matches!(self, PairList::Fractional { .. })
+    }
+
+    /// Calls given function with index in the first list, index in the second list
+    /// and index in this list of each section.
+    pub fn for_each_pair<F>(&self, mut f: F)
+    where
+        F: FnMut(i32, i32, u32),
+    {
+        match self {
+            PairList::Identity(points) => {
+                for i in 0..points.len() as u32 - 1 {
+                    f(i as i32, i as i32, i);
+                }
+            }
+            PairList::Simple { first, second, .. } => {
+                for (i, (&x, &y)) in first
+                    .iter()
+                    .zip(second.iter())
+                    .take(self.len() - 1)
+                    .enumerate()
+                {
+                    f(x, y, i as u32);
+                }
+            }
+            PairList::Disjoint {
+                first,
+                second,
+                inverted,
+            } => {
+                let mut f = |x, y, i| if *inverted { f(y, x, i) } else { f(x, y, i) };
+                let len = first.len() as u32;
+                for i in 0..len - 1 {
+                    f(i as i32, -1, i);
+                }
+                for i in 0..second.len() as u32 - 1 {
+                    f(len as i32 - 1, i as i32, len + i);
+                }
+            }
+            PairList::Fractional {
+                sections,
+                first_div,
+                second_div,
+            } => {
+                for i in 0..*sections {
+                    f((i / first_div) as i32, (i / second_div) as i32, i);
+                }
+            }
+        }
+    }
+
+    /// Collects points of this list.
+    pub fn to_points(&self) -> Box<[f64]> {
+        match self {
+            PairList::Identity(points) => points.to_boxed_slice(),
+            PairList::Simple { points, .. } => points.clone(),
+            PairList::Disjoint { first, second, .. } => first.iter().chain(second.iter()).collect(),
+            PairList::Fractional { sections, .. } => Points::Fractional(*sections).to_boxed_slice(),
+        }
+    }
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
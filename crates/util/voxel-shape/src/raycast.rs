@@ -0,0 +1,263 @@
+//! Ray casting through voxel shapes and blocks.
+
+use rimecraft_voxel_math::{direction::Direction, BBox, BlockPos, DVec3, IVec3};
+
+use crate::EPSILON;
+
+/// Result of a ray hitting a block, or missing any block.
+///
+/// # MCJE Reference
+///
+/// This type represents `net.minecraft.util.hit.BlockHitResult` (yarn).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockHitResult {
+    pos: DVec3,
+    side: Direction,
+    block_pos: BlockPos,
+    bbox: Option<BBox>,
+    missed: bool,
+    inside: bool,
+}
+
+impl BlockHitResult {
+    /// Creates a hit result at given position, on given side of the block.
+    ///
+    /// `inside` tells whether the ray started inside the block.
+    #[inline]
+    pub const fn new(pos: DVec3, side: Direction, block_pos: BlockPos, inside: bool) -> Self {
+        Self {
+            pos,
+            side,
+            block_pos,
+            bbox: None,
+            missed: false,
+            inside,
+        }
+    }
+
+    /// Creates a result of a ray missing any block, where `pos` is the
+    /// end of the ray.
+    #[inline]
+    pub const fn missed(pos: DVec3, side: Direction, block_pos: BlockPos) -> Self {
+        Self {
+            pos,
+            side,
+            block_pos,
+            bbox: None,
+            missed: true,
+            inside: false,
+        }
+    }
+
+    /// Returns this result with given side.
+    #[inline]
+    pub const fn with_side(self, side: Direction) -> Self {
+        Self { side, ..self }
+    }
+
+    /// Returns this result with given block position.
+    #[inline]
+    pub const fn with_block_pos(self, block_pos: BlockPos) -> Self {
+        Self { block_pos, ..self }
+    }
+
+    /// Returns this result with given hit box.
+    #[inline]
+    pub const fn with_bbox(self, bbox: BBox) -> Self {
+        Self {
+            bbox: Some(bbox),
+            ..self
+        }
+    }
+
+    /// Gets the position the ray hits.
+    #[inline]
+    pub fn pos(&self) -> DVec3 {
+        self.pos
+    }
+
+    /// Gets the side of the block the ray hits.
+    #[inline]
+    pub fn side(&self) -> Direction {
+        self.side
+    }
+
+    /// Gets the position of the block.
+    #[inline]
+    pub fn block_pos(&self) -> BlockPos {
+        self.block_pos
+    }
+
+    /// Gets the box of the shape the ray hits, in world coordinates.
+    #[inline]
+    pub fn bbox(&self) -> Option<BBox> {
+        self.bbox
+    }
+
+    /// Whether the ray missed any block.
+    #[inline]
+    pub fn is_missed(&self) -> bool {
+        self.missed
+    }
+
+    /// Whether the ray started inside the block.
+    #[inline]
+    pub fn is_inside(&self) -> bool {
+        self.inside
+    }
+}
+
+/// Traverses blocks the ray from `start` to `end` passes through in order,
+/// and returns the first hit given function reports.
+///
+/// A missed result at `end` is returned if no block is hit.
+///
+/// # MCJE Reference
+///
+/// This represents `BlockView.raycast(Vec3d, Vec3d, C, BiFunction, Function)` (yarn).
+pub fn raycast<F>(start: DVec3, end: DVec3, mut f: F) -> BlockHitResult
+where
+    F: FnMut(BlockPos) -> Option<BlockHitResult>,
+{
+    let missed = || {
+        BlockHitResult::missed(
+            end,
+            Direction::from_vec(start - end),
+            BlockPos::floored(end),
+        )
+    };
+    if start == end {
+        return missed();
+    }
+
+    let to = end.lerp(start, -EPSILON);
+    let from = start.lerp(end, -EPSILON);
+    let mut pos = from.floor().as_ivec3();
+    if let Some(hit) = f(BlockPos(pos)) {
+        return hit;
+    }
+
+    let delta = to - from;
+    let step = IVec3::from_array(delta.to_array().map(|d| {
+        if d > 0.0 {
+            1
+        } else if d < 0.0 {
+            -1
+        } else {
+            0
+        }
+    }));
+    let frac = from - from.floor();
+    let mut inv = DVec3::INFINITY;
+    let mut next = DVec3::INFINITY;
+    for i in 0..3 {
+        if step[i] != 0 {
+            inv[i] = f64::from(step[i]) / delta[i];
+            next[i] = inv[i] * if step[i] > 0 { 1.0 - frac[i] } else { frac[i] };
+        }
+    }
+
+    while next.x <= 1.0 || next.y <= 1.0 || next.z <= 1.0 {
+        let i = if next.x < next.y {
+            if next.x < next.z {
+                0
+            } else {
+                2
+            }
+        } else if next.y < next.z {
+            1
+        } else {
+            2
+        };
+        pos[i] += step[i];
+        next[i] += inv[i];
+        if let Some(hit) = f(BlockPos(pos)) {
+            return hit;
+        }
+    }
+    missed()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cuboid, full_cube, VoxelShapeSlice};
+
+    use super::*;
+
+    fn bbox(min: [f64; 3], max: [f64; 3]) -> BBox {
+        BBox::new(DVec3::from_array(min), DVec3::from_array(max))
+    }
+
+    #[test]
+    fn shape() {
+        let slab = cuboid(bbox([0.0, 0.0, 0.0], [1.0, 0.5, 1.0]));
+        let pos = BlockPos(IVec3::new(2, 0, 0));
+
+        let hit = slab
+            .raycast(DVec3::new(2.5, 2.0, 0.5), DVec3::new(2.5, -1.0, 0.5), pos)
+            .expect("ray should hit the slab");
+        assert!((hit.pos() - DVec3::new(2.5, 0.5, 0.5)).length() < 1.0E-9);
+        assert_eq!(hit.side(), Direction::Up);
+        assert_eq!(hit.block_pos(), pos);
+        assert_eq!(hit.bbox(), Some(bbox([2.0, 0.0, 0.0], [3.0, 0.5, 1.0])));
+        assert!(!hit.is_inside());
+
+        let hit = slab
+            .raycast(DVec3::new(0.0, 0.25, 0.5), DVec3::new(4.0, 0.25, 0.5), pos)
+            .expect("ray should hit the slab");
+        assert!((hit.pos() - DVec3::new(2.0, 0.25, 0.5)).length() < 1.0E-9);
+        assert_eq!(hit.side(), Direction::West);
+
+        // Passing above the slab.
+        assert!(slab
+            .raycast(DVec3::new(0.0, 0.75, 0.5), DVec3::new(4.0, 0.75, 0.5), pos)
+            .is_none());
+
+        let hit = slab
+            .raycast(DVec3::new(2.5, 0.25, 0.5), DVec3::new(5.0, 0.25, 0.5), pos)
+            .expect("ray should start inside the slab");
+        assert!(hit.is_inside());
+        assert_eq!(hit.side(), Direction::West);
+    }
+
+    #[test]
+    fn traverse() {
+        let shape_at = |pos: BlockPos| -> Option<Box<VoxelShapeSlice<'static>>> {
+            (pos.0 == IVec3::new(3, 0, 0) || pos.0 == IVec3::new(0, 0, 3)).then(full_cube)
+        };
+        let cast = |start: DVec3, end: DVec3| {
+            let mut visited = Vec::new();
+            let hit = raycast(start, end, |pos| {
+                visited.push(pos.0);
+                shape_at(pos).and_then(|s| s.raycast(start, end, pos))
+            });
+            (hit, visited)
+        };
+
+        let (hit, visited) = cast(DVec3::new(0.5, 0.5, 0.5), DVec3::new(5.5, 0.5, 0.5));
+        assert!(!hit.is_missed());
+        assert_eq!(hit.block_pos(), BlockPos(IVec3::new(3, 0, 0)));
+        assert_eq!(hit.side(), Direction::West);
+        assert!((hit.pos() - DVec3::new(3.0, 0.5, 0.5)).length() < 1.0E-9);
+        assert_eq!(
+            visited,
+            (0..4).map(|x| IVec3::new(x, 0, 0)).collect::<Vec<_>>()
+        );
+
+        let (hit, _) = cast(DVec3::new(0.5, 0.5, 0.5), DVec3::new(0.5, 0.5, 5.5));
+        assert_eq!(hit.block_pos(), BlockPos(IVec3::new(0, 0, 3)));
+        assert_eq!(hit.side(), Direction::North);
+
+        // Diagonal rays visit each block they pass through exactly once.
+        let (hit, visited) = cast(DVec3::new(0.5, 0.5, 0.5), DVec3::new(2.5, 2.2, 0.5));
+        assert!(hit.is_missed());
+        assert_eq!(hit.pos(), DVec3::new(2.5, 2.2, 0.5));
+        assert_eq!(hit.block_pos(), BlockPos(IVec3::new(2, 2, 0)));
+        assert_eq!(visited.first(), Some(&IVec3::ZERO));
+        assert_eq!(visited.last(), Some(&IVec3::new(2, 2, 0)));
+        assert_eq!(visited.len(), 5);
+        assert!(visited
+            .windows(2)
+            .all(|w| (w[1] - w[0]).abs().element_sum() == 1));
+    }
+}
@@ -2,12 +2,14 @@
 
 use std::{
     fmt::Debug,
-    ops::{Deref, DerefMut, RangeInclusive},
+    ops::{Deref, DerefMut, Range, RangeInclusive},
 };
 
 use bitvec::{bitbox, boxed::BitBox, slice::BitSlice};
 use rimecraft_voxel_math::direction::Axis;
 
+use crate::{op::BooleanOp, pair::PairList};
+
 trait AbstVoxelSet {
     fn props(&self) -> Props;
 
@@ -47,6 +49,28 @@ impl<'s> VoxelSetSlice<'s> {
             .unwrap_or_default()
     }
 
+    /// Whether this set contains a voxel at the given position, returning
+    /// `false` if the position is out of the set.
+    pub fn in_bounds_and_contains(&self, x: i32, y: i32, z: i32) -> bool {
+        let Props {
+            len_x,
+            len_y,
+            len_z,
+        } = self.inner.props();
+        match (u32::try_from(x), u32::try_from(y), u32::try_from(z)) {
+            (Ok(x), Ok(y), Ok(z)) if x < len_x && y < len_y && z < len_z => self.contains(x, y, z),
+            _ => false,
+        }
+    }
+
+    /// Whether this set contains no voxel.
+    pub fn is_empty(&self) -> bool {
+        Axis::ALL.into_iter().any(|axis| {
+            let bounds = self.bounds_of(axis);
+            bounds.start() >= bounds.end()
+        })
+    }
+
     /// Sets the voxel at given position.
     #[inline]
     pub fn set(&mut self, x: u32, y: u32, z: u32) {
@@ -71,6 +95,68 @@ impl<'s> VoxelSetSlice<'s> {
         self.inner.bounds(axis)
     }
 
+    /// Calls given function with the minimum (inclusive) and maximum (exclusive)
+    /// corners of each box in this set.
+    ///
+    /// Adjacent voxels are coalesced into boxes greedily, along the Z axis first,
+    /// then along the X axis and the Y axis.
+    pub fn for_each_box<F>(&self, mut f: F)
+    where
+        F: FnMut([u32; 3], [u32; 3]),
+    {
+        let props = self.inner.props();
+        let Props {
+            len_x,
+            len_y,
+            len_z,
+        } = props;
+        let mut set = VoxelSet::new(props);
+        for x in 0..len_x {
+            for y in 0..len_y {
+                for z in 0..len_z {
+                    if self.contains(x, y, z) {
+                        set.put(x, y, z, true);
+                    }
+                }
+            }
+        }
+
+        let is_full = |set: &VoxelSet, xs: Range<u32>, y: u32, zs: Range<u32>| {
+            xs.into_iter()
+                .all(|x| zs.clone().all(|z| set.contains(x, y, z)))
+        };
+        for y in 0..len_y {
+            for x in 0..len_x {
+                let mut start = None;
+                for z in 0..=len_z {
+                    if z < len_z && set.contains(x, y, z) {
+                        start.get_or_insert(z);
+                        continue;
+                    }
+                    let Some(z0) = start.take() else {
+                        continue;
+                    };
+                    let mut x1 = x + 1;
+                    while x1 < len_x && is_full(&set, x1..x1 + 1, y, z0..z) {
+                        x1 += 1;
+                    }
+                    let mut y1 = y + 1;
+                    while y1 < len_y && is_full(&set, x..x1, y1, z0..z) {
+                        y1 += 1;
+                    }
+                    for xx in x..x1 {
+                        for yy in y..y1 {
+                            for zz in z0..z {
+                                set.put(xx, yy, zz, false);
+                            }
+                        }
+                    }
+                    f([x, y, z0], [x1, y1, z]);
+                }
+            }
+        }
+    }
+
     /// Crops this set into a cropped slice.
     pub fn crop<'a>(&'a self, bounds: Bounds) -> Cropped<'a, 's> {
         Cropped {
@@ -175,11 +261,63 @@ impl VoxelSet {
         this
     }
 
+    /// Creates a new voxel set with all voxels in given region set.
+    ///
+    /// The region is defined by its minimum (inclusive) and maximum (exclusive) corners.
+    pub fn with_region(props: Props, min: [u32; 3], max: [u32; 3]) -> Self {
+        let mut this = Self::new(props);
+        for x in min[0]..max[0] {
+            for y in min[1]..max[1] {
+                for z in min[2]..max[2] {
+                    this.set(x, y, z);
+                }
+            }
+        }
+        this
+    }
+
+    /// Combines two voxel sets with given boolean function, into a set
+    /// whose voxels are the sections of given pair lists.
+    pub(crate) fn combine(
+        first: &VoxelSetSlice<'_>,
+        second: &VoxelSetSlice<'_>,
+        x: &PairList<'_>,
+        y: &PairList<'_>,
+        z: &PairList<'_>,
+        op: BooleanOp,
+    ) -> Self {
+        let mut this = Self::new(Props {
+            len_x: (x.len() - 1) as u32,
+            len_y: (y.len() - 1) as u32,
+            len_z: (z.len() - 1) as u32,
+        });
+        x.for_each_pair(|x1, x2, xi| {
+            y.for_each_pair(|y1, y2, yi| {
+                z.for_each_pair(|z1, z2, zi| {
+                    if op.apply(
+                        first.in_bounds_and_contains(x1, y1, z1),
+                        second.in_bounds_and_contains(x2, y2, z2),
+                    ) {
+                        this.set(xi, yi, zi);
+                    }
+                })
+            })
+        });
+        this
+    }
+
     /// Converts this set into a boxed slice.
     #[inline]
     pub fn into_boxed_slice(self) -> Box<VoxelSetSlice<'static>> {
         VoxelSetSlice::from_boxed(Box::new(self))
     }
+
+    /// Sets the bit of given position without touching the bounds.
+    #[inline]
+    fn put(&mut self, x: u32, y: u32, z: u32, value: bool) {
+        let index = AbstVoxelSet::index(self, x, y, z);
+        self.data.set(index, value);
+    }
 }
 
 impl AbstVoxelSet for VoxelSet {